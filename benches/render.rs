@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use grid_printer::{GridPrinter, RenderBuffer};
+
+fn numeric_grid(rows: usize, cols: usize) -> Vec<Vec<u64>> {
+    (0..rows)
+        .map(|i| (0..cols).map(|j| (i * cols + j) as u64 * 7919).collect())
+        .collect()
+}
+
+fn bench_render(c: &mut Criterion) {
+    let grid = numeric_grid(1000, 8);
+    let printer = GridPrinter::builder(grid.len(), 8).build();
+
+    c.bench_function("render 1000x8", |b| {
+        b.iter(|| printer.render(black_box(&grid)))
+    });
+
+    let mut out = String::new();
+    c.bench_function("render_into 1000x8", |b| {
+        b.iter(|| {
+            out.clear();
+            printer.render_into(&mut out, black_box(&grid)).unwrap();
+        })
+    });
+
+    let mut out = String::new();
+    let mut buffer = RenderBuffer::new();
+    c.bench_function("render_reusing 1000x8", |b| {
+        b.iter(|| printer.render_reusing(&mut out, &mut buffer, black_box(&grid)))
+    });
+}
+
+fn bench_styled(c: &mut Criterion) {
+    use grid_printer::style::{Fg, StyleOpt};
+
+    let grid = numeric_grid(1000, 8);
+    let printer = GridPrinter::builder(grid.len(), 8)
+        .header(true)
+        .col_style(0, StyleOpt::new().fg(Fg::Cyan)).unwrap()
+        .max_width(60)
+        .build();
+
+    c.bench_function("render styled 1000x8", |b| {
+        b.iter(|| printer.render(black_box(&grid)))
+    });
+}
+
+criterion_group!(benches, bench_render, bench_styled);
+criterion_main!(benches);
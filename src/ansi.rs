@@ -0,0 +1,128 @@
+//! The ansi module provides [`CsiSequence`], a builder for the Control Sequence
+//! Introducer escape sequences the GridPrinter writes: cursor movement, erasing
+//! lines, and SGR styles. It's public so that tools drawing around a grid, e.g.
+//! redrawing it in place, can write the same sequences.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::ansi::{CsiSequence, EraseLine};
+//!
+//! // Move up over a 3 line grid, clearing the first line before redrawing it.
+//! let redraw = format!("{}\r{}", CsiSequence::cursor_up(3), CsiSequence::erase_line(EraseLine::All));
+//! assert_eq!(redraw, "\x1b[3A\r\x1b[2K");
+//!
+//! let warn = CsiSequence::sgr().param(1).param(33);
+//! assert_eq!(format!("{}disk full{}", warn, CsiSequence::sgr_reset()), "\x1b[1;33mdisk full\x1b[0m");
+//! ```
+
+use core::fmt;
+use core::fmt::{Display, Write};
+use alloc::string::String;
+
+/// The part of the line erased by [`CsiSequence::erase_line`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EraseLine {
+    /// From the cursor to the end of the line.
+    ToEnd,
+    /// From the start of the line to the cursor.
+    ToStart,
+    /// The whole line.
+    All,
+}
+
+/// A CSI escape sequence, `ESC [`, followed by parameters separated by `;` and a
+/// final byte. Its `Display` output is the sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsiSequence {
+    params: String,
+    final_byte: char,
+}
+
+impl CsiSequence {
+
+    /// Create a sequence with the final byte `final_byte` and no parameters.
+    pub fn new(final_byte: char) -> Self {
+        Self {
+            params: String::new(),
+            final_byte,
+        }
+    }
+
+    /// Append the parameter `param`.
+    pub fn param<P: Display>(mut self, param: P) -> Self {
+        if !self.params.is_empty() {
+            self.params.push(';');
+        }
+        // Note: Writing to a String can't fail.
+        let _ = write!(self.params, "{}", param);
+
+        self
+    }
+
+    /// Move the cursor up `n` lines.
+    pub fn cursor_up(n: usize) -> Self {
+        Self::new('A').param(n)
+    }
+
+    /// Move the cursor down `n` lines.
+    pub fn cursor_down(n: usize) -> Self {
+        Self::new('B').param(n)
+    }
+
+    /// Move the cursor right `n` columns.
+    pub fn cursor_forward(n: usize) -> Self {
+        Self::new('C').param(n)
+    }
+
+    /// Move the cursor left `n` columns.
+    pub fn cursor_back(n: usize) -> Self {
+        Self::new('D').param(n)
+    }
+
+    /// Move the cursor to column `col` of its line, counting from 1.
+    pub fn cursor_to_column(col: usize) -> Self {
+        Self::new('G').param(col)
+    }
+
+    /// Move the cursor to line `row` and column `col`, counting from 1.
+    pub fn cursor_position(row: usize, col: usize) -> Self {
+        Self::new('H').param(row).param(col)
+    }
+
+    /// Erase the given part of the cursor's line.
+    pub fn erase_line(erase: EraseLine) -> Self {
+        let param = match erase {
+            EraseLine::ToEnd => 0,
+            EraseLine::ToStart => 1,
+            EraseLine::All => 2,
+        };
+        Self::new('K').param(param)
+    }
+
+    /// Set the private mode `mode`, e.g. `1049` for the alternate screen.
+    pub fn set_private_mode(mode: u16) -> Self {
+        Self::new('h').param(format_args!("?{}", mode))
+    }
+
+    /// Reset the private mode `mode`.
+    pub fn reset_private_mode(mode: u16) -> Self {
+        Self::new('l').param(format_args!("?{}", mode))
+    }
+
+    /// Select graphic rendition: set the styles given by the parameters appended,
+    /// e.g. `CsiSequence::sgr().param(31)` for a red foreground.
+    pub fn sgr() -> Self {
+        Self::new('m')
+    }
+
+    /// Reset every style.
+    pub fn sgr_reset() -> Self {
+        Self::sgr().param(0)
+    }
+}
+
+impl Display for CsiSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\x1b[{}{}", self.params, self.final_byte)
+    }
+}
@@ -0,0 +1,419 @@
+//! gridprint, enabled by the `cli` feature, reads CSV, TSV, or JSON from a file or
+//! stdin and prints it as a grid.
+//!
+//! ```bash
+//! $ printf 'item,price\nLamp,24.50\nDesk,180.00\n' | gridprint --header --align price=right
+//! item     price
+//! Lamp     24.50
+//! Desk    180.00
+//! ```
+
+use std::error::Error;
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
+use std::process;
+
+use grid_printer::GridPrinter;
+use grid_printer::column::{Align, ColRef, ColumnSpec};
+use grid_printer::style::{Bg, ColorMode, Fg, Sgr, StyleOpt};
+
+const USAGE: &str = "\
+Usage: gridprint [OPTIONS] [FILE]
+
+Print CSV, TSV, or JSON read from FILE, or from stdin, as a grid.
+
+Options:
+    --input <csv|tsv|json>   The input format, by default from FILE's extension, or csv
+    --header                 Treat the first row as a header row
+    --align <COL=ALIGN>      Align column COL left, right, or center
+    --style <COL=STYLE>      Style column COL, e.g. red, bold, or bg:blue, joined by commas
+    --format <plain|markdown>
+                             The output format, by default plain
+    --max-width <N>          Drop the trailing columns which don't fit in N characters
+    --porcelain              Print each row's cells separated by a single space, unstyled
+                             and unaligned, in a format which is stable across versions
+    -h, --help               Print this help
+
+COL is a column's index, counting from 0, or the name in its header cell.
+A JSON array of objects has a header row of the objects' keys.
+
+With --porcelain, a cell's backslashes, spaces, tabs, and newlines are written as
+\\\\, \\s, \\t, and \\n, and an empty cell as \\0, so a line splits on spaces
+into exactly its cells. The other output options are ignored.";
+
+/// The rows of cells read from the input.
+type Rows = Vec<Vec<String>>;
+
+/// The format of the input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Input {
+    Csv,
+    Tsv,
+    Json,
+}
+
+/// The format of the output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Plain,
+    Markdown,
+}
+
+/// The parsed command line.
+#[derive(Debug)]
+struct Args {
+    file: Option<String>,
+    input: Option<Input>,
+    header: bool,
+    aligns: Vec<(String, Align)>,
+    styles: Vec<(String, StyleOpt)>,
+    format: Format,
+    max_width: Option<usize>,
+    porcelain: bool,
+}
+
+/// Split an option's `COL=VALUE` argument.
+fn col_value(arg: &str) -> Result<(String, &str), String> {
+    match arg.split_once('=') {
+        Some((col, value)) => Ok((col.to_string(), value)),
+        None => Err(format!("expected COL=VALUE, found `{}`", arg)),
+    }
+}
+
+/// Parse a style of comma separated words: a foreground color, a text style, or a
+/// background color prefixed with `bg:`.
+fn parse_style(s: &str) -> Result<StyleOpt, Box<dyn Error>> {
+    let mut style = StyleOpt::new();
+    for word in s.split(',') {
+        style = match word.strip_prefix("bg:") {
+            Some(bg) => style.bg(bg.parse::<Bg>()?),
+            None => match word.parse::<Sgr>() {
+                Ok(sgr) => style.sgr(sgr),
+                Err(_) => style.fg(word.parse::<Fg>()?),
+            },
+        };
+    }
+
+    Ok(style)
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut argv: I) -> Result<Args, Box<dyn Error>> {
+    let mut args = Args {
+        file: None,
+        input: None,
+        header: false,
+        aligns: Vec::new(),
+        styles: Vec::new(),
+        format: Format::Plain,
+        max_width: None,
+        porcelain: false,
+    };
+    while let Some(arg) = argv.next() {
+        let mut value = |name: &str| argv.next().ok_or_else(|| format!("{} expects a value", name));
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+            "--header" => args.header = true,
+            "--input" => args.input = Some(match value("--input")?.as_str() {
+                "csv" => Input::Csv,
+                "tsv" => Input::Tsv,
+                "json" => Input::Json,
+                other => return Err(format!("unknown input format `{}`", other).into()),
+            }),
+            "--align" => {
+                let arg = value("--align")?;
+                let (col, align) = col_value(&arg)?;
+                let align = match align {
+                    "left" => Align::Left,
+                    "right" => Align::Right,
+                    "center" => Align::Center,
+                    other => return Err(format!("unknown alignment `{}`", other).into()),
+                };
+                args.aligns.push((col, align));
+            },
+            "--style" => {
+                let arg = value("--style")?;
+                let (col, style) = col_value(&arg)?;
+                args.styles.push((col, parse_style(style)?));
+            },
+            "--format" => args.format = match value("--format")?.as_str() {
+                "plain" => Format::Plain,
+                "markdown" => Format::Markdown,
+                other => return Err(format!("unknown output format `{}`", other).into()),
+            },
+            "--porcelain" => args.porcelain = true,
+            "--max-width" => args.max_width = Some(value("--max-width")?.parse()?),
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{}`", flag).into()),
+            _ if args.file.is_none() => args.file = Some(arg),
+            _ => return Err(format!("unexpected argument `{}`", arg).into()),
+        }
+    }
+
+    Ok(args)
+}
+
+/// Parse delimited text into rows.
+fn parse_delimited(text: &str, delimiter: u8) -> Result<Rows, Box<dyn Error>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(text.as_bytes());
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        rows.push(record?.iter().map(String::from).collect());
+    }
+
+    Ok(rows)
+}
+
+/// The text of a JSON value in a cell: a string without its quotes, or nothing
+/// for null.
+fn json_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Parse a JSON array of arrays into rows, or an array of objects into rows
+/// following a header row of the keys, returning whether a header row was added.
+fn parse_json(text: &str) -> Result<(Rows, bool), Box<dyn Error>> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(text)?;
+    let mut keys: Vec<String> = Vec::new();
+    for object in values.iter().filter_map(serde_json::Value::as_object) {
+        keys.extend(object.keys().filter(|key| !keys.contains(key)).cloned().collect::<Vec<_>>());
+    }
+    if keys.is_empty() {
+        let rows = values.iter()
+            .map(|row| match row {
+                serde_json::Value::Array(cells) => cells.iter().map(json_cell).collect(),
+                other => vec![json_cell(other)],
+            })
+            .collect();
+        return Ok((rows, false));
+    }
+
+    let mut rows = vec![keys.clone()];
+    for value in values.iter() {
+        let row = keys.iter()
+            .map(|key| value.get(key).map(json_cell).unwrap_or_default())
+            .collect();
+        rows.push(row);
+    }
+
+    Ok((rows, true))
+}
+
+/// Escape a cell for `--porcelain` output, so that it holds no spaces and isn't
+/// empty.
+fn porcelain_cell(cell: &str) -> String {
+    if cell.is_empty() {
+        return "\\0".to_string();
+    }
+    let mut escaped = String::with_capacity(cell.len());
+    for c in cell.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ' ' => escaped.push_str("\\s"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Write the rows for `--porcelain` output: each row's escaped cells separated by a
+/// single space.
+fn porcelain(rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|cell| porcelain_cell(cell)).collect();
+        out.push_str(&cells.join(" "));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Refer to a column by its index, if `col` is a number, or else by its name.
+fn col_ref(col: &str) -> ColRef {
+    match col.parse::<usize>() {
+        Ok(idx) => ColRef::Index(idx),
+        Err(_) => ColRef::from(col),
+    }
+}
+
+/// The index of the column `col` among the columns `names`, or an error if there
+/// is no such column.
+fn col_idx(col: &str, names: &[String]) -> Result<usize, String> {
+    let idx = match col_ref(col) {
+        ColRef::Index(idx) => Some(idx),
+        ColRef::Name(name) => names.iter().position(|n| *n == name),
+    };
+
+    idx.filter(|idx| *idx < names.len()).ok_or_else(|| format!("no column `{}`", col))
+}
+
+/// Render the input `text` as set by `args`.
+fn render(args: &Args, text: &str) -> Result<String, Box<dyn Error>> {
+    let input = args.input.unwrap_or_else(|| match args.file.as_deref() {
+        Some(path) if path.ends_with(".tsv") => Input::Tsv,
+        Some(path) if path.ends_with(".json") => Input::Json,
+        _ => Input::Csv,
+    });
+    let (mut rows, keys_header) = match input {
+        Input::Csv => (parse_delimited(text, b',')?, false),
+        Input::Tsv => (parse_delimited(text, b'\t')?, false),
+        Input::Json => parse_json(text)?,
+    };
+    if args.porcelain {
+        return Ok(porcelain(&rows));
+    }
+    // Note: A markdown table always has a header row.
+    let header = args.header || keys_header || args.format == Format::Markdown;
+
+    let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in rows.iter_mut() {
+        row.resize(cols, String::new());
+        if args.format == Format::Markdown {
+            for cell in row.iter_mut() {
+                *cell = cell.replace('|', "\\|");
+            }
+        }
+    }
+
+    let mut builder = GridPrinter::builder(rows.len(), cols)
+        .header(header)
+        .color_mode(ColorMode::Auto);
+    let names: Vec<String> = (0..cols)
+        .map(|idx| match (header, rows.first()) {
+            (true, Some(first)) => first[idx].clone(),
+            _ => format!("Column {}", idx + 1),
+        })
+        .collect();
+    if !args.aligns.is_empty() {
+        let mut specs: Vec<ColumnSpec> = names.iter().map(|name| ColumnSpec::new(name)).collect();
+        for (col, align) in args.aligns.iter() {
+            specs[col_idx(col, &names)?].align = *align;
+        }
+        builder = builder.columns(specs)?;
+    }
+    for (col, style) in args.styles.iter() {
+        builder = builder.col_style(col_idx(col, &names)?, style.clone())?;
+    }
+    if let Some(max_width) = args.max_width {
+        builder = builder.max_width(max_width);
+    }
+    if args.format == Format::Markdown {
+        builder = builder
+            .col_spacing(3)
+            .col_separator('|')
+            .header_separator('-')
+            .color_mode(ColorMode::Never);
+    }
+
+    Ok(builder.try_build()?.render(&rows))
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let args = parse_args(std::env::args().skip(1))?;
+    let mut text = String::new();
+    match args.file.as_deref() {
+        None | Some("-") => io::stdin().read_to_string(&mut text)?,
+        Some(path) => File::open(path)?.read_to_string(&mut text)?,
+    };
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    stdout.write_all(render(&args, &text)?.as_bytes())?;
+
+    Ok(stdout.flush()?)
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("gridprint: {}", err);
+        process::exit(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_porcelain_cell() {
+        assert_eq!(porcelain_cell("a b\\c"), "a\\sb\\\\c");
+        assert_eq!(porcelain_cell(""), "\\0");
+    }
+
+    const CSV: &str = "item,price\nLamp,24.50\nDesk,180.00\n";
+
+    fn args(argv: &[&str]) -> Result<Args, Box<dyn Error>> {
+        parse_args(argv.iter().map(|arg| arg.to_string()))
+    }
+
+    /// Drop the escape codes of a styled grid, which depend on whether stdout is a
+    /// terminal.
+    fn unstyled(grid: &str) -> String {
+        let mut out = String::new();
+        let mut chars = grid.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => { chars.by_ref().find(|c| *c == 'm'); },
+                c => out.push(c),
+            }
+        }
+
+        out
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args(&["--align", "price=right", "--style", "0=red,bold,bg:blue", "--format", "markdown", "in.csv"]).unwrap();
+        assert_eq!(parsed.aligns, vec![("price".to_string(), Align::Right)]);
+        assert_eq!(parsed.styles, vec![("0".to_string(), StyleOpt::new().fg(Fg::Red).sgr(Sgr::Bold).bg(Bg::Blue))]);
+        assert_eq!(parsed.format, Format::Markdown);
+        assert_eq!(parsed.file.as_deref(), Some("in.csv"));
+
+        assert!(args(&["--align", "price=up"]).is_err());
+        assert!(args(&["--align", "price"]).is_err());
+        assert!(args(&["--style", "price=nocolor"]).is_err());
+        assert!(args(&["--format", "html"]).is_err());
+        assert!(args(&["--align"]).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = render(&args(&["--header", "--align", "price=right"]).unwrap(), CSV).unwrap();
+        assert_eq!(grid, "item   price  \nLamp   24.50  \nDesk  180.00  \n");
+
+        let grid = render(&args(&["--format", "markdown", "--align", "1=right"]).unwrap(), CSV).unwrap();
+        assert_eq!(grid, "item |  price   \n---- | ------   \nLamp |  24.50   \nDesk | 180.00   \n");
+
+        let grid = render(&args(&["--header", "--style", "price=red", "--style", "0=bold"]).unwrap(), CSV).unwrap();
+        assert_eq!(unstyled(&grid), "item  price   \nLamp  24.50   \nDesk  180.00  \n");
+
+        let err = render(&args(&["--header", "--style", "nope=red"]).unwrap(), CSV).unwrap_err();
+        assert_eq!(err.to_string(), "no column `nope`");
+        assert!(render(&args(&["--style", "2=red"]).unwrap(), CSV).is_err());
+        assert!(render(&args(&["--align", "item=left"]).unwrap(), CSV).is_err());
+    }
+
+    #[test]
+    fn test_parse_json_objects() {
+        let (rows, header) = parse_json(r#"[{"name": "Lamp", "price": 24.5}, {"name": "Desk", "sku": null}]"#).unwrap();
+        assert!(header);
+        assert_eq!(rows, vec![
+            vec!["name", "price", "sku"],
+            vec!["Lamp", "24.5", ""],
+            vec!["Desk", "", ""],
+        ]);
+    }
+}
@@ -0,0 +1,128 @@
+//! The calendar module provides [`CalendarGrid`], which lays out the days of a
+//! month as a week-per-row grid printed with a GridPrinter.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::calendar::CalendarGrid;
+//!
+//! let calendar = CalendarGrid::month(2024, 2).unwrap();
+//! assert_eq!(calendar.render_plain(), concat!(
+//!     "Mo Tu We Th Fr Sa Su\n",
+//!     "          1  2  3  4\n",
+//!     " 5  6  7  8  9 10 11\n",
+//!     "12 13 14 15 16 17 18\n",
+//!     "19 20 21 22 23 24 25\n",
+//!     "26 27 28 29\n",
+//! ));
+//! ```
+
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+use crate::{GridPrinter, GridPrinterErr};
+use crate::format::days_from_civil;
+use crate::style::{Fg, Sgr, StyleOpt};
+
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// The days of a single month, in weeks starting on Monday.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarGrid {
+    year: i32,
+    month: u32,
+    header_style: StyleOpt,
+    weekend_style: StyleOpt,
+}
+
+impl CalendarGrid {
+
+    /// Lay out `month` (1 to 12) of `year`, with bold weekday headers and the
+    /// weekend in red.
+    pub fn month(year: i32, month: u32) -> Result<Self, GridPrinterErr> {
+        if !(1..=12).contains(&month) {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+
+        Ok(Self {
+            year,
+            month,
+            header_style: StyleOpt::new().sgr(Sgr::Bold),
+            weekend_style: StyleOpt::new().fg(Fg::Red),
+        })
+    }
+
+    /// Set the style of the weekday headers.
+    pub fn header_style(self, header_style: StyleOpt) -> Self {
+        Self { header_style, ..self }
+    }
+
+    /// Set the style of the Saturday and Sunday columns.
+    pub fn weekend_style(self, weekend_style: StyleOpt) -> Self {
+        Self { weekend_style, ..self }
+    }
+
+    /// The number of days in the month.
+    fn days(&self) -> u32 {
+        let leap = (self.year % 4 == 0 && self.year % 100 != 0) || self.year % 400 == 0;
+        match self.month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// The rows of the calendar: the weekday headers, then one row per week with
+    /// the day numbers right aligned. Days before the first of the month are blank,
+    /// and the last week ends with the last day of the month.
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        let first = days_from_civil(i64::from(self.year), i64::from(self.month), 1);
+        // 1970-01-01 was a Thursday, the fourth day of a week starting on Monday.
+        let offset = (first + 3).rem_euclid(7) as usize;
+
+        let mut rows = vec![WEEKDAYS.iter().map(|weekday| weekday.to_string()).collect()];
+        let mut week = vec![String::new(); offset];
+        for day in 1..=self.days() {
+            week.push(format!("{:>2}", day));
+            if week.len() == WEEKDAYS.len() {
+                rows.push(core::mem::take(&mut week));
+            }
+        }
+        if !week.is_empty() {
+            rows.push(week);
+        }
+
+        rows
+    }
+
+    /// The GridPrinter the calendar is printed with.
+    pub fn printer(&self) -> GridPrinter {
+        let rows = self.rows();
+        GridPrinter::builder(rows.len(), WEEKDAYS.len())
+            .col_spacing(1)
+            .header(true)
+            .highlight_rows(&[0], self.header_style.clone())
+            .col_style(5, self.weekend_style.clone()).unwrap()
+            .col_style(6, self.weekend_style.clone()).unwrap()
+            .build()
+    }
+
+    /// Render the calendar into a `String`, styled.
+    pub fn render(&self) -> String {
+        self.printer().render(&self.rows())
+    }
+
+    /// Render the calendar into a `String` without styles or trailing spaces, as
+    /// [`GridPrinter::render_plain`] does.
+    pub fn render_plain(&self) -> String {
+        self.printer().render_plain(&self.rows())
+    }
+
+    /// Print the calendar to stdout.
+    #[cfg(feature = "std")]
+    pub fn print(&self) {
+        self.printer().print(&self.rows());
+    }
+}
@@ -0,0 +1,150 @@
+//! The cell module provides the [`CellSource`] trait, which lets cell values lend
+//! their text to a GridPrinter directly instead of being formatted into a new
+//! `String` for every cell, and the [`RowTuple`] trait for rows of tuples.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//!
+//! let lines: Vec<Vec<&str>> = "a b c\nd e f"
+//!     .lines()
+//!     .map(|line| line.split(' ').collect())
+//!     .collect();
+//!
+//! // `&str` cells are borrowed during both measuring and rendering.
+//! let printer = GridPrinter::new(lines.len(), 3);
+//! printer.print_cells(&lines);
+//! ```
+
+use core::fmt::Display;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+
+/// A cell value which can produce its text, borrowing it when possible.
+///
+/// String-like types lend their contents directly, while other types (numbers,
+/// `char`, `bool`) are formatted once into an owned `String`.
+pub trait CellSource {
+    /// The text of the cell.
+    fn cell(&self) -> Cow<'_, str>;
+
+    /// The declared width of a cell printed verbatim, as [`RawText`](crate::raw::RawText)
+    /// is, or `None` for ordinary text, which is measured and sanitized.
+    fn raw_width(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl CellSource for str {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl CellSource for String {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_str())
+    }
+}
+
+impl CellSource for Cow<'_, str> {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_ref())
+    }
+}
+
+impl CellSource for Box<str> {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_ref())
+    }
+}
+
+impl CellSource for Rc<str> {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_ref())
+    }
+}
+
+impl CellSource for Arc<str> {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_ref())
+    }
+}
+
+impl<T: CellSource + ?Sized> CellSource for &T {
+    fn cell(&self) -> Cow<'_, str> {
+        (**self).cell()
+    }
+
+    fn raw_width(&self) -> Option<usize> {
+        (**self).raw_width()
+    }
+}
+
+macro_rules! impl_cell_source_display {
+    ($($t:ty),*) => {
+        $(
+            impl CellSource for $t {
+                fn cell(&self) -> Cow<'_, str> {
+                    Cow::Owned(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_cell_source_display!(
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64, char, bool
+);
+
+/// A row of heterogeneous cells, implemented for tuples of up to 12 elements which
+/// each implement `Display`, so typed rows can be printed without stringifying
+/// them into a `Vec<String>` first.
+///
+/// ```rust
+/// use grid_printer::GridPrinter;
+///
+/// let cars = [
+///     ("Ford", "Pinto", 1978, 750.0),
+///     ("Toyota", "Tacoma", 2006, 15475.23),
+/// ];
+/// let printer = GridPrinter::new(cars.len(), 4);
+/// printer.print_tuples(&cars);
+/// ```
+pub trait RowTuple {
+    /// The number of cells in the row.
+    const LEN: usize;
+
+    /// Hand each cell of the row, with its column index, to `f`.
+    fn for_each_cell(&self, f: &mut dyn FnMut(usize, &dyn Display));
+}
+
+macro_rules! impl_row_tuple {
+    ($len:expr; $($idx:tt $t:ident),+) => {
+        impl<$($t: Display),+> RowTuple for ($($t,)+) {
+            const LEN: usize = $len;
+
+            fn for_each_cell(&self, f: &mut dyn FnMut(usize, &dyn Display)) {
+                $(f($idx, &self.$idx);)+
+            }
+        }
+    };
+}
+
+impl_row_tuple!(1; 0 A);
+impl_row_tuple!(2; 0 A, 1 B);
+impl_row_tuple!(3; 0 A, 1 B, 2 C);
+impl_row_tuple!(4; 0 A, 1 B, 2 C, 3 D);
+impl_row_tuple!(5; 0 A, 1 B, 2 C, 3 D, 4 E);
+impl_row_tuple!(6; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+impl_row_tuple!(7; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+impl_row_tuple!(8; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+impl_row_tuple!(9; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I);
+impl_row_tuple!(10; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J);
+impl_row_tuple!(11; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K);
+impl_row_tuple!(12; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K, 11 L);
@@ -0,0 +1,169 @@
+//! The code module, enabled by the `code` feature, colors a column of code: diff
+//! lines by their `+` or `-` prefix, or a line of source with simple syntax
+//! highlighting, as in a table of files and their changes. Set it on a column with
+//! [`col_format`](crate::GridPrinterBuilder::col_format).
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::code::CodeStyle;
+//!
+//! let grid = vec![
+//!     vec!["src/lib.rs", "+    let width = 0;"],
+//!     vec!["src/lib.rs", "-    let width = 1;"],
+//! ];
+//! let printer = GridPrinter::builder(2, 2)
+//!     .col_format(1, CodeStyle::Diff).unwrap()
+//!     .build();
+//! printer.print(&grid);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::style::{Fg, Sgr, StyleOpt};
+
+/// The keywords highlighted by [`CodeStyle::Syntax`], common to Rust, C, Go,
+/// JavaScript, and Python.
+const KEYWORDS: [&str; 48] = [
+    "as", "async", "await", "break", "case", "class", "const", "continue", "def",
+    "default", "do", "else", "enum", "extern", "false", "fn", "for", "from", "func",
+    "if", "impl", "import", "in", "let", "loop", "match", "mod", "mut", "new", "null",
+    "package", "pub", "return", "self", "static", "struct", "switch", "trait", "true",
+    "type", "use", "var", "void", "where", "while", "with", "yield", "None",
+];
+
+/// How a column of code is colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodeStyle {
+    /// Color each line by its diff prefix: added `+` lines green, removed `-` lines
+    /// red, and `@@` hunk headers cyan. The `+++` and `---` file headers are bold.
+    Diff,
+    /// Color the keywords, string and character literals, numbers, and `//` or `#`
+    /// comments of a line of source code.
+    Syntax,
+}
+
+impl CodeStyle {
+
+    /// The style of the whole of `cell`, for diff lines.
+    pub(crate) fn style(&self, cell: &str) -> Option<StyleOpt> {
+        if *self != CodeStyle::Diff {
+            return None;
+        }
+        match () {
+            _ if cell.starts_with("+++") || cell.starts_with("---") => Some(StyleOpt::new().sgr(Sgr::Bold)),
+            _ if cell.starts_with("@@") => Some(StyleOpt::new().fg(Fg::Cyan)),
+            _ if cell.starts_with('+') => Some(StyleOpt::new().fg(Fg::Green)),
+            _ if cell.starts_with('-') => Some(StyleOpt::new().fg(Fg::Red)),
+            _ => None,
+        }
+    }
+
+    /// The byte ranges of `text` to highlight, in order, with their styles, for
+    /// source code.
+    pub(crate) fn spans(&self, text: &str) -> Vec<(usize, usize, StyleOpt)> {
+        match self {
+            CodeStyle::Diff => Vec::new(),
+            CodeStyle::Syntax => syntax_spans(text),
+        }
+    }
+}
+
+/// The end of the quoted literal starting at `start`, after its closing quote, or
+/// `None` if it isn't closed on the line. A `'` following `&`, `<`, or a word,
+/// e.g. a Rust lifetime, doesn't start a literal.
+fn literal_end(text: &str, start: usize, quote: char) -> Option<usize> {
+    let prev = text[..start].chars().next_back();
+    if quote == '\'' && prev.is_some_and(|c| c == '&' || c == '<' || c.is_alphanumeric()) {
+        return None;
+    }
+    let mut escaped = false;
+    for (idx, c) in text[start + 1..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return Some(start + 1 + idx + 1),
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Split `text` into the spans highlighted by [`CodeStyle::Syntax`].
+fn syntax_spans(text: &str) -> Vec<(usize, usize, StyleOpt)> {
+    let mut spans = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let (end, fg) = match c {
+            '#' => (text.len(), Fg::BrightBlack),
+            '/' if text[start..].starts_with("//") => (text.len(), Fg::BrightBlack),
+            '"' | '\'' | '`' => match literal_end(text, start, c) {
+                Some(end) => (end, Fg::Green),
+                None => continue,
+            },
+            c if c.is_ascii_digit() || c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((idx, c)) = chars.peek().copied() {
+                    if !(c.is_alphanumeric() || c == '_' || (c == '.' && text[start..idx].chars().all(|c| c.is_ascii_digit()))) {
+                        break;
+                    }
+                    end = idx + c.len_utf8();
+                    chars.next();
+                }
+                let word = &text[start..end];
+                match () {
+                    _ if c.is_ascii_digit() => (end, Fg::Yellow),
+                    _ if KEYWORDS.contains(&word) => (end, Fg::Magenta),
+                    _ => continue,
+                }
+            },
+            _ => continue,
+        };
+        spans.push((start, end, StyleOpt::new().fg(fg)));
+        while chars.peek().is_some_and(|(idx, _)| *idx < end) {
+            chars.next();
+        }
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_syntax_spans() {
+        let text = "let s = \"a\\\"b\"; // 2";
+        let spans: Vec<(&str, StyleOpt)> = syntax_spans(text).into_iter()
+            .map(|(start, end, style)| (&text[start..end], style))
+            .collect();
+        assert_eq!(spans, vec![
+            ("let", StyleOpt::new().fg(Fg::Magenta)),
+            ("\"a\\\"b\"", StyleOpt::new().fg(Fg::Green)),
+            ("// 2", StyleOpt::new().fg(Fg::BrightBlack)),
+        ]);
+
+        let text = "fn f<'a>(x: &'a str) -> f64 { 1.5 }";
+        let words: Vec<&str> = syntax_spans(text).into_iter().map(|(start, end, _)| &text[start..end]).collect();
+        assert_eq!(words, vec!["fn", "1.5"]);
+    }
+
+    #[test]
+    fn test_render_code_col() {
+        let printer = crate::GridPrinter::builder(2, 1)
+            .col_format(0, CodeStyle::Syntax).unwrap()
+            .build();
+        let (magenta, reset) = (Fg::Magenta.escape_code(), Fg::Reset.escape_code());
+        assert_eq!(printer.render(&[vec!["if x"], vec!["x"]]), alloc::format!("{magenta}if{reset} x  \nx     \n"));
+
+        let printer = crate::GridPrinter::builder(1, 1)
+            .col_format(0, CodeStyle::Diff).unwrap()
+            .build();
+        let (green, reset) = (Fg::Green.escape_code(), Fg::Reset.escape_code());
+        assert_eq!(printer.render(&[vec!["+x"]]), alloc::format!("{green}+x{reset}  \n"));
+    }
+}
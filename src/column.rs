@@ -0,0 +1,190 @@
+//! The column module provides [`ColumnSpec`], which describes a column in one
+//! place — its name, description, alignment, width, style, and format — rather
+//! than across a builder call per option, each taking the column's index.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::column::{Align, ColumnSpec};
+//! use grid_printer::format::Humanize;
+//! use grid_printer::style::{Fg, StyleOpt};
+//!
+//! let grid = vec![
+//!     vec!["item", "price", "sku", "size"],
+//!     vec!["Lamp", "24.50", "L-100", "1288490"],
+//!     vec!["Desk", "180.00", "D-220", "90123"],
+//! ];
+//! let printer = GridPrinter::builder(grid.len(), 4)
+//!     .header(true)
+//!     .columns(vec![
+//!         ColumnSpec::new("Item").description("The product name"),
+//!         ColumnSpec::new("Price").align(Align::Right).width(8).style(StyleOpt::new().fg(Fg::Green)),
+//!         ColumnSpec::new("SKU").hidden(true),
+//!         ColumnSpec::new("Size").format(Humanize::Bytes),
+//!     ])
+//!     .unwrap()
+//!     .build();
+//! printer.print(&grid);
+//! ```
+
+use alloc::string::{String, ToString};
+
+use crate::format::ColFormat;
+use crate::options::SortOrder;
+use crate::style::StyleOpt;
+
+/// A column, referred to by its index or by its name. A name is looked up when the
+/// grid is printed, among the names of the [`ColumnSpec`]s and then the cells of
+/// the header row, so the configuration follows the column if the source's
+/// columns are reordered.
+///
+/// Integers and strings convert into a ColRef, so methods taking one accept
+/// either, e.g. `col_style(2, style)` or `col_style("Price", style)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColRef {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for ColRef {
+    fn from(idx: usize) -> Self {
+        ColRef::Index(idx)
+    }
+}
+
+impl From<&str> for ColRef {
+    fn from(name: &str) -> Self {
+        ColRef::Name(name.to_string())
+    }
+}
+
+impl From<String> for ColRef {
+    fn from(name: String) -> Self {
+        ColRef::Name(name)
+    }
+}
+
+/// An option set for a column referred to by name, applied to the column once its
+/// name is looked up. A sort key keeps its priority among the sort keys.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum NamedCol {
+    Style(StyleOpt),
+    Sort(SortOrder, usize),
+    Hide,
+}
+
+/// How a cell is placed within the width of its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+impl Align {
+
+    /// The padding before a cell, out of the `slack` columns it doesn't fill.
+    pub(crate) fn lead(&self, slack: usize) -> usize {
+        match self {
+            Align::Left => 0,
+            Align::Right => slack,
+            Align::Center => slack / 2,
+        }
+    }
+}
+
+/// What happens to the cells of a column which are wider than it: set per column
+/// with [`col_overflow`](crate::GridPrinterBuilder::col_overflow).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    /// Size the column to its widest cell, cutting cells only when the column is
+    /// narrowed to fit the maximum width.
+    #[default]
+    Resize,
+    /// Limit the column to the given width, cutting the cells which are wider with
+    /// the truncation marker.
+    Truncate(usize),
+    /// Wrap the cells which are wider than the column onto further lines of their
+    /// row, narrowing the column first when the grid is wider than the maximum width.
+    Wrap,
+}
+
+/// The description of a column. Set for every column at once with
+/// [`columns`](crate::GridPrinterBuilder::columns).
+///
+/// The name is printed in place of the source's header cell when the printer has
+/// a header row, and labels the column's cells for screen readers when it doesn't.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnSpec {
+    pub name: String,
+    pub description: Option<String>,
+    pub align: Align,
+    pub width: Option<usize>,
+    pub style: Option<StyleOpt>,
+    pub format: Option<ColFormat>,
+    pub hidden: bool,
+}
+
+impl ColumnSpec {
+
+    /// Create a left aligned, visible column named `name`, as wide as its widest cell.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: None,
+            align: Align::Left,
+            width: None,
+            style: None,
+            format: None,
+            hidden: false,
+        }
+    }
+
+    /// Describe what the column holds, e.g. for a `--help` listing of the columns.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+
+        self
+    }
+
+    /// Set how the column's cells are aligned.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+
+        self
+    }
+
+    /// Fix the width of the column, truncating the cells which are wider.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+
+        self
+    }
+
+    /// Set the style of the column.
+    pub fn style(mut self, style: StyleOpt) -> Self {
+        self.style = Some(style);
+
+        self
+    }
+
+    /// Set the conversion applied to the column's data cells.
+    pub fn format<C: Into<ColFormat>>(mut self, format: C) -> Self {
+        self.format = Some(format.into());
+
+        self
+    }
+
+    /// Leave the column out of the printed grid.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+
+        self
+    }
+}
@@ -0,0 +1,172 @@
+//! Columns appended to the grid at print time, computed from the formatted cells
+//! of the source columns rather than stored in the source.
+
+use core::fmt;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+
+use crate::GridPrinter;
+
+const HISTOGRAM_BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// A function computing the cell of a derived column from the cells before it.
+pub(crate) type DeriveFn = Arc<dyn Fn(&[String]) -> String + Send + Sync>;
+
+/// A column computed from the other columns of the grid. Columns computed by a
+/// function cannot be serialized.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum DerivedCol {
+    /// Each row's share of the total of the numeric column at the index.
+    PercentOf(usize),
+    /// The rank of each row by the numeric column at the index, largest first.
+    Rank(usize),
+    /// A header, and a function of the cells before the column in each data row.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Fn(String, DeriveFn),
+}
+
+impl fmt::Debug for DerivedCol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivedCol::PercentOf(idx) => f.debug_tuple("PercentOf").field(idx).finish(),
+            DerivedCol::Rank(idx) => f.debug_tuple("Rank").field(idx).finish(),
+            DerivedCol::Fn(header, _) => f.debug_tuple("Fn").field(header).finish_non_exhaustive(),
+        }
+    }
+}
+
+impl DerivedCol {
+
+    /// The cells of the column for the formatted rows of `buff`, the header cell
+    /// first when `header` is set. The column is computed from the first `before`
+    /// cells of each row.
+    fn cells(&self, buff: &[Vec<String>], header: bool, before: usize) -> Vec<String> {
+        let (header, data) = match header && !buff.is_empty() {
+            true => (Some(&buff[0]), &buff[1..]),
+            false => (None, buff),
+        };
+        let mut cells: Vec<String> = match self {
+            DerivedCol::PercentOf(idx) => {
+                let values: Vec<Option<f64>> = data.iter()
+                    .map(|row| row.get(*idx).and_then(|cell| cell.trim().parse::<f64>().ok()))
+                    .collect();
+                let total: f64 = values.iter().flatten().sum();
+                let percents: Vec<String> = values.iter()
+                    .map(|value| match value {
+                        Some(value) if total != 0.0 => format!("{:.1}%", value / total * 100.0),
+                        _ => String::new(),
+                    })
+                    .collect();
+                // Note: Right align the percentages so that their decimal points line up.
+                let width = percents.iter().map(String::len).max().unwrap_or(0);
+                let percents = percents.into_iter().map(|percent| format!("{:>1$}", percent, width));
+                match header {
+                    None => percents.collect(),
+                    Some(header) => {
+                        let name = header.get(*idx).map_or("", String::as_str);
+                        core::iter::once(format!("% of {}", name)).chain(percents).collect()
+                    },
+                }
+            },
+            DerivedCol::Rank(idx) => {
+                let values: Vec<Option<f64>> = data.iter()
+                    .map(|row| row.get(*idx).and_then(|cell| cell.trim().parse::<f64>().ok()))
+                    .collect();
+                // Note: Tied rows share a rank, and the ranks after them are skipped.
+                let ranks: Vec<String> = values.iter()
+                    .map(|value| match value {
+                        Some(value) => (1 + values.iter().flatten().filter(|other| *other > value).count()).to_string(),
+                        None => String::new(),
+                    })
+                    .collect();
+                let width = ranks.iter().map(String::len).max().unwrap_or(0);
+                let ranks = ranks.into_iter().map(|rank| format!("{:>1$}", rank, width));
+                match header {
+                    None => ranks.collect(),
+                    Some(_) => core::iter::once(String::from("Rank")).chain(ranks).collect(),
+                }
+            },
+            DerivedCol::Fn(name, f) => {
+                let values = data.iter().map(|row| f(&row[..before]));
+                match header {
+                    None => values.collect(),
+                    Some(_) => core::iter::once(name.clone()).chain(values).collect(),
+                }
+            },
+        };
+        cells.resize(buff.len(), String::new());
+
+        cells
+    }
+}
+
+/// Draw the distribution of `values` as `buckets` block characters, each as tall
+/// as the count of values in its range relative to the fullest bucket.
+fn histogram(values: &[f64], buckets: usize) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if values.is_empty() || buckets == 0 {
+        return String::new();
+    }
+    let mut counts = vec![0usize; buckets];
+    for value in values {
+        let bucket = match max > min {
+            true => ((value - min) / (max - min) * buckets as f64) as usize,
+            false => 0,
+        };
+        counts[bucket.min(buckets - 1)] += 1;
+    }
+    let fullest = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    counts.iter()
+        .map(|count| HISTOGRAM_BLOCKS[(count * 8).div_ceil(fullest)])
+        .collect()
+}
+
+impl GridPrinter {
+
+    /// The number of columns taken from the source, before the derived columns.
+    pub(crate) fn source_cols(&self) -> usize {
+        self.cols - self.derived_cols.len()
+    }
+
+    /// The index of the rank column among the printed columns, if there is one.
+    pub(crate) fn rank_col(&self) -> Option<usize> {
+        self.derived_cols.iter()
+            .position(|derived_col| matches!(derived_col, DerivedCol::Rank(_)))
+            .map(|k| self.source_cols() + k)
+    }
+
+    /// The footer row of histograms beneath the columns configured with one, if any.
+    pub(crate) fn footer_row<S: AsRef<str>>(&self, buff: &[Vec<S>]) -> Option<Vec<String>> {
+        if self.col_footer_histograms.is_empty() {
+            return None;
+        }
+        let data = &buff[usize::from(self.header).min(buff.len())..];
+        let mut footer = vec![String::new(); self.cols];
+        for (idx, buckets) in self.col_footer_histograms.iter().filter(|(idx, _)| **idx < self.cols) {
+            let values: Vec<f64> = data.iter()
+                .filter_map(|row| row.get(*idx).and_then(|cell| cell.as_ref().trim().parse::<f64>().ok()))
+                .filter(|value| value.is_finite())
+                .collect();
+            footer[*idx] = histogram(&values, *buckets);
+        }
+
+        Some(footer)
+    }
+
+    /// Fill in the derived columns of the formatted rows.
+    pub(crate) fn derive_cols(&self, buff: &mut [Vec<String>]) {
+        let source_cols = self.source_cols();
+        for (k, derived_col) in self.derived_cols.iter().enumerate() {
+            let cells = derived_col.cells(buff, self.header, source_cols + k);
+            for (row, cell) in buff.iter_mut().zip(cells) {
+                row[source_cols + k] = cell;
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! The fixed module provides [`FixedGridPrinter`], a GridPrinter whose number of
+//! columns is part of its type, so that printing rows of the wrong length is a
+//! compile error rather than silently dropped or blank cells, and
+//! [`SmallGridPrinter`], a minimal printer for tables whose rows and columns are
+//! both known at compile time, which prints without allocating.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::fixed::FixedGridPrinter;
+//!
+//! let rows = [
+//!     ["Ford", "Pinto", "1978"],
+//!     ["Toyota", "Tacoma", "2006"],
+//! ];
+//! let printer = FixedGridPrinter::<3>::new(rows.len());
+//! printer.print(&rows);
+//! ```
+//!
+//! Rows of any other length are rejected at compile time:
+//! ```compile_fail
+//! use grid_printer::fixed::FixedGridPrinter;
+//!
+//! let printer = FixedGridPrinter::<3>::new(1);
+//! printer.print(&[["Ford", "Pinto"]]);
+//! ```
+
+use core::fmt;
+use core::fmt::Display;
+use core::convert::TryFrom;
+use alloc::vec::Vec;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::{GridPrinter, GridPrinterBuilder, GridPrinterErr};
+use crate::raw;
+use crate::width::{self, EmojiWidth};
+use crate::sink::Sink;
+#[cfg(feature = "std")]
+use crate::sink::IoSink;
+
+/// A GridPrinter for rows of exactly `COLS` cells.
+#[derive(Debug, Clone)]
+pub struct FixedGridPrinter<const COLS: usize> {
+    printer: GridPrinter,
+}
+
+impl<const COLS: usize> FixedGridPrinter<COLS> {
+
+    /// Create a FixedGridPrinter for `rows` rows with the default configuration.
+    pub fn new(rows: usize) -> Self {
+        Self { printer: GridPrinter::new(rows, COLS) }
+    }
+
+    /// Create a builder for `rows` rows of `COLS` columns. Build it into a
+    /// GridPrinter and convert that with `try_from` to keep the column count in
+    /// the type.
+    ///
+    /// ```rust
+    /// use std::convert::TryFrom;
+    /// use grid_printer::fixed::FixedGridPrinter;
+    ///
+    /// let builder = FixedGridPrinter::<2>::builder(1).col_spacing(1);
+    /// let printer = FixedGridPrinter::<2>::try_from(builder.build()).unwrap();
+    /// assert_eq!(printer.render(&[[1, 2]]), "1 2 \n");
+    /// ```
+    pub fn builder(rows: usize) -> GridPrinterBuilder {
+        GridPrinterBuilder::new(rows, COLS)
+    }
+
+    /// The underlying GridPrinter.
+    pub fn printer(&self) -> &GridPrinter {
+        &self.printer
+    }
+
+    /// Render the rows into a [`Sink`], one line at a time.
+    pub fn render_into<S: Sink, F: Display>(&self, sink: &mut S, source: &[[F; COLS]]) -> Result<(), S::Error> {
+        let printer = &self.printer;
+        let opts = &printer.print_options;
+        let buff: Vec<Vec<String>> = (0..printer.row_limit(opts))
+            .map(|i| printer.format_row(source.get(i).map(|row| &row[..]), i == 0 && printer.header))
+            .collect();
+        printer.for_each_line(&buff, opts, |line| sink.write_line_ending(line, printer.line_ending.as_str()))
+    }
+
+    /// Render the rows into a `String`.
+    pub fn render<F: Display>(&self, source: &[[F; COLS]]) -> String {
+        let mut out = String::new();
+        let Ok(()) = self.render_into(&mut out, source);
+
+        out
+    }
+
+    /// Print the rows to stdout.
+    #[cfg(feature = "std")]
+    pub fn print<F: Display>(&self, source: &[[F; COLS]]) {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_into(&mut sink, source).unwrap();
+        sink.0.flush().unwrap();
+    }
+}
+
+/// Convert a GridPrinter built for `COLS` columns, failing with
+/// [`GridPrinterErr::DimensionErr`] for any other number of columns.
+impl<const COLS: usize> TryFrom<GridPrinter> for FixedGridPrinter<COLS> {
+    type Error = GridPrinterErr;
+
+    fn try_from(printer: GridPrinter) -> Result<Self, Self::Error> {
+        match printer.cols == COLS {
+            true => Ok(Self { printer }),
+            false => Err(GridPrinterErr::DimensionErr),
+        }
+    }
+}
+
+impl GridPrinter {
+
+    /// Create a [`SmallGridPrinter`] for tables of exactly `R` rows of `C` columns.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let printer = GridPrinter::fixed::<2, 3>().col_spacing(1);
+    /// let mut out = String::new();
+    /// printer.render_to(&mut out, &[["GET", "/", "200"], ["POST", "/login", "302"]]).unwrap();
+    /// assert_eq!(out, "GET  /      200 \nPOST /login 302 \n");
+    /// ```
+    pub fn fixed<const R: usize, const C: usize>() -> SmallGridPrinter<R, C> {
+        SmallGridPrinter::new()
+    }
+}
+
+/// A printer for tables of exactly `R` rows of `C` columns, for latency-sensitive
+/// logging. The column widths are kept in arrays and the cells are measured without
+/// being formatted into strings, so printing cells of printable ASCII, such as
+/// numbers, never allocates.
+///
+/// Only the column spacing and emoji width are configurable; styles, headers, and
+/// the rest of the GridPrinter configuration are not supported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmallGridPrinter<const R: usize, const C: usize> {
+    col_spacing: usize,
+    emoji_width: EmojiWidth,
+}
+
+impl<const R: usize, const C: usize> Default for SmallGridPrinter<R, C> {
+    fn default() -> Self {
+        Self {
+            col_spacing: 2,
+            emoji_width: EmojiWidth::default(),
+        }
+    }
+}
+
+impl<const R: usize, const C: usize> SmallGridPrinter<R, C> {
+
+    /// Create a SmallGridPrinter with the default column spacing of 2.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the column spacing.
+    pub fn col_spacing(self, col_spacing: usize) -> Self {
+        Self { col_spacing, ..self }
+    }
+
+    /// Set how many columns wide emoji are drawn.
+    pub fn emoji_width(self, emoji_width: EmojiWidth) -> Self {
+        Self { emoji_width, ..self }
+    }
+
+    /// Measure the width of every cell, and of every column.
+    fn measure<F: Display>(&self, source: &[[F; C]; R]) -> ([[usize; C]; R], [usize; C]) {
+        let mut cell_widths = [[0; C]; R];
+        let mut col_widths = [0; C];
+        for (row, widths) in source.iter().zip(cell_widths.iter_mut()) {
+            for ((cell, width), col_width) in row.iter().zip(widths.iter_mut()).zip(col_widths.iter_mut()) {
+                *width = width::display_width_of(cell, self.emoji_width);
+                *col_width = (*col_width).max(*width);
+            }
+        }
+
+        (cell_widths, col_widths)
+    }
+
+    /// The width of each column of `source`, excluding the column spacing.
+    pub fn widths<F: Display>(&self, source: &[[F; C]; R]) -> [usize; C] {
+        self.measure(source).1
+    }
+
+    /// Write the table to `w`, each line terminated by `\n`.
+    pub fn render_to<W: fmt::Write, F: Display>(&self, w: &mut W, source: &[[F; C]; R]) -> fmt::Result {
+        let (cell_widths, col_widths) = self.measure(source);
+        for (row, widths) in source.iter().zip(cell_widths.iter()) {
+            for ((cell, width), col_width) in row.iter().zip(widths.iter()).zip(col_widths.iter()) {
+                fmt::Write::write_fmt(&mut raw::StripMarkers(&mut *w), format_args!("{}", cell))?;
+                for _ in *width..*col_width + self.col_spacing {
+                    w.write_char(' ')?;
+                }
+            }
+            w.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+
+    /// Print the table to stdout.
+    #[cfg(feature = "std")]
+    pub fn print<F: Display>(&self, source: &[[F; C]; R]) {
+        /// Adapts stdout to `fmt::Write`, keeping any I/O error.
+        struct Stdout<'a>(io::StdoutLock<'a>, io::Result<()>);
+
+        impl fmt::Write for Stdout<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.1 = self.0.write_all(s.as_bytes());
+                self.1.as_ref().map_err(|_| fmt::Error).copied()
+            }
+        }
+
+        let stdout = io::stdout();
+        let mut out = Stdout(stdout.lock(), Ok(()));
+        if self.render_to(&mut out, source).is_err() {
+            out.1.unwrap();
+        }
+        out.0.flush().unwrap();
+    }
+}
@@ -0,0 +1,1009 @@
+//! The format module provides the per-column conversions applied to cell values
+//! before they are measured, such as rendering boolean values as check marks,
+//! status keywords as colored badges, byte counts in binary units, percentages
+//! colored by thresholds, amounts of money, or numbers in scientific notation.
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+use crate::progress;
+use crate::style::{Bg, Fg, StyleOpt};
+#[cfg(feature = "code")]
+use crate::code::CodeStyle;
+
+/// The most decimals a number is written with; an `f64` holds no more than 17
+/// significant digits.
+pub const MAX_DECIMALS: usize = 17;
+
+/// How the values of a boolean column are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoolStyle {
+    /// Render `✓` and `✗`.
+    Check,
+    /// Render `yes` and `no`.
+    YesNo,
+    /// Render a green `●` and a red `○`.
+    Dots,
+}
+
+impl BoolStyle {
+
+    /// Parse a common boolean spelling, ignoring case and surrounding whitespace.
+    /// Values which are not recognized return `None`.
+    ///
+    /// ```rust
+    /// use grid_printer::format::BoolStyle;
+    ///
+    /// assert_eq!(BoolStyle::parse("True"), Some(true));
+    /// assert_eq!(BoolStyle::parse(" 0 "), Some(false));
+    /// assert_eq!(BoolStyle::parse("y"), Some(true));
+    /// assert_eq!(BoolStyle::parse("maybe"), None);
+    /// ```
+    pub fn parse(s: &str) -> Option<bool> {
+        let s = s.trim();
+        const TRUTHY: [&str; 9] = ["true", "t", "yes", "y", "on", "1", "✓", "✔", "●"];
+        const FALSEY: [&str; 9] = ["false", "f", "no", "n", "off", "0", "✗", "✘", "○"];
+        match () {
+            _ if TRUTHY.iter().any(|t| t.eq_ignore_ascii_case(s)) => Some(true),
+            _ if FALSEY.iter().any(|f| f.eq_ignore_ascii_case(s)) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// The text rendered for `value`.
+    pub fn text(&self, value: bool) -> &'static str {
+        match (self, value) {
+            (BoolStyle::Check, true) => "✓",
+            (BoolStyle::Check, false) => "✗",
+            (BoolStyle::YesNo, true) => "yes",
+            (BoolStyle::YesNo, false) => "no",
+            (BoolStyle::Dots, true) => "●",
+            (BoolStyle::Dots, false) => "○",
+        }
+    }
+
+    /// Render `cell` in this style, leaving values which do not parse as a boolean
+    /// unchanged.
+    pub(crate) fn apply<'a>(&self, cell: Cow<'a, str>) -> Cow<'a, str> {
+        match BoolStyle::parse(&cell) {
+            None => cell,
+            Some(value) => Cow::Borrowed(self.text(value)),
+        }
+    }
+
+    /// The color of an already rendered `cell`, for the styles which are colored.
+    pub(crate) fn style(&self, cell: &str) -> Option<StyleOpt> {
+        match self {
+            BoolStyle::Dots if cell == self.text(true) => Some(StyleOpt::new().fg(Fg::Green)),
+            BoolStyle::Dots if cell == self.text(false) => Some(StyleOpt::new().fg(Fg::Red)),
+            _ => None,
+        }
+    }
+}
+
+/// Renders status keywords as padded, uppercase badges with a background color
+/// chosen by the keyword, e.g. a green ` OK ` or a red ` FAIL `.
+///
+/// The default renderer knows the common keywords: `ok`, `pass`, `passed`,
+/// `success` and `healthy` are green; `warn` and `warning` are yellow; `fail`,
+/// `failed` and `error` are red.
+///
+/// ```rust
+/// use grid_printer::format::StatusRenderer;
+/// use grid_printer::style::Bg;
+///
+/// let status = StatusRenderer::default()
+///     .keyword("degraded", Bg::Yellow);
+/// assert_eq!(status.render("Degraded"), Some(" DEGRADED ".to_string()));
+/// assert_eq!(status.render("unknown"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatusRenderer {
+    keywords: Vec<(String, Bg)>,
+}
+
+impl Default for StatusRenderer {
+    fn default() -> Self {
+        let keywords = [
+            ("ok", Bg::Green),
+            ("pass", Bg::Green),
+            ("passed", Bg::Green),
+            ("success", Bg::Green),
+            ("healthy", Bg::Green),
+            ("warn", Bg::Yellow),
+            ("warning", Bg::Yellow),
+            ("fail", Bg::Red),
+            ("failed", Bg::Red),
+            ("error", Bg::Red),
+        ];
+        Self {
+            keywords: keywords.iter().map(|(keyword, bg)| (keyword.to_string(), bg.clone())).collect(),
+        }
+    }
+}
+
+impl StatusRenderer {
+
+    /// Create a StatusRenderer which knows no keywords.
+    pub fn new() -> Self {
+        Self { keywords: Vec::new() }
+    }
+
+    /// Render `keyword`, matched ignoring case, with the background color `bg`,
+    /// replacing any color it was given before.
+    pub fn keyword(mut self, keyword: &str, bg: Bg) -> Self {
+        self.keywords.retain(|(known, _)| !known.eq_ignore_ascii_case(keyword));
+        self.keywords.push((keyword.to_string(), bg));
+
+        self
+    }
+
+    /// The background color of `keyword`, ignoring case and surrounding whitespace.
+    fn color(&self, keyword: &str) -> Option<&Bg> {
+        let keyword = keyword.trim();
+        self.keywords.iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(keyword))
+            .map(|(_, bg)| bg)
+    }
+
+    /// Render `cell` as a badge, or return `None` if it is not a known keyword.
+    pub fn render(&self, cell: &str) -> Option<String> {
+        self.color(cell).map(|_| format!(" {} ", cell.trim().to_uppercase()))
+    }
+
+    /// The style of an already rendered badge.
+    pub(crate) fn style(&self, cell: &str) -> Option<StyleOpt> {
+        self.color(cell).map(|bg| StyleOpt::new().fg(Fg::Black).bg(bg.clone()))
+    }
+}
+
+/// Renders raw numbers in a human readable form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Humanize {
+    /// A count of bytes in binary units, e.g. `1.2 GiB`.
+    Bytes,
+    /// A count of seconds in its two largest units, e.g. `2h 13m`.
+    Duration,
+    /// A number with a metric suffix, e.g. `1.2M`.
+    Number,
+    /// A Unix timestamp in seconds, or an RFC 3339 timestamp, relative to `now`
+    /// (in Unix seconds), e.g. `3 minutes ago` or `in 2 days`.
+    RelativeTime { now: i64 },
+}
+
+impl Humanize {
+
+    /// Render `cell`, or return `None` if it is not a number this conversion
+    /// accepts.
+    ///
+    /// ```rust
+    /// use grid_printer::format::Humanize;
+    ///
+    /// assert_eq!(Humanize::Bytes.render("1288490188").as_deref(), Some("1.2 GiB"));
+    /// assert_eq!(Humanize::Duration.render("7980").as_deref(), Some("2h 13m"));
+    /// assert_eq!(Humanize::Number.render("1234567").as_deref(), Some("1.2M"));
+    /// assert_eq!(Humanize::Number.render("n/a"), None);
+    ///
+    /// let now = 1_700_000_000;
+    /// assert_eq!(Humanize::RelativeTime { now }.render("1699999820").as_deref(), Some("3 minutes ago"));
+    /// assert_eq!(Humanize::RelativeTime { now }.render("2023-11-16T22:13:20Z").as_deref(), Some("in 2 days"));
+    /// ```
+    pub fn render(&self, cell: &str) -> Option<String> {
+        if let Humanize::RelativeTime { now } = self {
+            let cell = cell.trim();
+            let time = match cell.parse::<f64>() {
+                Ok(time) if time.is_finite() => time as i64,
+                _ => rfc3339_seconds(cell)?,
+            };
+            return Some(Humanize::relative(time.checked_sub(*now)?));
+        }
+
+        let value: f64 = cell.trim().parse().ok()?;
+        if !value.is_finite() {
+            return None;
+        }
+        match self {
+            Humanize::Bytes if value < 0.0 => None,
+            Humanize::Bytes => Some(Humanize::scale(value, 1024.0, &[" B", " KiB", " MiB", " GiB", " TiB", " PiB", " EiB"])),
+            Humanize::Number if value < 0.0 => Some(format!("-{}", Humanize::scale(-value, 1000.0, &["", "K", "M", "B", "T"]))),
+            Humanize::Number => Some(Humanize::scale(value, 1000.0, &["", "K", "M", "B", "T"])),
+            Humanize::Duration if value < 0.0 => None,
+            Humanize::Duration => Some(Humanize::duration(value)),
+            Humanize::RelativeTime { .. } => None,
+        }
+    }
+
+    /// Divide `value` by `base` until it fits the largest unit possible. Whole
+    /// values in the smallest unit are written without a fraction.
+    fn scale(mut value: f64, base: f64, units: &[&str]) -> String {
+        let mut unit = 0;
+        while value >= base && unit + 1 < units.len() {
+            value /= base;
+            unit += 1;
+        }
+        // Note: Rounding may carry the value up to the next unit.
+        let rounded: f64 = format!("{:.1}", value).parse().unwrap_or(value);
+        if rounded >= base && unit + 1 < units.len() {
+            value /= base;
+            unit += 1;
+        }
+        match unit {
+            0 if value == (value as u64) as f64 => format!("{}{}", value, units[0]),
+            _ => format!("{:.1}{}", value, units[unit]),
+        }
+    }
+
+    /// Write an offset in seconds from now in its largest unit.
+    fn relative(offset: i64) -> String {
+        let seconds = offset.unsigned_abs();
+        let units = [("year", 31_536_000), ("month", 2_592_000), ("day", 86_400), ("hour", 3_600), ("minute", 60), ("second", 1)];
+        let (unit, n) = match units.iter().find(|(_, size)| seconds >= *size) {
+            None => return "just now".to_string(),
+            Some((unit, size)) => (unit, seconds / size),
+        };
+        let plural = match n {
+            1 => "",
+            _ => "s",
+        };
+        match offset < 0 {
+            true => format!("{} {}{} ago", n, unit, plural),
+            false => format!("in {} {}{}", n, unit, plural),
+        }
+    }
+
+    /// Write a number of seconds in its two largest units.
+    fn duration(seconds: f64) -> String {
+        if seconds < 1.0 {
+            return format!("{}ms", (seconds * 1000.0) as u64);
+        }
+        let seconds = seconds as u64;
+        let units = [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+        let parts: Vec<String> = units.iter()
+            .scan(seconds, |rest, (unit, size)| {
+                let n = *rest / size;
+                *rest %= size;
+                Some((n, unit))
+            })
+            .skip_while(|(n, _)| *n == 0)
+            .take(2)
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, unit)| format!("{}{}", n, unit))
+            .collect();
+
+        parts.join(" ")
+    }
+}
+
+/// Renders numbers in exponent notation, for values of a wide dynamic range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Notation {
+    /// Scientific notation with the given number of decimals, e.g. `1.23e6`.
+    Scientific(usize),
+    /// Engineering notation with the given number of decimals, its exponent a
+    /// multiple of 3, e.g. `12.35e-6`.
+    Engineering(usize),
+}
+
+impl Notation {
+
+    /// Render `cell`, or return `None` if it is not a number.
+    ///
+    /// ```rust
+    /// use grid_printer::format::Notation;
+    ///
+    /// assert_eq!(Notation::Scientific(2).render("1234567").as_deref(), Some("1.23e6"));
+    /// assert_eq!(Notation::Scientific(1).render("-0.00042").as_deref(), Some("-4.2e-4"));
+    /// assert_eq!(Notation::Engineering(2).render("0.0000123456").as_deref(), Some("12.35e-6"));
+    /// assert_eq!(Notation::Engineering(1).render("999960").as_deref(), Some("1.0e6"));
+    /// assert_eq!(Notation::Engineering(2).render("n/a"), None);
+    /// ```
+    pub fn render(&self, cell: &str) -> Option<String> {
+        let value: f64 = cell.trim().parse().ok()?;
+        if !value.is_finite() {
+            return None;
+        }
+        match self {
+            Notation::Scientific(decimals) => Some(format!("{:.*e}", decimals.min(&MAX_DECIMALS), value)),
+            Notation::Engineering(decimals) => {
+                let decimals = *decimals.min(&MAX_DECIMALS);
+                let exp: i32 = format!("{:e}", value).split_once('e')?.1.parse().ok()?;
+                let exp = exp.div_euclid(3) * 3;
+                let mantissa = format!("{:.*}", decimals, Notation::shift(value, -exp));
+                // Note: Rounding may carry the mantissa up to the next multiple of 3.
+                match mantissa.trim_start_matches('-').split('.').next().map_or(0, str::len) > 3 {
+                    true => Some(format!("{:.*}e{}", decimals, Notation::shift(value, -exp - 3), exp + 3)),
+                    false => Some(format!("{}e{}", mantissa, exp)),
+                }
+            },
+        }
+    }
+
+    /// Multiply `value` by 10 to the power of `exp`.
+    fn shift(value: f64, exp: i32) -> f64 {
+        // Note: Scale in two halves, since 10^exp alone overflows for subnormal values.
+        let half = exp / 2;
+        [half, exp - half].iter().fold(value, |value, exp| {
+            let scale = (0..exp.unsigned_abs()).fold(1.0, |scale, _| scale * 10.0);
+            match *exp < 0 {
+                true => value / scale,
+                false => value * scale,
+            }
+        })
+    }
+}
+
+/// Renders percentages, colored green, yellow, or red by thresholds, optionally
+/// after a bar of their value, e.g. `█████░░░  62%`. Cells may be written with or
+/// without a `%`.
+///
+/// By default values from `70` are yellow and from `90` red. If the warning
+/// threshold is above the critical one, lower values are worse instead, e.g. for
+/// free disk space.
+///
+/// ```rust
+/// use grid_printer::format::Percent;
+///
+/// let percent = Percent::new().bar(8);
+/// assert_eq!(percent.render("62.4%").as_deref(), Some("████▉░░░  62%"));
+///
+/// let percent = Percent::new().fraction().decimals(1);
+/// assert_eq!(percent.render("0.1234").as_deref(), Some("12.3%"));
+/// assert_eq!(percent.render("n/a"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Percent {
+    warn: f64,
+    critical: f64,
+    decimals: usize,
+    fraction: bool,
+    bar_width: Option<usize>,
+}
+
+impl Default for Percent {
+    fn default() -> Self {
+        Self {
+            warn: 70.0,
+            critical: 90.0,
+            decimals: 0,
+            fraction: false,
+            bar_width: None,
+        }
+    }
+}
+
+impl Percent {
+
+    /// Create a Percent with the default thresholds, `70` and `90`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Color values from `warn` yellow and from `critical` red, or, if `warn` is
+    /// above `critical`, values up to `warn` yellow and up to `critical` red.
+    pub fn thresholds(mut self, warn: f64, critical: f64) -> Self {
+        self.warn = warn;
+        self.critical = critical;
+
+        self
+    }
+
+    /// Write values with `decimals` digits after the decimal point, at most
+    /// [`MAX_DECIMALS`].
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals.min(MAX_DECIMALS);
+
+        self
+    }
+
+    /// Take values written without a `%` as fractions of 1, e.g. `0.42` for `42%`.
+    pub fn fraction(mut self) -> Self {
+        self.fraction = true;
+
+        self
+    }
+
+    /// Draw a bar `width` columns wide before each value.
+    pub fn bar(mut self, width: usize) -> Self {
+        self.bar_width = Some(width);
+
+        self
+    }
+
+    /// The percentage written in `cell`, scaled from a fraction if need be.
+    fn parse(&self, cell: &str) -> Option<f64> {
+        let cell = cell.trim();
+        let value: f64 = match cell.strip_suffix('%') {
+            Some(number) => number.trim_end().parse().ok()?,
+            None if self.fraction => cell.parse::<f64>().ok()? * 100.0,
+            None => cell.parse().ok()?,
+        };
+
+        Some(value).filter(|value| value.is_finite())
+    }
+
+    /// Render `cell`, or return `None` if it is not a number.
+    pub fn render(&self, cell: &str) -> Option<String> {
+        let value = self.parse(cell)?;
+        // Note: Deserialized options don't pass through the setter's clamp.
+        let decimals = self.decimals.min(MAX_DECIMALS);
+        // Note: The width of the value is fixed so that the bars of a column line up.
+        match self.bar_width {
+            None => Some(format!("{:.*}%", decimals, value)),
+            Some(width) => {
+                let value_width = decimals + if decimals > 0 { 4 } else { 3 };
+                let bar = progress::bar(value / 100.0, width);
+                Some(format!("{} {:>2$.3$}%", bar, value, value_width, decimals))
+            },
+        }
+    }
+
+    /// The color of an already rendered `cell`, by the value after its bar.
+    pub(crate) fn style(&self, cell: &str) -> Option<StyleOpt> {
+        let value = cell.rsplit(' ').next()?.strip_suffix('%')?.parse::<f64>().ok()?;
+        let (warn, critical) = match self.warn > self.critical {
+            true => (value <= self.warn, value <= self.critical),
+            false => (value >= self.warn, value >= self.critical),
+        };
+        let fg = match (warn, critical) {
+            (_, true) => Fg::Red,
+            (true, false) => Fg::Yellow,
+            (false, false) => Fg::Green,
+        };
+
+        Some(StyleOpt::new().fg(fg))
+    }
+}
+
+/// How negative amounts of a currency column are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NegativeStyle {
+    /// A leading minus sign, e.g. `-$123.45`.
+    Minus,
+    /// Accounting style parentheses, e.g. `($123.45)`.
+    Parens,
+    /// A leading minus sign, with the amount colored red.
+    Red,
+}
+
+/// Renders amounts of money with a currency symbol, thousands separators, and a
+/// fixed number of decimals, rounding the amount, e.g. `$1,234.50`. Set with
+/// [`col_currency`](crate::GridPrinterBuilder::col_currency).
+///
+/// Cells may be written with or without the symbol, and negative amounts with a
+/// minus sign or in parentheses.
+///
+/// ```rust
+/// use grid_printer::format::{CurrencyOpts, NegativeStyle};
+///
+/// let usd = CurrencyOpts::default();
+/// assert_eq!(usd.render("1234.5").as_deref(), Some("$1,234.50"));
+/// assert_eq!(usd.render("-0.004").as_deref(), Some("$0.00"));
+///
+/// let usd = usd.negative(NegativeStyle::Parens);
+/// assert_eq!(usd.render("-123.456").as_deref(), Some("($123.46)"));
+///
+/// let eur = CurrencyOpts::new("€").symbol_after().separators('.', ',');
+/// assert_eq!(eur.render("-1234567.891").as_deref(), Some("-1.234.567,89 €"));
+/// assert_eq!(eur.render("n/a"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurrencyOpts {
+    symbol: String,
+    symbol_after: bool,
+    thousands: Option<char>,
+    decimal: char,
+    decimals: usize,
+    negative: NegativeStyle,
+}
+
+impl Default for CurrencyOpts {
+    fn default() -> Self {
+        Self::new("$")
+    }
+}
+
+impl CurrencyOpts {
+
+    /// Write amounts with `symbol` before them, `,` between thousands, and `.`
+    /// before two decimals.
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            symbol_after: false,
+            thousands: Some(','),
+            decimal: '.',
+            decimals: 2,
+            negative: NegativeStyle::Minus,
+        }
+    }
+
+    /// Write the symbol after amounts, separated by a space, e.g. `12,00 €`.
+    pub fn symbol_after(mut self) -> Self {
+        self.symbol_after = true;
+
+        self
+    }
+
+    /// Separate thousands with `thousands` and the decimals with `decimal`, e.g.
+    /// `'.'` and `','` for `1.234,50`.
+    pub fn separators(mut self, thousands: char, decimal: char) -> Self {
+        self.thousands = Some(thousands);
+        self.decimal = decimal;
+
+        self
+    }
+
+    /// Write amounts without a thousands separator.
+    pub fn no_thousands(mut self) -> Self {
+        self.thousands = None;
+
+        self
+    }
+
+    /// Round amounts to `decimals` digits after the decimal separator, at most
+    /// [`MAX_DECIMALS`].
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals.min(MAX_DECIMALS);
+
+        self
+    }
+
+    /// Write negative amounts in the given style.
+    pub fn negative(mut self, negative: NegativeStyle) -> Self {
+        self.negative = negative;
+
+        self
+    }
+
+    /// The amount written in `cell`, with or without the symbol.
+    fn parse(&self, cell: &str) -> Option<f64> {
+        let cell = cell.trim();
+        let (cell, parens) = match cell.strip_prefix('(').and_then(|cell| cell.strip_suffix(')')) {
+            Some(cell) => (cell.trim(), true),
+            None => (cell, false),
+        };
+        let (sign, cell) = match cell.strip_prefix('-') {
+            Some(cell) => (-1.0, cell),
+            None => (1.0, cell),
+        };
+        let cell = match self.symbol.is_empty() {
+            true => cell,
+            false => cell.strip_prefix(self.symbol.as_str())
+                .or_else(|| cell.strip_suffix(self.symbol.as_str()))
+                .unwrap_or(cell),
+        };
+        let value: f64 = cell.trim().parse().ok()?;
+        let value = match parens {
+            true => -value.abs(),
+            false => sign * value,
+        };
+
+        Some(value).filter(|value| value.is_finite())
+    }
+
+    /// Render `cell`, or return `None` if it is not an amount.
+    pub fn render(&self, cell: &str) -> Option<String> {
+        let value = self.parse(cell)?;
+        // Note: Deserialized options don't pass through the setter's clamp.
+        let digits = format!("{:.*}", self.decimals.min(MAX_DECIMALS), value.abs());
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+        // Note: An amount which rounds to zero is written without a sign.
+        let negative = value < 0.0 && digits.bytes().any(|b| matches!(b, b'1'..=b'9'));
+
+        let mut amount = String::new();
+        for (idx, digit) in whole.chars().enumerate() {
+            if idx > 0 && (whole.len() - idx) % 3 == 0 {
+                amount.extend(self.thousands);
+            }
+            amount.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            amount.push(self.decimal);
+            amount.push_str(fraction);
+        }
+        let amount = match self.symbol_after {
+            true => format!("{} {}", amount, self.symbol),
+            false => format!("{}{}", self.symbol, amount),
+        };
+
+        match (negative, self.negative) {
+            (false, _) => Some(amount),
+            (true, NegativeStyle::Parens) => Some(format!("({})", amount)),
+            (true, NegativeStyle::Minus | NegativeStyle::Red) => Some(format!("-{}", amount)),
+        }
+    }
+
+    /// The color of an already rendered negative amount, for the red style.
+    pub(crate) fn style(&self, cell: &str) -> Option<StyleOpt> {
+        match self.negative {
+            NegativeStyle::Red if cell.starts_with('-') => Some(StyleOpt::new().fg(Fg::Red)),
+            _ => None,
+        }
+    }
+}
+
+/// Parse an RFC 3339 timestamp, e.g. `2023-11-14T22:13:20.5+01:00`, into Unix
+/// seconds. Fractions of a second are dropped. Years past 9999, and offsets of a
+/// day or more, are rejected.
+fn rfc3339_seconds(s: &str) -> Option<i64> {
+    fn number(s: &str) -> Option<i64> {
+        match s.bytes().all(|b| b.is_ascii_digit()) && !s.is_empty() {
+            true => s.parse().ok(),
+            false => None,
+        }
+    }
+
+    let (date, time) = s.split_at(s.find(['T', 't', ' '])?);
+    let time = &time[1..];
+    let mut date = date.splitn(3, '-');
+    let (year, month, day) = (number(date.next()?)?, number(date.next()?)?, number(date.next()?)?);
+
+    let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+        None => return None,
+        Some(i) => time.split_at(i),
+    };
+    let offset = match offset {
+        "Z" | "z" => 0,
+        _ => {
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let (hours, minutes) = (number(hours)?, number(minutes)?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let seconds = hours * 3_600 + minutes * 60;
+            match offset.starts_with('-') {
+                true => -seconds,
+                false => seconds,
+            }
+        }
+    };
+    let time = time.split('.').next()?;
+    let mut time = time.splitn(3, ':');
+    let (hour, minute, second) = (number(time.next()?)?, number(time.next()?)?, number(time.next()?)?);
+    if year > 9999 || !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second - offset)
+}
+
+/// The number of days from 1970-01-01 to the given date, after Howard Hinnant's
+/// `days_from_civil`.
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// A family of units the values of a column are measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Units {
+    /// Durations, from `ns` to `h`, scaled between `ns`, `µs`, `ms`, and `s`.
+    Time,
+    /// Sizes, in decimal or binary units from `B` to `TiB`, scaled between `B`,
+    /// `KB`, `MB`, `GB`, and `TB`.
+    Bytes,
+}
+
+impl Units {
+
+    /// The units values may be written in, with their size in the family's base
+    /// unit, seconds or bytes.
+    fn parsed(&self) -> &'static [(&'static str, f64)] {
+        match self {
+            Units::Time => &[
+                ("ns", 1e-9), ("us", 1e-6), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.0),
+                ("sec", 1.0), ("min", 60.0), ("h", 3_600.0),
+            ],
+            Units::Bytes => &[
+                ("B", 1.0), ("KB", 1e3), ("kB", 1e3), ("MB", 1e6), ("GB", 1e9), ("TB", 1e12),
+                ("KiB", 1_024.0), ("MiB", 1_048_576.0), ("GiB", 1_073_741_824.0), ("TiB", 1_099_511_627_776.0),
+            ],
+        }
+    }
+
+    /// The units values are scaled to, smallest first.
+    fn scaled(&self) -> &'static [(&'static str, f64)] {
+        match self {
+            Units::Time => &[("ns", 1e-9), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.0)],
+            Units::Bytes => &[("B", 1.0), ("KB", 1e3), ("MB", 1e6), ("GB", 1e9), ("TB", 1e12)],
+        }
+    }
+
+    /// The size of `unit`, if it is one of this family's.
+    fn size(&self, unit: &str) -> Option<f64> {
+        self.parsed().iter().find(|(name, _)| *name == unit).map(|(_, size)| *size)
+    }
+}
+
+/// Scales the values of a column, written with or without units, to the common
+/// unit which reads best, and notes that unit in the header. Set with
+/// [`col_units`](crate::GridPrinterBuilder::col_units).
+///
+/// The common unit is the largest in which every non-zero value is at least 1.
+/// Cells which are not a number in a known unit are left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnitScale {
+    units: Units,
+    base: Option<String>,
+}
+
+impl UnitScale {
+
+    /// Scale values of the given family of units. Values without a unit are taken
+    /// to be in seconds or bytes.
+    pub fn new(units: Units) -> Self {
+        Self { units, base: None }
+    }
+
+    /// Take values without a unit to be in `base`, e.g. `"ms"`. A base unit outside
+    /// the family leaves such values unchanged.
+    pub fn base(mut self, base: &str) -> Self {
+        self.base = Some(base.to_string());
+
+        self
+    }
+
+    /// The value of `cell` in the family's base unit, seconds or bytes.
+    fn parse(&self, cell: &str) -> Option<f64> {
+        let cell = cell.trim();
+        let split = cell.find(|c: char| c.is_alphabetic()).unwrap_or(cell.len());
+        let (number, unit) = cell.split_at(split);
+        let value: f64 = number.trim().parse().ok()?;
+        let size = match (unit, self.base.as_deref()) {
+            ("", Some(base)) => self.units.size(base)?,
+            ("", None) => 1.0,
+            (unit, _) => self.units.size(unit)?,
+        };
+
+        Some(value * size).filter(|value| value.is_finite())
+    }
+
+    /// Scale the data cells of the column at `idx` of the formatted rows to their
+    /// common unit, and append that unit to the header, if `header` is set.
+    pub(crate) fn scale_col(&self, buff: &mut [Vec<String>], idx: usize, header: bool) {
+        let offset = usize::from(header).min(buff.len());
+        let values: Vec<Option<f64>> = buff[offset..].iter()
+            .map(|row| row.get(idx).and_then(|cell| self.parse(cell)))
+            .collect();
+        let smallest = values.iter()
+            .flatten()
+            .map(|value| value.abs())
+            .filter(|value| *value > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        let scaled = self.units.scaled();
+        let (unit, size) = scaled.iter()
+            .rev()
+            .find(|(_, size)| *size <= smallest)
+            .unwrap_or(&scaled[0]);
+
+        let scaled_values: Vec<Option<String>> = values.iter()
+            .map(|value| value.map(|value| format!("{:.1}", value / size)))
+            .collect();
+        // Note: The fractions are dropped when every value is whole in the common unit.
+        let whole = scaled_values.iter().flatten().all(|value| value.ends_with(".0"));
+        for (row, value) in buff[offset..].iter_mut().zip(scaled_values) {
+            if let Some(mut value) = value {
+                if whole {
+                    value.truncate(value.len() - 2);
+                }
+                row[idx] = value;
+            }
+        }
+        if let Some(cell) = buff.first_mut().filter(|_| header).and_then(|row| row.get_mut(idx)) {
+            cell.push_str(&format!(" ({})", unit));
+        }
+    }
+}
+
+/// A conversion applied to every cell of a column, excluding the header row. Set
+/// with [`col_format`](crate::GridPrinterBuilder::col_format).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColFormat {
+    /// Render boolean values in the given style.
+    Bool(BoolStyle),
+    /// Render status keywords as colored badges.
+    Status(StatusRenderer),
+    /// Render raw numbers in a human readable form.
+    Humanize(Humanize),
+    /// Render numbers in scientific or engineering notation.
+    Notation(Notation),
+    /// Render percentages colored by thresholds.
+    Percent(Percent),
+    /// Render amounts of money.
+    Currency(CurrencyOpts),
+    /// Color diff lines or source code.
+    #[cfg(feature = "code")]
+    Code(CodeStyle),
+}
+
+impl From<BoolStyle> for ColFormat {
+    fn from(bool_style: BoolStyle) -> Self {
+        ColFormat::Bool(bool_style)
+    }
+}
+
+impl From<StatusRenderer> for ColFormat {
+    fn from(status: StatusRenderer) -> Self {
+        ColFormat::Status(status)
+    }
+}
+
+impl From<Humanize> for ColFormat {
+    fn from(humanize: Humanize) -> Self {
+        ColFormat::Humanize(humanize)
+    }
+}
+
+impl From<Notation> for ColFormat {
+    fn from(notation: Notation) -> Self {
+        ColFormat::Notation(notation)
+    }
+}
+
+impl From<Percent> for ColFormat {
+    fn from(percent: Percent) -> Self {
+        ColFormat::Percent(percent)
+    }
+}
+
+impl From<CurrencyOpts> for ColFormat {
+    fn from(currency: CurrencyOpts) -> Self {
+        ColFormat::Currency(currency)
+    }
+}
+
+#[cfg(feature = "code")]
+impl From<CodeStyle> for ColFormat {
+    fn from(code: CodeStyle) -> Self {
+        ColFormat::Code(code)
+    }
+}
+
+impl ColFormat {
+
+    /// Convert `cell`, leaving values the conversion does not recognize unchanged.
+    pub(crate) fn apply<'a>(&self, cell: Cow<'a, str>) -> Cow<'a, str> {
+        let converted = match self {
+            ColFormat::Bool(bool_style) => return bool_style.apply(cell),
+            ColFormat::Status(status) => status.render(&cell),
+            ColFormat::Humanize(humanize) => humanize.render(&cell),
+            ColFormat::Notation(notation) => notation.render(&cell),
+            ColFormat::Percent(percent) => percent.render(&cell),
+            ColFormat::Currency(currency) => currency.render(&cell),
+            #[cfg(feature = "code")]
+            ColFormat::Code(_) => None,
+        };
+        match converted {
+            None => cell,
+            Some(converted) => Cow::Owned(converted),
+        }
+    }
+
+    /// The style of an already converted `cell`, which takes precedence over the
+    /// column style.
+    pub(crate) fn style(&self, cell: &str) -> Option<StyleOpt> {
+        match self {
+            ColFormat::Bool(bool_style) => bool_style.style(cell),
+            ColFormat::Status(status) => status.style(cell),
+            ColFormat::Percent(percent) => percent.style(cell),
+            ColFormat::Currency(currency) => currency.style(cell),
+            ColFormat::Humanize(_) | ColFormat::Notation(_) => None,
+            #[cfg(feature = "code")]
+            ColFormat::Code(code) => code.style(cell),
+        }
+    }
+
+    /// The style of code for a column of code.
+    #[cfg(feature = "code")]
+    pub(crate) fn code(&self) -> Option<&CodeStyle> {
+        match self {
+            ColFormat::Code(code) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_rfc3339_seconds() {
+        assert_eq!(rfc3339_seconds("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(rfc3339_seconds("2023-11-14T22:13:20Z"), Some(1_700_000_000));
+        assert_eq!(rfc3339_seconds("2023-11-14T23:13:20.250+01:00"), Some(1_700_000_000));
+        assert_eq!(rfc3339_seconds("1969-12-31T23:59:59Z"), Some(-1));
+        assert_eq!(rfc3339_seconds("2023-13-01T00:00:00Z"), None);
+        assert_eq!(rfc3339_seconds("yesterday"), None);
+        assert_eq!(rfc3339_seconds("99999999999999999-01-01T00:00:00Z"), None);
+        assert_eq!(rfc3339_seconds("2023-01-01T00:00:00+9999999999999999:00"), None);
+
+        let now = Humanize::RelativeTime { now: 1_700_000_000 };
+        assert_eq!(now.render("-1e30"), None);
+        assert_eq!(Humanize::RelativeTime { now: i64::MIN }.render("1"), None);
+    }
+
+    #[test]
+    fn test_humanize_rounding() {
+        assert_eq!(Humanize::Number.render("999949").as_deref(), Some("999.9K"));
+        assert_eq!(Humanize::Number.render("999960").as_deref(), Some("1.0M"));
+        assert_eq!(Humanize::Number.render("-999960").as_deref(), Some("-1.0M"));
+        assert_eq!(Humanize::Number.render("999.96").as_deref(), Some("1.0K"));
+        assert_eq!(Humanize::Number.render("999").as_deref(), Some("999"));
+        assert_eq!(Humanize::Number.render("999960000000000000").as_deref(), Some("999960.0T"));
+        assert_eq!(Humanize::Bytes.render("1048570").as_deref(), Some("1.0 MiB"));
+    }
+
+    #[test]
+    fn test_notation_extremes() {
+        let eng = Notation::Engineering(2);
+        assert_eq!(eng.render("1e-320").as_deref(), Some("10.00e-321"));
+        assert_eq!(eng.render("5e-324").as_deref(), Some("4.94e-324"));
+        assert_eq!(eng.render("-5e-324").as_deref(), Some("-4.94e-324"));
+        assert_eq!(eng.render("1.7976931348623157e308").as_deref(), Some("179.77e306"));
+        assert_eq!(eng.render("2.2250738585072014e-308").as_deref(), Some("22.25e-309"));
+        assert_eq!(Notation::Scientific(2).render("5e-324").as_deref(), Some("4.94e-324"));
+    }
+
+    #[test]
+    fn test_percent_style() {
+        let percent = Percent::new().bar(4);
+        let colors: Vec<Option<StyleOpt>> = ["10", "70", "95.5%"].iter()
+            .map(|cell| percent.style(&percent.render(cell).unwrap()))
+            .collect();
+        assert_eq!(colors, vec![
+            Some(StyleOpt::new().fg(Fg::Green)),
+            Some(StyleOpt::new().fg(Fg::Yellow)),
+            Some(StyleOpt::new().fg(Fg::Red)),
+        ]);
+
+        let free = Percent::new().thresholds(20.0, 5.0);
+        assert_eq!(free.style("3%"), Some(StyleOpt::new().fg(Fg::Red)));
+        assert_eq!(free.style("15%"), Some(StyleOpt::new().fg(Fg::Yellow)));
+        assert_eq!(free.style("80%"), Some(StyleOpt::new().fg(Fg::Green)));
+        assert_eq!(free.style("n/a"), None);
+    }
+
+    #[test]
+    fn test_currency_parse() {
+        let usd = CurrencyOpts::default().negative(NegativeStyle::Red);
+        assert_eq!(usd.parse("$12.5"), Some(12.5));
+        assert_eq!(usd.parse("(12.5)"), Some(-12.5));
+        assert_eq!(usd.parse("-$12.5"), Some(-12.5));
+        assert_eq!(usd.render("999.999").as_deref(), Some("$1,000.00"));
+        assert_eq!(usd.style(&usd.render("-3").unwrap()), Some(StyleOpt::new().fg(Fg::Red)));
+        assert_eq!(usd.style(&usd.render("3").unwrap()), None);
+
+        let yen = CurrencyOpts::new("¥").decimals(0).no_thousands();
+        assert_eq!(yen.render("1234567.5").as_deref(), Some("¥1234568"));
+
+        let many = CurrencyOpts::default().decimals(70_000);
+        assert_eq!(many.render("1.5").as_deref(), Some("$1.50000000000000000"));
+        assert_eq!(Percent::new().decimals(70_000).render("5").as_deref(), Some("5.00000000000000000%"));
+        assert_eq!(Notation::Scientific(70_000).render("5").as_deref(), Some("5.00000000000000000e0"));
+        assert_eq!(Notation::Engineering(70_000).render("5").as_deref(), Some("5.00000000000000000e0"));
+    }
+}
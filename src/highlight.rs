@@ -0,0 +1,93 @@
+//! The highlight module provides the patterns used to style matching substrings
+//! inside cells, e.g. to show search hits in context.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+
+/// A pattern searched for inside the cells of a grid. A regular expression is
+/// serialized as its source.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PatternSource", into = "PatternSource"))]
+pub enum Pattern {
+    /// Match the given text exactly.
+    Text(String),
+    /// Match a regular expression.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl From<&str> for Pattern {
+    fn from(text: &str) -> Self {
+        Pattern::Text(text.to_string())
+    }
+}
+
+impl From<String> for Pattern {
+    fn from(text: String) -> Self {
+        Pattern::Text(text)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl From<regex::Regex> for Pattern {
+    fn from(regex: regex::Regex) -> Self {
+        Pattern::Regex(regex)
+    }
+}
+
+/// The serialized form of a [`Pattern`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum PatternSource {
+    Text(String),
+    Regex(String),
+}
+
+#[cfg(feature = "serde")]
+impl From<Pattern> for PatternSource {
+    fn from(pattern: Pattern) -> Self {
+        match pattern {
+            Pattern::Text(text) => PatternSource::Text(text),
+            #[cfg(feature = "regex")]
+            Pattern::Regex(regex) => PatternSource::Regex(regex.as_str().to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PatternSource> for Pattern {
+    type Error = String;
+
+    fn try_from(source: PatternSource) -> Result<Self, Self::Error> {
+        match source {
+            PatternSource::Text(text) => Ok(Pattern::Text(text)),
+            #[cfg(feature = "regex")]
+            PatternSource::Regex(regex) => regex::Regex::new(&regex)
+                .map(Pattern::Regex)
+                .map_err(|err| err.to_string()),
+            #[cfg(not(feature = "regex"))]
+            PatternSource::Regex(regex) => Err(alloc::format!("regex pattern {:?} needs the regex feature", regex)),
+        }
+    }
+}
+
+impl Pattern {
+
+    /// The byte ranges of the non-overlapping, non-empty matches in `s`.
+    pub(crate) fn find_in(&self, s: &str) -> Vec<(usize, usize)> {
+        match self {
+            Pattern::Text(text) if text.is_empty() => Vec::new(),
+            Pattern::Text(text) => s.match_indices(text.as_str())
+                .map(|(start, text)| (start, start + text.len()))
+                .collect(),
+            #[cfg(feature = "regex")]
+            Pattern::Regex(regex) => regex.find_iter(s)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+        }
+    }
+}
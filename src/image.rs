@@ -0,0 +1,137 @@
+//! The image module provides [`ImageCell`], enabled by the `images` feature, which
+//! draws an image inline in a cell using the Kitty graphics protocol or the iTerm2
+//! inline image protocol, e.g. for thumbnails alongside the metadata of each row.
+//!
+//! The image is scaled by the terminal to a box of the given columns and rows. An
+//! image taller than one row is drawn over the lines printed below its own, so
+//! leave those lines blank. Terminals without support for the protocol print
+//! nothing, or the escape sequence itself.
+//!
+//! # Example
+//! ```rust,no_run
+//! use grid_printer::GridPrinter;
+//! use grid_printer::image::{ImageCell, ImageProtocol};
+//!
+//! use grid_printer::cell::CellSource;
+//!
+//! let png = std::fs::read("thumbnail.png").unwrap();
+//! let thumbnail = ImageCell::new(&png, ImageProtocol::Kitty, 4, 1);
+//! let grid: Vec<Vec<&dyn CellSource>> = vec![
+//!     vec![&thumbnail, &"thumbnail.png", &"12K"],
+//! ];
+//! let printer = GridPrinter::new(grid.len(), 3);
+//! printer.print_cells(&grid);
+//! ```
+
+use core::fmt;
+use core::fmt::Display;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::cell::CellSource;
+use crate::raw::RawText;
+
+/// The size of the chunks an image is sent to Kitty in, as the protocol requires.
+const KITTY_CHUNK: usize = 4096;
+
+/// The escape sequence protocol an image is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// The Kitty graphics protocol, also supported by WezTerm and Ghostty.
+    Kitty,
+    /// The iTerm2 inline image protocol, also supported by WezTerm and mintty.
+    Iterm2,
+}
+
+/// An image drawn in a cell box of `cols` columns and `rows` rows.
+///
+/// As a [`CellSource`], it is [`RawText`] of the image's escape sequence, counted
+/// as `cols` columns wide, so it is printed verbatim by a GridPrinter.
+///
+/// ```rust
+/// use grid_printer::image::{ImageCell, ImageProtocol};
+///
+/// let image = ImageCell::new(b"GIF89a", ImageProtocol::Iterm2, 2, 1);
+/// assert_eq!(
+///     image.escape(),
+///     "\x1b]1337;File=inline=1;size=6;width=2;height=1;preserveAspectRatio=0:R0lGODlh\x07",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageCell<'a> {
+    data: &'a [u8],
+    protocol: ImageProtocol,
+    cols: usize,
+    rows: usize,
+}
+
+impl<'a> ImageCell<'a> {
+
+    /// Create an ImageCell for the encoded image `data`, e.g. the contents of a PNG
+    /// file, sized to `cols` columns and `rows` rows. Kitty only accepts PNG data.
+    pub fn new(data: &'a [u8], protocol: ImageProtocol, cols: usize, rows: usize) -> Self {
+        Self { data, protocol, cols, rows }
+    }
+
+    /// The escape sequence which draws the image.
+    pub fn escape(&self) -> String {
+        let encoded = STANDARD.encode(self.data);
+        match self.protocol {
+            ImageProtocol::Kitty => {
+                // Note: The cursor is left where the image starts (`C=1`), so that the
+                // blanks printed over the image keep the cell's width countable.
+                let mut escape = String::new();
+                let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK).collect();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = usize::from(i + 1 < chunks.len());
+                    let chunk = core::str::from_utf8(chunk).unwrap_or_default();
+                    match i {
+                        0 => escape.push_str(&format!(
+                            "\x1b_Ga=T,f=100,C=1,c={},r={},m={};{}\x1b\\", self.cols, self.rows, more, chunk,
+                        )),
+                        _ => escape.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk)),
+                    }
+                }
+                escape
+            },
+            ImageProtocol::Iterm2 => format!(
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+                self.data.len(), self.cols, self.rows, encoded,
+            ),
+        }
+    }
+}
+
+impl ImageCell<'_> {
+
+    /// The image's escape sequence as raw text.
+    fn raw(&self) -> RawText<String> {
+        match self.protocol {
+            // Note: Kitty leaves the cursor at the start of the image, so the image
+            // takes no columns itself and is followed by blanks for its box.
+            ImageProtocol::Kitty => RawText::new(format!("{}{:2$}", self.escape(), "", self.cols), self.cols),
+            ImageProtocol::Iterm2 => RawText::new(self.escape(), self.cols),
+        }
+    }
+}
+
+impl Display for ImageCell<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.raw().fmt(f)
+    }
+}
+
+impl CellSource for ImageCell<'_> {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Owned(self.raw().to_string())
+    }
+
+    fn raw_width(&self) -> Option<usize> {
+        Some(self.cols)
+    }
+}
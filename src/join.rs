@@ -0,0 +1,132 @@
+//! The join module provides [`HorizontalJoin`], which renders several grids next
+//! to each other, e.g. for before/after comparisons or dashboards on wide
+//! terminals.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::join::HorizontalJoin;
+//!
+//! let before = vec![vec!["a", "1"], vec!["b", "2"]];
+//! let after = vec![vec!["a", "10"]];
+//! let printer = GridPrinter::builder(2, 2).col_spacing(1).build();
+//!
+//! let out = HorizontalJoin::new()
+//!     .gutter(2)
+//!     .grid(&printer, &before)
+//!     .grid(&printer, &after)
+//!     .render();
+//! assert_eq!(out, concat!(
+//!     "a 1   a 10 \n",
+//!     "b 2        \n",
+//! ));
+//! ```
+
+use core::fmt::Display;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+use crate::GridPrinter;
+use crate::sink::{LineEnding, Sink};
+
+/// Grids rendered side by side with their top edges aligned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HorizontalJoin {
+    gutter: usize,
+    blocks: Vec<(usize, Vec<String>)>,
+    line_ending: LineEnding,
+}
+
+impl Default for HorizontalJoin {
+    fn default() -> Self {
+        Self {
+            gutter: 4,
+            blocks: Vec::new(),
+            line_ending: LineEnding::Lf,
+        }
+    }
+}
+
+impl HorizontalJoin {
+
+    /// Create an empty HorizontalJoin with a gutter of four spaces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of spaces between adjacent grids.
+    pub fn gutter(self, gutter: usize) -> Self {
+        Self { gutter, ..self }
+    }
+
+    /// Render `source` with `printer` and place it to the right of the grids added
+    /// before it. The joined lines end with the line ending of the first grid's
+    /// printer.
+    pub fn grid<F: Display>(mut self, printer: &GridPrinter, source: &[Vec<F>]) -> Self {
+        let width = printer.layout(source).total_width;
+        let mut lines = Vec::new();
+        printer.print_lines(source, |line| lines.push(line.to_string()));
+        if self.blocks.is_empty() {
+            self.line_ending = printer.line_ending.clone();
+        }
+        self.blocks.push((width, lines));
+
+        self
+    }
+
+    /// Render the joined grids into a [`Sink`], one line at a time. Grids with fewer
+    /// lines than the tallest are padded with blank lines beneath.
+    pub fn render_into<S: Sink>(&self, sink: &mut S) -> Result<(), S::Error> {
+        let height = self.blocks.iter().map(|(_, lines)| lines.len()).max().unwrap_or(0);
+
+        let mut line = String::new();
+        for i in 0..height {
+            line.clear();
+            for (block_idx, (width, lines)) in self.blocks.iter().enumerate() {
+                if block_idx > 0 {
+                    line.extend(core::iter::repeat_n(' ', self.gutter));
+                }
+                match lines.get(i) {
+                    Some(block_line) => line.push_str(block_line),
+                    None => line.extend(core::iter::repeat_n(' ', *width)),
+                }
+            }
+            sink.write_line_ending(&line, self.line_ending.as_str())?;
+        }
+
+        Ok(())
+    }
+
+    /// Render the joined grids into a `String`, terminating each line with the line
+    /// ending of the first grid's printer.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let Ok(()) = self.render_into(&mut out);
+
+        out
+    }
+
+    /// Print the joined grids to stdout.
+    #[cfg(feature = "std")]
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_line_ending() {
+        let printer = GridPrinter::builder(1, 1).line_ending(LineEnding::CrLf).build();
+        let out = HorizontalJoin::new()
+            .gutter(1)
+            .grid(&printer, &[vec!["a"]])
+            .grid(&GridPrinter::builder(2, 1).build(), &[vec!["b"], vec!["c"]])
+            .render();
+        assert_eq!(out, "a   b  \r\n    c  \r\n");
+    }
+}
@@ -0,0 +1,218 @@
+//! The layout module provides [`Layout`], the measurements a GridPrinter would
+//! print a grid with, computed without printing it, along with checks of the
+//! invariants every layout and rendered grid must keep, [`StructuredGrid`], a laid
+//! out grid for front-ends which draw it themselves, and [`TruncationReport`], the
+//! parts of a grid a GridPrinter did not print.
+
+use core::fmt;
+use alloc::vec::Vec;
+use alloc::string::String;
+
+use crate::style::StyleOpt;
+use crate::text::ControlChars;
+use crate::width::{self, EmojiWidth};
+
+/// The measured layout of a grid, as returned by
+/// [`GridPrinter::layout`](crate::GridPrinter::layout).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+    /// The width of each printed column, excluding the column spacing. Columns
+    /// dropped to fit the maximum width are not included.
+    pub col_widths: Vec<usize>,
+    /// The width of every printed line, including the spacing after the last column.
+    pub total_width: usize,
+    /// The number of lines each printed source row occupies, excluding the header
+    /// separator and any repeated headers.
+    pub row_heights: Vec<usize>,
+}
+
+impl Layout {
+
+    /// Check the invariants of the layout itself: every row occupies at least one
+    /// line, and the columns fit within the total width.
+    pub fn validate(&self) -> Vec<LayoutViolation> {
+        let mut violations = Vec::new();
+
+        let col_total: usize = self.col_widths.iter().sum();
+        if col_total > self.total_width {
+            violations.push(LayoutViolation::ColWidthsExceedTotal { col_total, total_width: self.total_width });
+        }
+        for (row, height) in self.row_heights.iter().enumerate() {
+            if *height == 0 {
+                violations.push(LayoutViolation::ZeroRowHeight { row });
+            }
+        }
+
+        violations
+    }
+
+    /// Check that every rendered line, with its escape codes and terminator removed,
+    /// is exactly [`total_width`](Layout::total_width) columns wide, as the lines of a
+    /// grid laid out with this layout must be.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::width::EmojiWidth;
+    ///
+    /// let grid = vec![vec!["a", "bcd"], vec!["ef", "🇺🇸"]];
+    /// let printer = GridPrinter::new(2, 2);
+    /// let layout = printer.layout(&grid);
+    /// let rendered = printer.render(&grid);
+    /// let lines: Vec<&str> = rendered.lines().collect();
+    /// assert!(layout.validate_lines(&lines, EmojiWidth::Two).is_empty());
+    /// ```
+    pub fn validate_lines<S: AsRef<str>>(&self, lines: &[S], emoji_width: EmojiWidth) -> Vec<LayoutViolation> {
+        lines.iter()
+            .enumerate()
+            .filter_map(|(line, text)| {
+                let plain = ControlChars::Strip.apply(text.as_ref());
+                let width = width::display_width(&plain, emoji_width);
+                match width == self.total_width {
+                    true => None,
+                    false => Some(LayoutViolation::LineWidth { line, width, total_width: self.total_width }),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A single broken invariant found when validating a [`Layout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutViolation {
+    /// The column widths add up to more than the total width.
+    ColWidthsExceedTotal { col_total: usize, total_width: usize },
+    /// A row occupies no lines.
+    ZeroRowHeight { row: usize },
+    /// A rendered line is not as wide as the total width.
+    LineWidth { line: usize, width: usize, total_width: usize },
+}
+
+impl fmt::Display for LayoutViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutViolation::ColWidthsExceedTotal { col_total, total_width } => {
+                write!(f, "columns {} wide exceed the total width {}", col_total, total_width)
+            },
+            LayoutViolation::ZeroRowHeight { row } => {
+                write!(f, "row {} occupies no lines", row)
+            },
+            LayoutViolation::LineWidth { line, width, total_width } => {
+                write!(f, "line {} is {} wide instead of {}", line, width, total_width)
+            },
+        }
+    }
+}
+
+/// A grid laid out by a GridPrinter but not drawn, as returned by
+/// [`GridPrinter::render_structured`](crate::GridPrinter::render_structured), for
+/// GUI front-ends and TUI frameworks which draw the cells themselves.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructuredGrid {
+    /// The measured layout of the grid.
+    pub layout: Layout,
+    /// The cells of the header row, if the printer has one.
+    pub header: Option<Vec<StructuredCell>>,
+    /// The cells of each data row, limited to the printed columns.
+    pub rows: Vec<Vec<StructuredCell>>,
+}
+
+/// A single cell of a [`StructuredGrid`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructuredCell {
+    /// The text of the cell as printed, without padding or markup tags, truncated
+    /// to its column's width.
+    pub text: String,
+    /// The display width of `text`.
+    pub width: usize,
+    /// The style the cell is printed in, if any.
+    pub style: Option<StyleOpt>,
+}
+
+/// The parts of a source which were not printed, as returned by
+/// [`GridPrinter::print_checked`](crate::GridPrinter::print_checked).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TruncationReport {
+    /// The number of source rows beyond the printed rows.
+    pub dropped_rows: usize,
+    /// The number of source columns not printed, whether beyond the printer's
+    /// columns or dropped to fit the maximum width. Hidden columns aren't counted.
+    pub dropped_cols: usize,
+}
+
+impl TruncationReport {
+
+    /// Whether any part of the source was not printed.
+    pub fn is_truncated(&self) -> bool {
+        self.dropped_rows > 0 || self.dropped_cols > 0
+    }
+}
+
+impl fmt::Display for TruncationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        match (self.dropped_rows, self.dropped_cols) {
+            (0, 0) => write!(f, "nothing truncated"),
+            (rows, 0) => write!(f, "{} row{} not shown", rows, plural(rows)),
+            (0, cols) => write!(f, "{} column{} not shown", cols, plural(cols)),
+            (rows, cols) => write!(f, "{} row{} and {} column{} not shown", rows, plural(rows), cols, plural(cols)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use alloc::vec;
+    use alloc::string::ToString;
+    use proptest::prelude::*;
+    use crate::GridPrinter;
+
+    fn cell() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "[a-z0-9 ]{0,12}",
+            Just("中文".to_string()),
+            Just("e\u{301}".to_string()),
+            Just("🇺🇸".to_string()),
+            Just("👨‍👩‍👧".to_string()),
+            Just("[red]x[/]".to_string()),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_lines_match_layout(
+            grid in prop::collection::vec(prop::collection::vec(cell(), 3), 1..6),
+            col_spacing in 0usize..4,
+            header in any::<bool>(),
+            separator in any::<bool>(),
+            markup in any::<bool>(),
+            max_width in prop::option::of(4usize..40),
+        ) {
+            let mut builder = GridPrinter::builder(grid.len(), 3)
+                .col_spacing(col_spacing)
+                .header(header)
+                .markup(markup);
+            if col_spacing > 0 {
+                builder = builder.col_separator('│');
+            }
+            if header && separator {
+                builder = builder.header_separator('─');
+            }
+            if let Some(max_width) = max_width {
+                builder = builder.max_width(max_width);
+            }
+            let printer = builder.build();
+
+            let layout = printer.layout(&grid);
+            prop_assert_eq!(layout.validate(), vec![]);
+            let rendered = printer.render(&grid);
+            let lines: Vec<&str> = rendered.lines().collect();
+            prop_assert_eq!(layout.validate_lines(&lines, EmojiWidth::Two), vec![]);
+        }
+    }
+}
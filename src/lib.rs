@@ -26,17 +26,113 @@
 //! Lamborghini    Diablo    Yellow    2001    $238,459.99
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod ansi;
+pub mod calendar;
+pub mod cell;
+pub mod column;
+pub mod fixed;
+pub mod format;
+pub mod highlight;
+pub mod join;
+pub mod layout;
+pub mod matrix;
+pub mod options;
+pub mod progress;
+pub mod raw;
+pub mod report;
+pub mod sink;
+pub mod sort;
 pub mod style;
+pub mod text;
+pub mod tree;
+pub mod watch;
+pub mod width;
+mod derive;
+mod markup;
+mod render;
+mod sgr;
+#[cfg(feature = "bumpalo")]
+mod arena;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "ratatui")]
+pub mod tui;
+#[cfg(feature = "images")]
+pub mod image;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "screen")]
+pub mod screen;
+#[cfg(feature = "scroll")]
+mod scroll;
+#[cfg(feature = "code")]
+pub mod code;
+
+extern crate alloc;
 
+use alloc::vec;
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::sync::Arc;
+use core::fmt;
+use core::fmt::Display;
+use core::convert::TryFrom;
+use core::ops::Range;
+#[cfg(feature = "std")]
 use std::io;
-use std::fmt;
+#[cfg(feature = "std")]
 use std::io::Write;
-use std::fmt::Display;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::cell::RefCell;
 
-use crate::style::StyleOpt;
-use crate::style::stylize;
+use crate::style::{Palette, StyleOpt};
+use crate::style::ColorMode;
+use crate::style::ColorSupport;
+use crate::width::EmojiWidth;
+use crate::text::Tabs;
+use crate::text::ControlChars;
+use crate::text::ascii_glyphs;
+use crate::style::stylize_into;
+use crate::cell::CellSource;
+use crate::cell::RowTuple;
+use crate::format::{BoolStyle, ColFormat, CurrencyOpts, StatusRenderer, UnitScale};
+use crate::column::{ColRef, ColumnSpec, NamedCol, Overflow};
+use crate::highlight::Pattern;
+use crate::render::{LineRenderer, RowContext};
+use crate::sink::Sink;
+use crate::sink::LineEnding;
+use crate::sink::FmtSink;
+use crate::sink::LineSink;
+#[cfg(feature = "std")]
+use crate::sink::IoSink;
+
+pub use crate::options::PrintOptions;
+use crate::options::SortOrder;
+use crate::sort::{ColType, Collation};
+use crate::options::{ColStats, OutlierRule};
+use crate::derive::DerivedCol;
+pub use crate::layout::Layout;
+pub use crate::layout::LayoutViolation;
+pub use crate::layout::{StructuredCell, StructuredGrid};
+pub use crate::render::RenderBuffer;
+pub use crate::layout::TruncationReport;
+
+/// The marker used in place of text cut from a cell.
+const ELLIPSIS: &str = "…";
+
+/// The width of the tab stops in the cells of a column whose whitespace is preserved.
+const DEFAULT_TAB_WIDTH: usize = 8;
+
+/// Whether `order` names each of the `cols` columns exactly once.
+fn is_permutation(order: &[usize], cols: usize) -> bool {
+    let mut seen = vec![false; cols];
+    order.len() == cols && order.iter().all(|col_idx| *col_idx < cols && !core::mem::replace(&mut seen[*col_idx], true))
+}
 
 /// An API to easily print a two dimensional array to stdout.
 ///
@@ -66,13 +162,62 @@ use crate::style::stylize;
 /// Toyota         Tacoma    Red       2006    $15,475.23
 /// Lamborghini    Diablo    Yellow    2001    $238,459.99
 /// ```
-// #[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GridPrinterBuilder", into = "GridPrinterBuilder"))]
 pub struct GridPrinter {
     rows: usize,
     cols: usize,
-    max_widths: RefCell<Vec<usize>>,
     col_spacing: usize,
     col_styles: Option<Vec<Option<StyleOpt>>>,
+    sample_rows: Option<usize>,
+    print_options: PrintOptions,
+    markup: bool,
+    emoji_width: EmojiWidth,
+    tabs: Option<Tabs>,
+    control_chars: ControlChars,
+    header: bool,
+    header_separator: Option<char>,
+    repeat_header_every: Option<usize>,
+    abbreviate_headers: bool,
+    header_short_names: BTreeMap<String, String>,
+    vertical_headers: bool,
+    col_formats: BTreeMap<usize, ColFormat>,
+    default_style: Option<StyleOpt>,
+    col_separator: Option<char>,
+    border_style: Option<StyleOpt>,
+    fill_background: bool,
+    highlight_rows: BTreeMap<usize, StyleOpt>,
+    highlights: Vec<(Pattern, StyleOpt)>,
+    line_ending: LineEnding,
+    col_gaps: Vec<(usize, usize, usize)>,
+    uniform_columns: bool,
+    palette: Palette,
+    col_style_names: BTreeMap<usize, String>,
+    highlight_row_names: BTreeMap<usize, String>,
+    ascii_only: bool,
+    sort_keys: Vec<(usize, SortOrder)>,
+    truncation_marker: Option<String>,
+    truncation_style: Option<StyleOpt>,
+    col_outlier_styles: BTreeMap<usize, (StyleOpt, OutlierRule)>,
+    derived_cols: Vec<DerivedCol>,
+    rank_top_style: Option<(usize, StyleOpt)>,
+    suppress_repeats: BTreeSet<usize>,
+    repeat_style: Option<StyleOpt>,
+    key_cols: BTreeSet<usize>,
+    wrap_columns: bool,
+    column_order: Option<Vec<usize>>,
+    col_footer_histograms: BTreeMap<usize, usize>,
+    col_units: BTreeMap<usize, UnitScale>,
+    col_types: BTreeMap<usize, ColType>,
+    collation: Collation,
+    columns: Vec<ColumnSpec>,
+    hidden_cols: BTreeSet<usize>,
+    named_cols: Vec<(String, NamedCol)>,
+    minimize_escapes: bool,
+    color_support: Option<ColorSupport>,
+    col_overflows: BTreeMap<usize, Overflow>,
+    preserved_whitespace: BTreeSet<usize>,
 }
 
 impl GridPrinter {
@@ -88,192 +233,5303 @@ impl GridPrinter {
         GridPrinterBuilder::new(rows, cols)
     }
 
-    fn pad(n: usize) -> String {
-        vec![' '; n].into_iter().collect()
+    fn pad(line: &mut String, n: usize) {
+        line.extend(core::iter::repeat_n(' ', n));
     }
 
-    #[allow(clippy::print_with_newline)]
-    pub fn print_cell(&self, cell: &str, col_idx: usize, style_opt: Option<&StyleOpt>) {
+    fn col_style(&self, col_idx: usize) -> Option<&StyleOpt> {
+        match self.col_styles.as_ref() {
+            None => None,
+            Some(col_styles) => match col_styles.get(col_idx) {
+                None => None,
+                Some(style_opt) => style_opt.as_ref(),
+            }
+        }
+    }
 
-        let mut s = cell.to_string(); 
-        if let Some(style_opt) = style_opt {
-            s = stylize(cell, style_opt);
+    /// The printer's own PrintOptions, with any options set in `overrides` taking precedence.
+    fn resolve(&self, overrides: &PrintOptions) -> PrintOptions {
+        PrintOptions {
+            color_mode: overrides.color_mode.clone().or_else(|| self.print_options.color_mode.clone()),
+            max_width: overrides.max_width.or(self.print_options.max_width),
+            max_rows: overrides.max_rows.or(self.print_options.max_rows),
+            accessible: overrides.accessible.or(self.print_options.accessible),
         }
-        let col_width = self.max_widths.borrow()[col_idx];
-        let pad = GridPrinter::pad(col_width - cell.len() + self.col_spacing);
-        print!("{}{}", s, pad);
     }
 
-    #[allow(clippy::print_with_newline)]
-    pub fn print<F: Display>(&self, source: &[Vec<F>]) {
-        let mut buff: Vec<String> = Vec::new();
+    /// The number of source rows to print under the resolved `opts`.
+    fn row_limit(&self, opts: &PrintOptions) -> usize {
+        match opts.max_rows {
+            None => self.rows,
+            Some(max_rows) => max_rows.min(self.rows),
+        }
+    }
 
-        for i in 0..self.rows {
-            let row = source.get(i);
-            for j in 0..self.cols {
-                let cell = match row {
-                    None => "".to_string(),
-                    Some(row) => match row.get(j) {
-                        None => "".to_string(),
-                        Some(el) => format!("{}", el),
-                    } 
-                };
-                let len = cell.len();
-                if len > self.max_widths.borrow()[j] {
-                    self.max_widths.borrow_mut()[j] = len;
-                }
-                // self.buff.borrow_mut().push(cell);
-                buff.push(cell);
-            }
+    /// The printed width of `cell`, excluding any markup tags.
+    fn cell_width(&self, cell: &str) -> usize {
+        match self.markup && !raw::contains_raw(cell) {
+            false => self.text_width(cell),
+            true => markup::parse(cell).iter().map(|span| self.text_width(span.text)).sum(),
         }
+    }
 
+    /// The printed width of `text`, excluding any escape sequences kept to style it,
+    /// with its raw segments counted as their declared widths.
+    fn text_width(&self, text: &str) -> usize {
+        if raw::contains_raw(text) {
+            return raw::raw_width(text, |text| self.text_width(text));
+        }
+        match self.control_chars {
+            ControlChars::KeepStyles => text::styled_width(text, self.emoji_width),
+            _ => width::display_width(text, self.emoji_width),
+        }
+    }
 
-        for (i, cell) in buff.iter().enumerate() {
-            let col_idx = i % self.cols;
-            let _row_idx = i / self.rows;
+    /// Append a cell containing [`RawText`](raw::RawText) to `line` verbatim, padded
+    /// by its declared width. Raw cells are never truncated.
+    fn render_raw_cell(&self, line: &mut String, cell: &str, col_width: usize, style_opt: Option<&StyleOpt>, styled: bool) {
+        let text = raw::unwrap_raw(cell);
+        let width = self.text_width(cell);
+        match style_opt.filter(|_| styled) {
+            Some(style_opt) => {
+                stylize_into(line, &text, style_opt);
+                self.pad_cell(line, col_width.saturating_sub(width), Some(style_opt));
+            },
+            None => {
+                line.push_str(&text);
+                GridPrinter::pad(line, col_width.saturating_sub(width));
+            },
+        }
+    }
 
-            let style_opt = match self.col_styles.as_ref() {
-                None => None,
-                Some(col_styles) => match col_styles.get(col_idx) {
-                    None => None,
-                    Some(style_opt) => style_opt.as_ref(),
-                }
+    /// Append a cell containing markup to `line`, styling each span over `style_opt`.
+    fn render_markup_cell(&self, line: &mut String, cell: &str, col_width: usize, style_opt: Option<&StyleOpt>, styled: bool) {
+        let spans = markup::parse(cell);
+        let plain: String = spans.iter().map(|span| span.text).collect();
+        let (_, text_limit, marker) = self.truncate_cell(&plain, col_width);
+        let mut width = 0;
+        for span in spans {
+            let (text, text_width) = width::truncate(span.text, text_limit - width, self.emoji_width);
+            width += text_width;
+            let span_style = match (styled, style_opt, span.style.as_ref()) {
+                (false, _, _) => None,
+                (true, Some(style_opt), Some(span_style)) => Some(style_opt.merge(span_style)),
+                (true, style_opt, span_style) => span_style.or(style_opt).cloned(),
             };
+            match styled {
+                true => self.render_text(line, text, span_style.as_ref()),
+                false => line.push_str(text),
+            }
+        }
+        if let Some(marker) = marker {
+            self.render_marker(line, marker, style_opt, styled);
+            width += width::display_width(marker, self.emoji_width);
+        }
+        match styled {
+            true => self.pad_cell(line, col_width - width, style_opt),
+            false => GridPrinter::pad(line, col_width - width),
+        }
+    }
+
+    /// Pad a cell with `n` spaces, carrying the background color of `style_opt`
+    /// across them when the background is filled.
+    fn pad_cell(&self, line: &mut String, n: usize, style_opt: Option<&StyleOpt>) {
+        match style_opt.and_then(|style_opt| style_opt.bg.as_ref()) {
+            Some(bg) if self.fill_background && n > 0 => {
+                let padding: String = core::iter::repeat_n(' ', n).collect();
+                stylize_into(line, &padding, &StyleOpt::new().bg(bg.clone()));
+            },
+            _ => GridPrinter::pad(line, n),
+        }
+    }
+
+    /// Append a styled, padded cell to `line`, truncating it if it is wider than `col_width`.
+    fn render_cell(&self, line: &mut String, cell: &str, col_width: usize, style_opt: Option<&StyleOpt>, styled: bool) {
+        let (cell, mut width, marker) = self.truncate_cell(cell, col_width);
+        match styled {
+            true => self.render_text(line, &cell, style_opt),
+            false => line.push_str(&cell),
+        }
+        if let Some(marker) = marker {
+            self.render_marker(line, marker, style_opt, styled);
+            width += width::display_width(marker, self.emoji_width);
+        }
+        match styled {
+            true => self.pad_cell(line, col_width - width, style_opt),
+            false => GridPrinter::pad(line, col_width - width),
+        }
+    }
+
+    /// The style of code of column `col_idx`, if its format is a
+    /// [`CodeStyle`](code::CodeStyle).
+    #[cfg(feature = "code")]
+    fn code_style(&self, col_idx: usize) -> Option<&code::CodeStyle> {
+        self.col_formats.get(&col_idx).and_then(ColFormat::code)
+    }
+
+    /// Append a styled cell of code to `line` like [`render_cell`](GridPrinter::render_cell),
+    /// with the spans highlighted by the column's style of code drawn over `style_opt`.
+    #[cfg(feature = "code")]
+    fn render_code_cell(&self, line: &mut String, cell: &str, col_width: usize, style_opt: Option<&StyleOpt>, col_idx: usize) {
+        let (cell, mut width, marker) = self.truncate_cell(cell, col_width);
+        let spans = self.code_style(col_idx).map(|code| code.spans(&cell)).unwrap_or_default();
+        let mut pos = 0;
+        for (start, end, span_style) in spans {
+            self.render_text(line, &cell[pos..start], style_opt);
+            let span_style = style_opt.map_or_else(|| span_style.clone(), |style_opt| style_opt.merge(&span_style));
+            stylize_into(line, &cell[start..end], &span_style);
+            pos = end;
+        }
+        self.render_text(line, &cell[pos..], style_opt);
+        if let Some(marker) = marker {
+            self.render_marker(line, marker, style_opt, true);
+            width += width::display_width(marker, self.emoji_width);
+        }
+        self.pad_cell(line, col_width - width, style_opt);
+    }
 
-            self.print_cell(cell, col_idx, style_opt);
+    /// Append `text` to `line` in `style_opt`, styling the substrings matched by the
+    /// highlight patterns on top of it. Where matches overlap, the earlier pattern wins.
+    fn render_text(&self, line: &mut String, text: &str, style_opt: Option<&StyleOpt>) {
+        let mut matches: Vec<(usize, usize, &StyleOpt)> = self.highlights.iter()
+            .flat_map(|(pattern, highlight)| pattern.find_in(text).into_iter().map(move |(start, end)| (start, end, highlight)))
+            .collect();
+        matches.sort_by_key(|(start, _, _)| *start);
 
-            if (i + 1) % self.cols == 0 {
-                print!("\n");
-                io::stdout().flush().unwrap();
+        // Note: A styled cell is opened once, with each match drawn over the cell's
+        // style and the cell's style restored after it, rather than reset.
+        let style_opt = match (style_opt, matches.is_empty()) {
+            (Some(style_opt), false) => {
+                style_opt.push_codes(line);
+                let mut pos = 0;
+                for (start, end, highlight) in matches {
+                    if start < pos {
+                        continue;
+                    }
+                    style::push_restoring(line, &text[pos..start], style_opt);
+                    style::stylize_nested_into(line, &text[start..end], highlight, style_opt);
+                    pos = end;
+                }
+                style::push_restoring(line, &text[pos..], style_opt);
+                return line.push_str(style::Fg::Reset.escape_code());
+            },
+            (style_opt, _) => style_opt,
+        };
+
+        let mut pos = 0;
+        for (start, end, highlight) in matches {
+            if start < pos {
+                continue;
             }
+            line.push_str(&text[pos..start]);
+            stylize_into(line, &text[start..end], highlight);
+            pos = end;
         }
+        self.render_span(line, &text[pos..], style_opt);
+    }
 
+    /// Append `text` to `line`, in `style_opt` if given.
+    fn render_span(&self, line: &mut String, text: &str, style_opt: Option<&StyleOpt>) {
+        match style_opt {
+            _ if text.is_empty() => (),
+            None => line.push_str(text),
+            Some(style_opt) => stylize_into(line, text, style_opt),
+        }
+    }
 
+    /// Append a structural part of the grid, such as a separator, to `line` in the
+    /// border style.
+    fn render_border(&self, line: &mut String, border: &str, styled: bool) {
+        match (styled, self.border_style.as_ref()) {
+            (true, Some(border_style)) => stylize_into(line, border, border_style),
+            _ => line.push_str(border),
+        }
     }
-}
 
-/// A Builder to create/customize a GridPrinter instance
-/// ```rust
-/// use grid_printer::GridPrinter;
-/// use grid_printer::GridPrinterBuilder;
-/// 
-/// let rows = 3;
-/// let cols = 3;
-/// let printer: GridPrinter = GridPrinterBuilder::new(rows, cols)
-///     .col_spacing(4)
-///     .build();
-/// ```
-#[derive(Debug)]
-pub struct GridPrinterBuilder {
-    rows: usize,
-    cols: usize,
-    col_spacing: usize,
-    col_styles: Option<Vec<Option<StyleOpt>>>,
-}
+    /// Append the spacing after a column to `line`, with the column separator
+    /// centered in it unless the column is the last one.
+    pub(crate) fn render_gap(&self, line: &mut String, col_idx: usize, last: bool, styled: bool) {
+        let gap = self.gap(col_idx);
+        match self.col_separator {
+            Some(sep) if !last && gap > 0 => {
+                let left = (gap - 1) / 2;
+                GridPrinter::pad(line, left);
+                self.render_border(line, sep.encode_utf8(&mut [0; 4]), styled);
+                GridPrinter::pad(line, gap - left - 1);
+            },
+            _ => GridPrinter::pad(line, gap),
+        }
+    }
 
-impl Default for GridPrinterBuilder {
-     fn default() -> Self {
-        Self {
-            rows: 1,
-            cols: 1,
-            col_spacing: 2,
-            col_styles: None,
+    /// The spacing after column `col_idx`.
+    fn gap(&self, col_idx: usize) -> usize {
+        self.col_gaps.iter()
+            .rev()
+            .find(|(i, j, _)| *i == col_idx && *j == col_idx + 1)
+            .map_or(self.col_spacing, |(_, _, n)| *n)
+    }
+
+    /// Append the rule drawn beneath the header row to `line`.
+    pub(crate) fn render_separator(&self, line: &mut String, sep: char, max_widths: &[usize], styled: bool) {
+        let start = line.len();
+        for (col_idx, max_width) in max_widths.iter().enumerate() {
+            let rule: String = core::iter::repeat_n(sep, *max_width).collect();
+            self.render_border(line, &rule, styled);
+            self.render_gap(line, col_idx, col_idx + 1 == max_widths.len(), styled);
         }
+        self.fold_ascii(line, start);
+        self.downgrade_sgr(line, start, styled);
+        self.minimize_sgr(line, start, styled);
     }
-}
 
-impl GridPrinterBuilder {
+    /// Print `cell` to stdout, padded to `col_width` columns, e.g. a column width of
+    /// the [`layout`](GridPrinter::layout), and followed by the column spacing.
+    #[cfg(feature = "std")]
+    pub fn print_cell(&self, cell: &str, col_width: usize, style_opt: Option<&StyleOpt>) {
+        let mut s = String::new();
+        self.render_cell(&mut s, cell, col_width, style_opt, true);
+        GridPrinter::pad(&mut s, self.col_spacing);
+        print!("{}", s);
+    }
 
-    pub fn new(rows: usize, cols: usize) -> Self {
-        GridPrinterBuilder {
-            rows,
-            cols,
-            ..Default::default()
+    /// Clean up a cell value before it is measured, e.g. expanding its tabs.
+    fn normalize<'a>(&self, cell: Cow<'a, str>) -> Cow<'a, str> {
+        let cell = raw::strip_markers(cell);
+        let cell = match self.tabs.as_ref() {
+            Some(tabs) if cell.contains('\t') => Cow::Owned(tabs.apply(&cell, self.emoji_width)),
+            _ => cell,
+        };
+        match self.control_chars {
+            ControlChars::Keep => cell,
+            control_chars if cell.chars().any(char::is_control) => Cow::Owned(control_chars.apply(&cell)),
+            _ => cell,
         }
     }
 
-    pub fn col_spacing(mut self, col_spacing: usize) -> Self {
-        self.col_spacing = col_spacing;
+    /// Expand the tabs of a cell whose whitespace is preserved at the default tab
+    /// stops, unless the printer handles tabs itself, so its indentation is measured.
+    fn expand_indent<'a>(&self, cell: Cow<'a, str>) -> Cow<'a, str> {
+        match self.tabs {
+            None if cell.contains('\t') => Cow::Owned(Tabs::Expand(DEFAULT_TAB_WIDTH).apply(&cell, self.emoji_width)),
+            _ => cell,
+        }
+    }
 
-        self
+    /// Normalize a cell of column `col_idx` and apply the column's conversion, which
+    /// is skipped for the header row.
+    fn format_cell<'a>(&self, col_idx: usize, cell: Cow<'a, str>, header: bool) -> Cow<'a, str> {
+        let cell = match self.columns.get(col_idx).filter(|_| header) {
+            Some(column) => Cow::Owned(column.name.clone()),
+            None if self.preserved_whitespace.contains(&col_idx) => self.normalize(self.expand_indent(cell)),
+            None => self.normalize(cell),
+        };
+        let sort_key = self.sort_keys.iter().find(|(sort_col, _)| *sort_col == col_idx);
+        let cell = match (self.col_formats.get(&col_idx), sort_key) {
+            (Some(col_format), _) if !header => col_format.apply(cell),
+            (_, Some((_, order))) if header => Cow::Owned(format!("{}{}", cell, order.indicator())),
+            _ => cell,
+        };
+        match self.col_types.get(&col_idx).filter(|_| !header).and_then(|col_type| col_type.align(&cell)) {
+            Some(aligned) => Cow::Owned(aligned),
+            None => cell,
+        }
     }
 
-    pub fn col_styles(mut self, col_styles: Vec<Option<StyleOpt>>) -> Result<Self, GridPrinterErr> {
-        match col_styles.len() == self.cols {
-            false => Err(GridPrinterErr::DimensionErr),
-            true => {
-                self.col_styles = Some(col_styles);
+    /// The order the formatted rows are printed in: the header row first, then the
+    /// data rows, sorted by the sort keys if any are set.
+    fn row_order<S: AsRef<str>>(&self, buff: &[Vec<S>]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..buff.len()).collect();
+        if self.sort_keys.is_empty() {
+            return order;
+        }
+        let data = match self.header {
+            true => order.get_mut(1..).unwrap_or_default(),
+            false => &mut order[..],
+        };
+        let collator = self.collation.collator();
+        let compare_key = |a: usize, b: usize, (sort_col, sort_order): (usize, SortOrder)| {
+            let key = |i: usize| buff[i].get(sort_col).map_or("", |cell| cell.as_ref());
+            let (a, b) = (key(a), key(b));
+            match (self.col_types.get(&sort_col), sort_order) {
+                (Some(col_type), SortOrder::Descending) => col_type.compare(a, b).reverse(),
+                (Some(col_type), _) => col_type.compare(a, b),
+                (None, SortOrder::Ascending) => sort::compare_default(a, b, &collator),
+                (None, SortOrder::Descending) => sort::compare_default(a, b, &collator).reverse(),
+                (None, SortOrder::Natural) => sort::compare_natural(a, b, &collator),
+            }
+        };
+        data.sort_by(|a, b| {
+            self.sort_keys.iter()
+                .map(|sort_key| compare_key(*a, *b, *sort_key))
+                .find(|ordering| *ordering != core::cmp::Ordering::Equal)
+                .unwrap_or(core::cmp::Ordering::Equal)
+        });
 
-                Ok(self)
+        order
+    }
+
+    /// Format a single row of the source, filling any missing cells with empty strings.
+    fn format_row<F: Display>(&self, row: Option<&[F]>, header: bool) -> Vec<String> {
+        let source_cols = self.source_cols();
+        (0..self.cols)
+            .map(|j| match row.and_then(|row| row.get(j)).filter(|_| j < source_cols) {
+                None => "".to_string(),
+                Some(el) => self.format_cell(j, Cow::Owned(format!("{}", el)), header).into_owned(),
+            })
+            .collect()
+    }
+
+    /// Borrow a single row of the source, filling any missing cells with empty strings.
+    fn borrow_row<'a, C: CellSource>(&self, row: Option<&'a [C]>, header: bool) -> Vec<Cow<'a, str>> {
+        let source_cols = self.source_cols();
+        (0..self.cols)
+            .map(|j| match row.and_then(|row| row.get(j)).filter(|_| j < source_cols) {
+                None => Cow::Borrowed(""),
+                Some(el) => match el.raw_width() {
+                    Some(width) => Cow::Owned(raw::mark_raw(&el.cell(), width)),
+                    None => self.format_cell(j, el.cell(), header),
+                },
+            })
+            .collect()
+    }
+
+    /// Widen `max_widths` so that every cell of `row` fits.
+    fn measure_row<S: AsRef<str>>(&self, max_widths: &mut [usize], row: &[S]) {
+        for (max_width, cell) in max_widths.iter_mut().zip(row) {
+            let len = self.cell_width(cell.as_ref());
+            if len > *max_width {
+                *max_width = len;
             }
         }
     }
 
-    pub fn col_style(mut self, idx: usize, opt: StyleOpt) -> Result<Self, GridPrinterErr> {
-        // Note: The size check here is somewhat redundant given the subsequent logic; however,
-        // performing the check here guarantees we don't mutate the GridPrinterBuilder by adding
-        // a Vec for an index that is outside the column range.
-        if idx >= self.cols {
-            return Err(GridPrinterErr::DimensionErr);
+    /// Measure the width of each column of the formatted rows.
+    fn max_widths<S: AsRef<str>>(&self, buff: &[Vec<S>]) -> Vec<usize> {
+        let mut max_widths: Vec<usize> = vec![0; self.cols];
+        for (i, row) in buff.iter().enumerate() {
+            match i == 0 && self.header {
+                true => self.measure_header(&mut max_widths, row),
+                false => self.measure_row(&mut max_widths, row),
+            }
+        }
+        if let Some(footer) = self.footer_row(buff) {
+            self.measure_row(&mut max_widths, &footer);
         }
 
-        let col_styles = self.col_styles.get_or_insert(vec![None; self.cols]);
-        let col_style = col_styles.get_mut(idx)
-            .ok_or(GridPrinterErr::DimensionErr)?;
-        *col_style = Some(opt);
+        max_widths
+    }
 
-        Ok(self)
+    /// Widen `max_widths` so that the header row fits. A vertical header only needs
+    /// its widest character to fit.
+    fn measure_header<S: AsRef<str>>(&self, max_widths: &mut [usize], row: &[S]) {
+        if !self.vertical_headers {
+            return self.measure_row(max_widths, row);
+        }
+        for (max_width, cell) in max_widths.iter_mut().zip(row) {
+            let widest = width::clusters(cell.as_ref(), self.emoji_width)
+                .map(|(_, width)| width)
+                .max()
+                .unwrap_or(0);
+            if widest > *max_width {
+                *max_width = widest;
+            }
+        }
     }
 
-    pub fn build(self) -> GridPrinter {
-        GridPrinter {
-            rows: self.rows,
-            cols: self.cols,
-            max_widths: RefCell::new(vec![0; self.cols]),
-            col_spacing: self.col_spacing,
-            col_styles: self.col_styles,
+    /// Drop the trailing columns which do not fit within `max_width`. If not even the
+    /// first column fits, it is kept and narrowed to fit instead.
+    fn fit_widths(&self, max_widths: &mut Vec<usize>, max_width: Option<usize>) {
+        let max_width = match max_width {
+            None => return,
+            Some(max_width) => max_width,
+        };
+
+        let mut total = 0;
+        let fits = max_widths.iter()
+            .enumerate()
+            .take_while(|(col_idx, width)| {
+                total += *width + self.gap(*col_idx);
+                total <= max_width
+            })
+            .count();
+        if fits == 0 && !max_widths.is_empty() {
+            max_widths.truncate(1);
+            max_widths[0] = max_width.saturating_sub(self.gap(0));
+        } else {
+            max_widths.truncate(fits);
         }
     }
 
-}
+    /// The total width of a line with the given column widths, including spacing.
+    fn line_width(&self, max_widths: &[usize]) -> usize {
+        max_widths.iter().enumerate().map(|(col_idx, width)| width + self.gap(col_idx)).sum()
+    }
 
-#[derive(Debug)]
-pub enum GridPrinterErr {
-    DimensionErr,
-}
+    /// Cut `cell` down to at most `width` columns, leaving room for the truncation
+    /// marker, if one is set, if it doesn't fit. Returns the remaining text, the
+    /// columns it occupies, and the marker to draw after it, cut down itself if
+    /// `width` is narrower.
+    ///
+    /// The escape sequences kept by [`ControlChars::KeepStyles`] are kept up to the
+    /// cut, and a style they leave open is closed before the marker, so the marker is
+    /// only ever drawn in the cell's and the truncation's styles.
+    fn truncate_cell<'c>(&'c self, cell: &'c str, width: usize) -> (Cow<'c, str>, usize, Option<&'c str>) {
+        let cell_width = self.text_width(cell);
+        if cell_width <= width {
+            return (Cow::Borrowed(cell), cell_width, None);
+        }
+        let (marker, marker_width) = match self.truncation_marker.as_deref() {
+            Some(marker) => width::truncate(marker, width, self.emoji_width),
+            None => ("", 0),
+        };
+        let (cut, cut_width) = match self.control_chars {
+            ControlChars::KeepStyles => {
+                let (cut, cut_width) = text::truncate_styled(cell, width - marker_width, self.emoji_width);
+                (Cow::Owned(cut), cut_width)
+            },
+            _ => {
+                let (cut, cut_width) = width::truncate(cell, width - marker_width, self.emoji_width);
+                (Cow::Borrowed(cut), cut_width)
+            },
+        };
 
-impl Display for GridPrinterErr {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            GridPrinterErr::DimensionErr => {
-                write!(f, "DimensionErr. Caused by mismatch in dimension size between method calls.")
+        (cut, cut_width, Some(marker).filter(|marker| !marker.is_empty()))
+    }
+
+    /// Shorten `cell` to at most `width` columns, marking the cut with an ellipsis.
+    fn ellipsize(&self, cell: &str, width: usize) -> String {
+        let (cut, _) = width::truncate(cell, width.saturating_sub(1), self.emoji_width);
+        let mut ellipsized = cut.to_string();
+        if width > 0 {
+            ellipsized.push_str(ELLIPSIS);
+        }
+
+        ellipsized
+    }
+
+    /// Append the truncation marker to `line`, in the truncation style layered over
+    /// the style of the cell it cuts.
+    fn render_marker(&self, line: &mut String, marker: &str, style_opt: Option<&StyleOpt>, styled: bool) {
+        let marker_style = match (style_opt, self.truncation_style.as_ref()) {
+            (Some(style_opt), Some(truncation_style)) => Some(style_opt.merge(truncation_style)),
+            (style_opt, truncation_style) => truncation_style.or(style_opt).cloned(),
+        };
+        match marker_style {
+            Some(marker_style) if styled => stylize_into(line, marker, &marker_style),
+            _ => line.push_str(marker),
+        }
+    }
+
+    /// When the header row makes the grid wider than `max_width`, abbreviate each
+    /// header which is wider than its column's data, narrowing `max_widths` to match.
+    /// Returns the abbreviated header cells, if any.
+    fn abbreviate_header<S: AsRef<str>>(&self, buff: &[Vec<S>], max_widths: &mut [usize], max_width: Option<usize>) -> Option<Vec<String>> {
+        let max_width = max_width?;
+        let header = buff.first()?;
+        if !self.header || !self.abbreviate_headers || self.vertical_headers || self.line_width(max_widths) <= max_width {
+            return None;
+        }
+
+        let mut data_widths = vec![0; self.cols];
+        for row in buff[1..].iter() {
+            self.measure_row(&mut data_widths, row);
+        }
+        let abbreviated = header.iter()
+            .zip(max_widths.iter_mut().zip(data_widths))
+            .map(|(cell, (max_width, data_width))| {
+                let cell = cell.as_ref();
+                if self.cell_width(cell) <= data_width {
+                    return cell.to_string();
+                }
+                match self.header_short_names.get(cell) {
+                    Some(short) => {
+                        *max_width = data_width.max(self.cell_width(short));
+                        short.clone()
+                    },
+                    // Note: The header is cut when rendered, leaving room for at least
+                    // one character before the truncation marker.
+                    None if self.truncation_marker.is_some() => {
+                        let marker = self.truncation_marker.as_deref().unwrap_or_default();
+                        *max_width = data_width.max(width::display_width(marker, self.emoji_width) + 1);
+                        cell.to_string()
+                    },
+                    // Note: Without a truncation marker, headers are still marked with
+                    // an ellipsis, leaving room for at least one character before it.
+                    None => {
+                        let short = self.ellipsize(cell, data_width.max(2));
+                        *max_width = data_width.max(self.cell_width(&short));
+                        short
+                    },
+                }
+            })
+            .collect();
+
+        Some(abbreviated)
+    }
+
+    /// Narrow the wrapping columns, the widest first, until the line fits within
+    /// `max_width` or they are a single column wide.
+    fn fit_wrap_cols(&self, max_widths: &mut [usize], max_width: Option<usize>) {
+        let max_width = match max_width {
+            None => return,
+            Some(max_width) => max_width,
+        };
+        let mut excess = self.line_width(max_widths).saturating_sub(max_width);
+        while excess > 0 {
+            let widest = max_widths.iter()
+                .enumerate()
+                .filter(|(col_idx, width)| self.col_overflows.get(col_idx) == Some(&Overflow::Wrap) && **width > 1)
+                .max_by_key(|(_, width)| **width)
+                .map(|(col_idx, _)| col_idx);
+            match widest {
+                None => break,
+                Some(col_idx) => max_widths[col_idx] -= 1,
+            }
+            excess -= 1;
+        }
+    }
+
+    /// Set the columns of a fixed width to it, and widen every column to the widest
+    /// when the columns are uniform.
+    fn settle_widths(&self, max_widths: &mut [usize]) {
+        for (max_width, column) in max_widths.iter_mut().zip(self.columns.iter()) {
+            if let Some(width) = column.width {
+                *max_width = width;
+            }
+        }
+        for (col_idx, overflow) in self.col_overflows.iter() {
+            if let (Some(max_width), Overflow::Truncate(width)) = (max_widths.get_mut(*col_idx), overflow) {
+                *max_width = (*max_width).min(*width);
+            }
+        }
+        if self.uniform_columns {
+            let widest = max_widths.iter().copied().max().unwrap_or(0);
+            max_widths.iter_mut().for_each(|width| *width = widest);
+        }
+    }
+
+    /// The columns in the order they are printed: the column order, if it names
+    /// every column once, or else their own order. Hidden columns are left out.
+    pub(crate) fn col_order(&self) -> Vec<usize> {
+        let mut order = match self.column_order.as_ref() {
+            Some(order) if is_permutation(order, self.cols) => order.clone(),
+            _ => (0..self.cols).collect(),
+        };
+        order.retain(|col_idx| !self.is_hidden(*col_idx));
+
+        order
+    }
+
+    /// Whether the column at `col_idx` is left out of the printed grid.
+    pub(crate) fn is_hidden(&self, col_idx: usize) -> bool {
+        self.hidden_cols.contains(&col_idx) || self.columns.get(col_idx).is_some_and(|column| column.hidden)
+    }
+
+    /// A copy of the printer with the options set for columns by name applied to the
+    /// columns of those names, or `None` if no column was referred to by name. Names
+    /// are looked up among the column specs, and then in `header`, the header row.
+    fn bind_names<S: AsRef<str>>(&self, header: Option<&[S]>) -> Option<GridPrinter> {
+        if self.named_cols.is_empty() {
+            return None;
+        }
+        let header = header.filter(|_| self.header).unwrap_or_default();
+        let mut printer = GridPrinter { named_cols: Vec::new(), ..self.clone() };
+        for (name, named_col) in self.named_cols.iter() {
+            let idx = self.columns.iter()
+                .position(|column| column.name == *name)
+                .or_else(|| header.iter().position(|cell| cell.as_ref().trim() == name));
+            let idx = match idx {
+                Some(idx) if idx < self.cols => idx,
+                _ => continue,
+            };
+            match named_col {
+                NamedCol::Style(style) => {
+                    let col_styles = printer.col_styles.get_or_insert(vec![None; self.cols]);
+                    col_styles.resize(col_styles.len().max(self.cols), None);
+                    col_styles[idx] = Some(style.clone());
+                },
+                NamedCol::Sort(order, priority) => {
+                    let at = (*priority).min(printer.sort_keys.len());
+                    printer.sort_keys.insert(at, (idx, *order));
+                },
+                NamedCol::Hide => {
+                    printer.hidden_cols.insert(idx);
+                },
+            }
+        }
+
+        Some(printer)
+    }
+
+    /// A copy of the printer downgrading colors to the terminal's support, when no
+    /// color support was set and the terminal lacks true color.
+    #[cfg(feature = "std")]
+    pub(crate) fn detect_color_support(&self) -> Option<GridPrinter> {
+        if self.color_support.is_some() {
+            return None;
+        }
+        match ColorSupport::detect() {
+            ColorSupport::TrueColor => None,
+            color_support => {
+                let mut printer = self.clone();
+                printer.color_support = Some(color_support);
+                Some(printer)
             },
         }
     }
-}
 
-impl Error for GridPrinterErr {}
+    /// Like [`bind_names`](GridPrinter::bind_names), looking names up in the first
+    /// row of `source`.
+    fn bind_source_names<F: Display>(&self, source: &[Vec<F>]) -> Option<GridPrinter> {
+        if self.named_cols.is_empty() {
+            return None;
+        }
+        let header: Option<Vec<String>> = source.first().map(|row| row.iter().map(ToString::to_string).collect());
 
+        self.bind_names(header.as_deref())
+    }
 
-#[cfg(test)]
-mod tests {
+    /// Arrange the columns in the column order and, when the grid is wider than
+    /// `max_width`, keep the key columns and then as many of the others as fit. If the
+    /// printed columns are no longer the leading columns in their own order,
+    /// `max_widths` is narrowed and rearranged to match and their indices are
+    /// returned; otherwise the trailing columns are left to [`fit_widths`](GridPrinter::fit_widths).
+    fn fit_key_cols(&self, max_widths: &mut Vec<usize>, max_width: Option<usize>) -> Option<Vec<usize>> {
+        if max_widths.len() != self.cols {
+            return None;
+        }
 
-    use super::*;
+        let order = self.col_order();
+        let mut visible = order.clone();
+        if let Some(max_width) = max_width.filter(|max_width| !self.key_cols.is_empty() && self.line_width(max_widths) > *max_width) {
+            let mut kept: Vec<usize> = self.key_cols.iter().copied().filter(|col_idx| *col_idx < self.cols).collect();
+            let mut total: usize = kept.iter().map(|col_idx| max_widths[*col_idx] + self.gap(*col_idx)).sum();
+            for col_idx in order.iter().copied().filter(|col_idx| !self.key_cols.contains(col_idx)) {
+                total += max_widths[col_idx] + self.gap(col_idx);
+                if total > max_width {
+                    break;
+                }
+                kept.push(col_idx);
+            }
+            visible.retain(|col_idx| kept.contains(col_idx));
+        }
+        if visible.len() == self.cols && visible.iter().enumerate().all(|(pos, col_idx)| pos == *col_idx) {
+            return None;
+        }
+        *max_widths = visible.iter().map(|col_idx| max_widths[*col_idx]).collect();
 
-    #[test]
-    fn test_2d_arr() {
-        let v = vec![
-            vec![1, 20, 3, ],
-            vec![40, 5, 6, ],
-            vec![7, 800, 9, ],
+        Some(visible)
+    }
+
+    /// Narrow the measured `max_widths` to fit `opts`, abbreviating the header if
+    /// enabled and dropping the columns which don't fit. Returns the abbreviated
+    /// header cells, if any, and the indices of the printed columns when key columns
+    /// were kept in place of earlier ones. Uniform columns are first widened to the widest.
+    fn fit_layout<S: AsRef<str>>(&self, buff: &[Vec<S>], max_widths: &mut Vec<usize>, opts: &PrintOptions) -> (Option<Vec<String>>, Option<Vec<usize>>) {
+        self.settle_widths(max_widths);
+        self.fit_wrap_cols(max_widths, opts.max_width);
+        let header_cells = self.abbreviate_header(buff, max_widths, opts.max_width);
+        let visible = self.fit_key_cols(max_widths, opts.max_width);
+        self.fit_widths(max_widths, opts.max_width);
+
+        (header_cells, visible)
+    }
+
+    /// The style of a cell of row `row_idx` and column `col_idx`: the default style,
+    /// overridden by the column style, the style of the column's format, and finally
+    /// any highlight of the row. Named styles are looked up in the palette.
+    fn cell_style<S: AsRef<str>>(&self, row_idx: usize, col_idx: usize, row: &[S], col_stats: &BTreeMap<usize, ColStats>) -> Option<StyleOpt> {
+        let cell = row[col_idx].as_ref();
+        let format_style = self.col_formats.get(&col_idx).and_then(|col_format| col_format.style(cell));
+        let named = |names: &BTreeMap<usize, String>, idx| names.get(&idx).and_then(|name| self.palette.get(name));
+        let outlier_style = match self.col_outlier_styles.get(&col_idx) {
+            Some((style, rule)) if !(self.header && row_idx == 0) => col_stats.get(&col_idx)
+                .filter(|stats| stats.is_outlier(cell, *rule))
+                .map(|_| style),
+            _ => None,
+        };
+        let layers = [
+            self.default_style.as_ref(),
+            self.col_style(col_idx),
+            named(&self.col_style_names, col_idx),
+            format_style.as_ref(),
+            outlier_style,
+            self.top_rank_style(row),
+            self.highlight_rows.get(&row_idx),
+            named(&self.highlight_row_names, row_idx),
         ];
+        layers.iter()
+            .flatten()
+            .fold(None, |style: Option<StyleOpt>, layer| match style {
+                None => Some((*layer).clone()),
+                Some(style) => Some(style.merge(layer)),
+            })
+    }
 
-        let rows = v.len();
-        let cols = v[0].len();
-        let printer = GridPrinterBuilder::new(rows, cols)
-            .col_spacing(20)
-            .build();
-        printer.print(&v);
+    /// The style of `row` when its rank is among the top ranks to emphasize.
+    fn top_rank_style<S: AsRef<str>>(&self, row: &[S]) -> Option<&StyleOpt> {
+        let (top, style) = self.rank_top_style.as_ref()?;
+        let rank = row.get(self.rank_col()?)?.as_ref().trim().parse::<usize>().ok()?;
+
+        Some(style).filter(|_| rank <= *top)
+    }
+
+    /// Measure the statistics of the columns with an outlier style over the data
+    /// rows of `buff`.
+    fn col_stats<S: AsRef<str>>(&self, buff: &[Vec<S>]) -> BTreeMap<usize, ColStats> {
+        let data = match self.header {
+            true => buff.get(1..).unwrap_or_default(),
+            false => buff,
+        };
+        self.col_outlier_styles.keys()
+            .filter_map(|col_idx| {
+                let cells = data.iter().filter_map(|row| row.get(*col_idx)).map(AsRef::as_ref);
+                ColStats::measure(cells).map(|stats| (*col_idx, stats))
+            })
+            .collect()
+    }
+
+    /// The columns whose repeats are suppressed in which `row` repeats `prev`, the
+    /// row printed above it.
+    fn repeated_cols<S: AsRef<str>, P: AsRef<str>>(&self, prev: Option<&[P]>, row: &[S]) -> Vec<usize> {
+        let prev = match prev {
+            None => return Vec::new(),
+            Some(prev) => prev,
+        };
+        self.suppress_repeats.iter()
+            .copied()
+            .filter(|col_idx| match (prev.get(*col_idx), row.get(*col_idx)) {
+                (Some(above), Some(cell)) => !cell.as_ref().is_empty() && above.as_ref() == cell.as_ref(),
+                _ => false,
+            })
+            .collect()
+    }
+
+    /// The style of a cell which repeats the one above it, layered over `cell_style`.
+    /// Without a repeat style, the cell is blanked instead.
+    fn repeat_cell_style(&self, cell_style: Option<StyleOpt>) -> Option<StyleOpt> {
+        match (cell_style, self.repeat_style.as_ref()) {
+            (Some(cell_style), Some(repeat_style)) => Some(cell_style.merge(repeat_style)),
+            (cell_style, repeat_style) => repeat_style.cloned().or(cell_style),
+        }
+    }
+
+    /// Append a formatted row to `line`, excluding the line terminator. Only the
+    /// columns present in `max_widths` are rendered, in the given context.
+    fn render_row<S: AsRef<str>>(&self, line: &mut String, row_idx: usize, row: &[S], max_widths: &[usize], ctx: &RowContext<'_>, styled: bool) {
+        let start = line.len();
+        match self.wrap_row(row, max_widths, ctx) {
+            None => self.render_row_line(line, row_idx, row, row, max_widths, ctx, styled),
+            Some(lines) => for (i, texts) in lines.iter().enumerate() {
+                if i > 0 {
+                    line.push('\n');
+                }
+                self.render_row_line(line, row_idx, row, texts, max_widths, ctx, styled);
+            },
+        }
+        self.fold_ascii(line, start);
+        self.downgrade_sgr(line, start, styled);
+        self.minimize_sgr(line, start, styled);
+    }
+
+    /// Wrap the cells of `row` in the wrapping columns which are wider than their
+    /// columns, returning the text of every cell on each line of the row, or `None`
+    /// when no cell wraps.
+    fn wrap_row<'r, S: AsRef<str>>(&self, row: &'r [S], max_widths: &[usize], ctx: &RowContext<'_>) -> Option<Vec<Vec<Cow<'r, str>>>> {
+        if !self.col_overflows.values().any(|overflow| *overflow == Overflow::Wrap) {
+            return None;
+        }
+        let mut wrapped: Vec<Vec<Cow<str>>> = row.iter().map(|cell| vec![Cow::Borrowed(cell.as_ref())]).collect();
+        for (pos, max_width) in max_widths.iter().enumerate() {
+            let col_idx = ctx.visible.map_or(pos, |visible| visible[pos]);
+            let cell = match row.get(col_idx) {
+                None => break,
+                Some(cell) => cell.as_ref(),
+            };
+            let plain = !self.markup && !raw::contains_raw(cell) && !cell.contains('\x1b');
+            if self.col_overflows.get(&col_idx) == Some(&Overflow::Wrap) && plain && self.cell_width(cell) > *max_width {
+                wrapped[col_idx] = match self.preserved_whitespace.contains(&col_idx) {
+                    true => text::cut_lines(cell, *max_width, self.emoji_width),
+                    false => text::wrap(cell, *max_width, self.emoji_width),
+                };
+            }
+        }
+        let height = wrapped.iter().map(Vec::len).max().filter(|height| *height > 1)?;
+
+        let lines = (0..height)
+            .map(|i| wrapped.iter().map(|cell_lines| cell_lines.get(i).cloned().unwrap_or_default()).collect())
+            .collect();
+        Some(lines)
+    }
+
+    /// Append one line of a row to `line`: the cells' `texts`, styled by the cells
+    /// of `row`.
+    #[allow(clippy::too_many_arguments)]
+    fn render_row_line<S: AsRef<str>, T: AsRef<str>>(&self, line: &mut String, row_idx: usize, row: &[S], texts: &[T], max_widths: &[usize], ctx: &RowContext<'_>, styled: bool) {
+        let no_stats = BTreeMap::new();
+        let col_stats = ctx.col_stats.unwrap_or(&no_stats);
+        for (pos, max_width) in max_widths.iter().enumerate() {
+            let col_idx = ctx.visible.map_or(pos, |visible| visible[pos]);
+            let mut cell = match texts.get(col_idx) {
+                None => break,
+                Some(cell) => cell.as_ref(),
+            };
+            let mut cell_style = self.cell_style(row_idx, col_idx, row, col_stats);
+            if ctx.repeated.contains(&col_idx) {
+                match self.repeat_style.is_some() {
+                    true => cell_style = self.repeat_cell_style(cell_style),
+                    false => cell = "",
+                }
+            }
+            let align = self.columns.get(col_idx).map(|column| column.align).unwrap_or_default();
+            let lead = align.lead(max_width.saturating_sub(self.cell_width(cell)));
+            match styled {
+                true => self.pad_cell(line, lead, cell_style.as_ref()),
+                false => GridPrinter::pad(line, lead),
+            }
+            let max_width = max_width - lead;
+            match (raw::contains_raw(cell), self.markup) {
+                (true, _) => self.render_raw_cell(line, cell, max_width, cell_style.as_ref(), styled),
+                (false, true) => self.render_markup_cell(line, cell, max_width, cell_style.as_ref(), styled),
+                #[cfg(feature = "code")]
+                (false, false) if styled && self.code_style(col_idx).is_some() => {
+                    self.render_code_cell(line, cell, max_width, cell_style.as_ref(), col_idx);
+                },
+                (false, false) => self.render_cell(line, cell, max_width, cell_style.as_ref(), styled),
+            }
+            self.render_gap(line, col_idx, pos + 1 == max_widths.len(), styled);
+        }
+    }
+
+    /// Replace the glyphs appended to `line` after `start` with their ASCII
+    /// equivalents, when the printer is ASCII-only.
+    fn fold_ascii(&self, line: &mut String, start: usize) {
+        if !self.ascii_only {
+            return;
+        }
+        if let Cow::Owned(folded) = ascii_glyphs(&line[start..]) {
+            line.truncate(start);
+            line.push_str(&folded);
+        }
+    }
+
+    /// Downgrade the colors of the escape sequences appended to `line` after `start`
+    /// to the printer's color support.
+    fn downgrade_sgr(&self, line: &mut String, start: usize, styled: bool) {
+        let color_support = self.color_support.unwrap_or_default();
+        if !styled || color_support == ColorSupport::TrueColor {
+            return;
+        }
+        let downgraded = sgr::downgrade(&line[start..], color_support);
+        line.truncate(start);
+        line.push_str(&downgraded);
+    }
+
+    /// Rewrite the escape sequences appended to `line` after `start` to write only
+    /// the style attributes which change, when the printer minimizes escapes.
+    fn minimize_sgr(&self, line: &mut String, start: usize, styled: bool) {
+        if !styled || !self.minimize_escapes {
+            return;
+        }
+        let minimized = line[start..].split('\n').map(sgr::minimize).collect::<Vec<String>>().join("\n");
+        line.truncate(start);
+        line.push_str(&minimized);
+    }
+
+    /// Measure the formatted rows and hand each rendered line, excluding the line
+    /// terminator, to `f`, reusing a single line buffer.
+    fn for_each_line<S, E, L>(&self, buff: &[Vec<S>], opts: &PrintOptions, f: L) -> Result<(), E>
+    where
+        S: AsRef<str>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        self.render_lines(buff, opts, f).map(|_| ())
+    }
+
+    /// Like [`for_each_line`](GridPrinter::for_each_line), returning the indices of the
+    /// columns which fit and were printed.
+    fn render_lines<S, E, L>(&self, buff: &[Vec<S>], opts: &PrintOptions, f: L) -> Result<Vec<usize>, E>
+    where
+        S: AsRef<str>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        self.render_lines_with(buff, self.max_widths(buff), opts, f)
+    }
+
+    /// Render the formatted rows like [`render_lines`](GridPrinter::render_lines), with
+    /// the given measured column widths.
+    fn render_lines_with<S, E, L>(&self, buff: &[Vec<S>], mut max_widths: Vec<usize>, opts: &PrintOptions, mut f: L) -> Result<Vec<usize>, E>
+    where
+        S: AsRef<str>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        if let Some(max_width) = opts.max_width.filter(|_| self.wrap_columns && opts.accessible != Some(true)) {
+            self.settle_widths(&mut max_widths);
+            if self.line_width(&max_widths) > max_width {
+                return self.render_chunks(buff, &max_widths, max_width, opts, f);
+            }
+        }
+        let (header_cells, visible) = self.fit_layout(buff, &mut max_widths, opts);
+        let styled = opts.color_mode.clone().unwrap_or_default().enabled();
+        // Note: Lines for screen readers label every column which isn't hidden.
+        let printed_cols = match (opts.accessible == Some(true), visible.as_ref()) {
+            (true, _) => self.col_order(),
+            (false, Some(visible)) => visible[..max_widths.len()].to_vec(),
+            (false, None) => (0..max_widths.len()).collect(),
+        };
+
+        let mut renderer = LineRenderer::new(self, max_widths, styled, header_cells)
+            .visible(visible)
+            .accessible(opts.accessible.unwrap_or(false))
+            .col_stats(self.col_stats(buff));
+        for i in self.row_order(buff) {
+            renderer.push_row(i, &buff[i], &mut f)?;
+        }
+        if let Some(footer) = self.footer_row(buff) {
+            renderer.push_footer(&footer, &mut f)?;
+        }
+
+        Ok(printed_cols)
+    }
+
+    /// Split the columns into chunks which each fit within `max_width`, every chunk
+    /// holding the key columns and the next columns in the column order.
+    fn chunk_cols(&self, max_widths: &[usize], max_width: usize) -> Vec<Vec<usize>> {
+        let order = self.col_order();
+        let keys: Vec<usize> = self.key_cols.iter()
+            .copied()
+            .filter(|col_idx| *col_idx < max_widths.len() && !self.is_hidden(*col_idx))
+            .collect();
+        let key_width: usize = keys.iter().map(|col_idx| max_widths[*col_idx] + self.gap(*col_idx)).sum();
+
+        let mut chunks = Vec::new();
+        let mut chunk = keys.clone();
+        let mut total = key_width;
+        for col_idx in order.iter().copied().filter(|col_idx| !keys.contains(col_idx)) {
+            let width = max_widths[col_idx] + self.gap(col_idx);
+            if total + width > max_width && chunk.len() > keys.len() {
+                chunks.push(core::mem::replace(&mut chunk, keys.clone()));
+                total = key_width;
+            }
+            chunk.push(col_idx);
+            total += width;
+        }
+        if chunk.len() > keys.len() || chunks.is_empty() {
+            chunks.push(chunk);
+        }
+        chunks.iter_mut().for_each(|chunk| chunk.sort_by_key(|col_idx| order.iter().position(|o| o == col_idx)));
+
+        chunks
+    }
+
+    /// Render the formatted rows as stacked sub-tables of the column chunks, each
+    /// fitting within `max_width`, separated by blank lines. Returns the indices of
+    /// the columns printed, which are all but the hidden ones.
+    fn render_chunks<S, E, L>(&self, buff: &[Vec<S>], max_widths: &[usize], max_width: usize, opts: &PrintOptions, mut f: L) -> Result<Vec<usize>, E>
+    where
+        S: AsRef<str>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        let styled = opts.color_mode.clone().unwrap_or_default().enabled();
+        for (k, chunk) in self.chunk_cols(max_widths, max_width).into_iter().enumerate() {
+            if k > 0 {
+                f("")?;
+            }
+            let mut widths: Vec<usize> = chunk.iter().map(|col_idx| max_widths[*col_idx]).collect();
+            // Note: A single column too wide for a chunk of its own is narrowed to fit.
+            let overflow = self.line_width(&widths).saturating_sub(max_width);
+            if let Some(width) = widths.iter_mut().rev().find(|width| **width > overflow) {
+                *width -= overflow;
+            }
+
+            let mut renderer = LineRenderer::new(self, widths, styled, None)
+                .visible(Some(chunk))
+                .col_stats(self.col_stats(buff));
+            for i in self.row_order(buff) {
+                renderer.push_row(i, &buff[i], &mut f)?;
+            }
+            if let Some(footer) = self.footer_row(buff) {
+                renderer.push_footer(&footer, &mut f)?;
+            }
+        }
+
+        Ok(self.col_order())
+    }
+
+    fn for_each_iter_line<F, R, I, E, L>(&self, source: I, opts: &PrintOptions, mut f: L) -> Result<(), E>
+    where
+        F: Display,
+        R: AsRef<[F]>,
+        I: IntoIterator<Item = R>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        let mut source = source.into_iter().take(self.row_limit(opts));
+        let sample: Vec<Vec<String>> = match self.sample_rows {
+            None => source.by_ref().enumerate().map(|(i, row)| self.format_row(Some(row.as_ref()), i == 0 && self.header)).collect(),
+            Some(n) => source.by_ref().take(n).enumerate().map(|(i, row)| self.format_row(Some(row.as_ref()), i == 0 && self.header)).collect(),
+        };
+        let mut max_widths = self.max_widths(&sample);
+        let (header_cells, visible) = self.fit_layout(&sample, &mut max_widths, opts);
+        let styled = opts.color_mode.clone().unwrap_or_default().enabled();
+
+        let mut renderer = LineRenderer::new(self, max_widths, styled, header_cells)
+            .visible(visible)
+            .accessible(opts.accessible.unwrap_or(false))
+            .col_stats(self.col_stats(&sample));
+        for (i, row) in sample.iter().enumerate() {
+            renderer.push_row(i, row, &mut f)?;
+        }
+        for (i, row) in source.enumerate() {
+            renderer.push_row(sample.len() + i, &self.format_row(Some(row.as_ref()), false), &mut f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Print the rows yielded by `source`, up to the configured number of rows.
+    ///
+    /// Unlike [`print`](GridPrinter::print), missing rows are not padded. When the
+    /// printer was built with [`sample_rows`](GridPrinterBuilder::sample_rows), only the
+    /// first `n` rows are buffered to measure the column widths; the remaining rows are
+    /// streamed straight to stdout, and cells wider than their column are truncated.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let printer = GridPrinter::builder(usize::MAX, 2)
+    ///     .sample_rows(100)
+    ///     .build();
+    /// printer.print_iter((0..1_000).map(|i| vec![i, i * i]));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn print_iter<F, R, I>(&self, source: I)
+    where
+        F: Display,
+        R: AsRef<[F]>,
+        I: IntoIterator<Item = R>,
+    {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_iter_into(&mut sink, source).unwrap();
+        sink.0.flush().unwrap();
+    }
+
+    fn format_grid<F: Display>(&self, source: &[Vec<F>], opts: &PrintOptions) -> Vec<Vec<String>> {
+        // Note: Column widths are measured per call rather than stored on the GridPrinter, which
+        // keeps a configured GridPrinter immutable and therefore `Send + Sync`.
+        let rows = self.row_limit(opts);
+        // Uniform columns, abbreviated headers, and wrapped columns need every column
+        // measured to fit.
+        let measure_all = self.uniform_columns || (self.header && self.abbreviate_headers) || self.wrap_columns || !self.col_units.is_empty()
+            || !self.columns.is_empty();
+        let max_width = match opts.max_width {
+            Some(max_width) if !measure_all => max_width,
+            _ => {
+                let mut buff: Vec<Vec<String>> = (0..rows)
+                    .map(|i| self.format_row(source.get(i).map(Vec::as_slice), i == 0 && self.header))
+                    .collect();
+                self.scale_units(&mut buff);
+                self.derive_cols(&mut buff);
+                return buff;
+            },
+        };
+
+        // Format column by column, so that once a column overflows `max_width` the
+        // columns after it, which are dropped anyway, are never formatted.
+        let source_cols = self.source_cols();
+        let mut buff: Vec<Vec<String>> = vec![Vec::with_capacity(self.cols); rows];
+        let mut total = 0;
+        for j in 0..self.cols {
+            let mut width = [0];
+            for (i, row) in buff.iter_mut().enumerate() {
+                let header = i == 0 && self.header;
+                let cell = match source.get(i).and_then(|row| row.get(j)).filter(|_| j < source_cols) {
+                    Some(el) if total <= max_width || self.key_cols.contains(&j) => self.format_cell(j, Cow::Owned(format!("{}", el)), header).into_owned(),
+                    _ => String::new(),
+                };
+                match header {
+                    true => self.measure_header(&mut width, &[&cell]),
+                    false => self.measure_row(&mut width, &[&cell]),
+                }
+                row.push(cell);
+            }
+            total += width[0] + self.gap(j);
+        }
+        self.scale_units(&mut buff);
+        self.derive_cols(&mut buff);
+
+        buff
+    }
+
+    /// The specs the columns were described with, if any, e.g. to list the names
+    /// and descriptions of the columns.
+    pub fn columns(&self) -> &[ColumnSpec] {
+        &self.columns
+    }
+
+    /// Scale the columns with a unit scale to their common unit.
+    pub(crate) fn scale_units(&self, buff: &mut [Vec<String>]) {
+        for (idx, unit_scale) in self.col_units.iter() {
+            unit_scale.scale_col(buff, *idx, self.header);
+        }
+    }
+
+    fn render_grid<S: Sink, F: Display>(&self, sink: &mut S, source: &[Vec<F>], opts: &PrintOptions) -> Result<(), S::Error> {
+        if let Some(printer) = self.bind_source_names(source) {
+            return printer.render_grid(sink, source, opts);
+        }
+        if self.counts_widths(opts) {
+            return self.render_counted(sink, source, opts);
+        }
+        let buff = self.format_grid(source, opts);
+        self.for_each_line(&buff, opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+    }
+
+    /// Render `source` into `out`, replacing its contents, with the formatted cells
+    /// kept in `buffer`. Reusing the same `out` and `buffer` across prints in a hot
+    /// loop reuses their allocations, so that once they have grown to fit, printing
+    /// no longer allocates a string per cell.
+    ///
+    /// ```rust
+    /// use grid_printer::{GridPrinter, RenderBuffer};
+    ///
+    /// let printer = GridPrinter::new(2, 2);
+    /// let mut out = String::new();
+    /// let mut buffer = RenderBuffer::new();
+    /// for tick in 0..3 {
+    ///     printer.render_reusing(&mut out, &mut buffer, &vec![vec![tick, 1], vec![2, 3]]);
+    /// }
+    /// assert_eq!(out, "2  1  \n2  3  \n");
+    /// ```
+    pub fn render_reusing<F: Display>(&self, out: &mut String, buffer: &mut RenderBuffer, source: &[Vec<F>]) {
+        if let Some(printer) = self.bind_source_names(source) {
+            return printer.render_reusing(out, buffer, source);
+        }
+        let opts = &self.print_options;
+        let rows = self.row_limit(opts);
+        let source_cols = self.source_cols();
+        buffer.cells.resize_with(rows, Vec::new);
+        for (i, row) in buffer.cells.iter_mut().enumerate() {
+            row.resize_with(self.cols, String::new);
+            for (j, cell) in row.iter_mut().enumerate() {
+                cell.clear();
+                // Note: Like `format_row`, only cells of the source are formatted.
+                let el = match source.get(i).and_then(|row| row.get(j)).filter(|_| j < source_cols) {
+                    Some(el) => el,
+                    None => continue,
+                };
+                let _ = fmt::Write::write_fmt(cell, format_args!("{}", el));
+                let formatted = match self.format_cell(j, Cow::Borrowed(cell.as_str()), i == 0 && self.header) {
+                    Cow::Borrowed(_) => None,
+                    Cow::Owned(formatted) => Some(formatted),
+                };
+                if let Some(formatted) = formatted {
+                    *cell = formatted;
+                }
+            }
+        }
+        self.scale_units(&mut buffer.cells);
+        self.derive_cols(&mut buffer.cells);
+
+        out.clear();
+        let Ok(()) = self.for_each_line(&buffer.cells, opts, |line| out.write_line_ending(line, self.line_ending.as_str()));
+    }
+
+    /// Whether the cells can be measured without being formatted first: when nothing
+    /// needs the formatted text before printing, such as a column format, markup,
+    /// sorting, outlier statistics, derived columns, or abbreviated headers, and no
+    /// maximum width drops columns.
+    fn counts_widths(&self, opts: &PrintOptions) -> bool {
+        self.tabs.is_none()
+            && self.control_chars == ControlChars::Keep
+            && self.col_formats.is_empty()
+            && !self.markup
+            && self.sort_keys.is_empty()
+            && self.col_outlier_styles.is_empty()
+            && self.derived_cols.is_empty()
+            && self.col_footer_histograms.is_empty()
+            && self.col_units.is_empty()
+            && self.col_types.is_empty()
+            && self.preserved_whitespace.is_empty()
+            && !(self.header && self.abbreviate_headers)
+            && opts.max_width.is_none()
+    }
+
+    /// Render `source` into a [`Sink`], measuring the data cells with a counting
+    /// writer in the first pass and formatting each of them once, in the output pass.
+    fn render_counted<S: Sink, F: Display>(&self, sink: &mut S, source: &[Vec<F>], opts: &PrintOptions) -> Result<(), S::Error> {
+        let rows = self.row_limit(opts);
+        let header = match self.header && rows > 0 {
+            true => Some(self.format_row(source.first().map(Vec::as_slice), true)),
+            false => None,
+        };
+        let offset = usize::from(header.is_some());
+
+        let mut max_widths = vec![0; self.cols];
+        if let Some(header) = header.as_ref() {
+            self.measure_header(&mut max_widths, header);
+        }
+        for row in source.iter().take(rows).skip(offset) {
+            for (max_width, el) in max_widths.iter_mut().zip(row) {
+                *max_width = (*max_width).max(width::display_width_of(el, self.emoji_width));
+            }
+        }
+        let header_buff: Vec<Vec<String>> = header.iter().cloned().collect();
+        let (header_cells, visible) = self.fit_layout(&header_buff, &mut max_widths, opts);
+        let styled = opts.color_mode.clone().unwrap_or_default().enabled();
+
+        let mut f = |line: &str| sink.write_line_ending(line, self.line_ending.as_str());
+        let mut renderer = LineRenderer::new(self, max_widths, styled, header_cells)
+            .visible(visible)
+            .accessible(opts.accessible.unwrap_or(false));
+        if let Some(header) = header.as_ref() {
+            renderer.push_row(0, header, &mut f)?;
+        }
+        for i in offset..rows {
+            renderer.push_row(i, &self.format_row(source.get(i).map(Vec::as_slice), false), &mut f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Measure the grid as [`render`](GridPrinter::render) would print it, without
+    /// rendering it.
+    ///
+    /// ```rust
+    /// use grid_printer::{GridPrinter, Layout};
+    ///
+    /// let grid = vec![
+    ///     vec!["a", "bcd"],
+    ///     vec!["ef", "g"],
+    /// ];
+    /// let layout = GridPrinter::new(2, 2).layout(&grid);
+    /// assert_eq!(layout, Layout {
+    ///     col_widths: vec![2, 3],
+    ///     total_width: 9,
+    ///     row_heights: vec![1, 1],
+    /// });
+    /// ```
+    pub fn layout<F: Display>(&self, source: &[Vec<F>]) -> Layout {
+        if let Some(printer) = self.bind_source_names(source) {
+            return printer.layout(source);
+        }
+        let opts = &self.print_options;
+        let buff = self.format_grid(source, opts);
+
+        self.measure_layout(&buff, opts).0
+    }
+
+    /// Measure the formatted rows into a Layout, alongside the abbreviated header
+    /// cells, if any.
+    #[allow(clippy::type_complexity)]
+    fn measure_layout(&self, buff: &[Vec<String>], opts: &PrintOptions) -> (Layout, Option<Vec<String>>, Option<Vec<usize>>) {
+        let mut max_widths = self.max_widths(buff);
+        let (header_cells, visible) = self.fit_layout(buff, &mut max_widths, opts);
+        let total_width = self.line_width(&max_widths);
+
+        let renderer = LineRenderer::new(self, max_widths, false, header_cells.clone())
+            .visible(visible.clone());
+        let row_heights = buff.iter()
+            .enumerate()
+            .map(|(i, row)| match i == 0 && self.header {
+                true => renderer.render_header(row).len(),
+                false => renderer.row_height(i, row),
+            })
+            .collect();
+
+        let layout = Layout {
+            col_widths: renderer.into_widths(),
+            total_width,
+            row_heights,
+        };
+
+        (layout, header_cells, visible)
+    }
+
+    /// Measure several related grids together, so that printing each of them with
+    /// [`print_with_layout`](GridPrinter::print_with_layout) gives them identical
+    /// column widths. The row heights of the grids are concatenated in order.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let web: Vec<Vec<&str>> = vec![vec!["web-1", "ok"]];
+    /// let db: Vec<Vec<&str>> = vec![vec!["db-primary-1", "degraded"]];
+    /// let printer = GridPrinter::builder(1, 2).col_spacing(1).build();
+    ///
+    /// let layout = printer.measure(&[&web, &db]);
+    /// assert_eq!(printer.render_with_layout(&layout, &web), "web-1        ok       \n");
+    /// assert_eq!(printer.render_with_layout(&layout, &db), "db-primary-1 degraded \n");
+    /// ```
+    pub fn measure<F: Display>(&self, sources: &[&[Vec<F>]]) -> Layout {
+        let opts = &self.print_options;
+        let buffs: Vec<Vec<Vec<String>>> = sources.iter()
+            .map(|source| self.format_grid(source, opts))
+            .collect();
+
+        let mut max_widths = vec![0; self.cols];
+        for buff in buffs.iter() {
+            for (max_width, width) in max_widths.iter_mut().zip(self.max_widths(buff)) {
+                *max_width = (*max_width).max(width);
+            }
+        }
+        // Note: Shared layouts are applied to the leading columns, so key columns
+        // aren't kept in place of earlier ones here.
+        self.settle_widths(&mut max_widths);
+        self.fit_widths(&mut max_widths, opts.max_width);
+        let total_width = self.line_width(&max_widths);
+
+        let renderer = LineRenderer::new(self, max_widths, false, None);
+        let row_heights = buffs.iter()
+            .flat_map(|buff| buff.iter().enumerate())
+            .map(|(i, row)| match i == 0 && self.header {
+                true => renderer.render_header(row).len(),
+                false => renderer.row_height(i, row),
+            })
+            .collect();
+
+        Layout {
+            col_widths: renderer.into_widths(),
+            total_width,
+            row_heights,
+        }
+    }
+
+    /// Render `source` into a [`Sink`], one line at a time, with the column widths of
+    /// a shared `layout` from [`measure`](GridPrinter::measure).
+    pub fn render_with_layout_into<S: Sink, F: Display>(&self, sink: &mut S, layout: &Layout, source: &[Vec<F>]) -> Result<(), S::Error> {
+        let opts = &self.print_options;
+        let buff = self.format_grid(source, opts);
+        self.render_lines_with(&buff, layout.col_widths.clone(), opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+            .map(|_| ())
+    }
+
+    /// Render `source` into a `String` with the column widths of a shared `layout`.
+    pub fn render_with_layout<F: Display>(&self, layout: &Layout, source: &[Vec<F>]) -> String {
+        let mut buf = String::new();
+        let Ok(()) = self.render_with_layout_into(&mut buf, layout, source);
+
+        buf
+    }
+
+    /// Print `source` to stdout with the column widths of a shared `layout`.
+    #[cfg(feature = "std")]
+    pub fn print_with_layout<F: Display>(&self, layout: &Layout, source: &[Vec<F>]) {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_with_layout_into(&mut sink, layout, source).unwrap();
+        sink.0.flush().unwrap();
+    }
+
+    /// Render only the source rows within `range` into lines, excluding the line
+    /// terminators, with the column widths of a precomputed `layout`. A scrollable
+    /// UI over a huge source can measure it once with [`layout`](GridPrinter::layout)
+    /// and then format just the visible window on each frame.
+    ///
+    /// The header row, and any row with wrapped cells, renders into as many lines as
+    /// it occupies; header separators and repeated headers are not included.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid: Vec<Vec<usize>> = (0..1000).map(|i| vec![i, i * i]).collect();
+    /// let printer = GridPrinter::new(grid.len(), 2);
+    /// let layout = printer.layout(&grid);
+    ///
+    /// let window = printer.render_rows(&grid, 998..1000, &layout);
+    /// assert_eq!(window, vec!["998  996004  ", "999  998001  "]);
+    /// ```
+    pub fn render_rows<F: Display>(&self, source: &[Vec<F>], range: Range<usize>, layout: &Layout) -> Vec<String> {
+        let styled = self.print_options.color_mode.clone().unwrap_or_default().enabled();
+        let visible = match layout.col_widths.len() == self.cols {
+            true => Some(self.col_order()),
+            false => None,
+        };
+        let renderer = LineRenderer::new(self, layout.col_widths.clone(), styled, None)
+            .visible(visible);
+
+        let end = range.end.min(self.row_limit(&self.print_options));
+        let mut lines = Vec::with_capacity(end.saturating_sub(range.start));
+        for i in range.start..end {
+            let row = self.format_row(source.get(i).map(Vec::as_slice), i == 0 && self.header);
+            let rendered = match i == 0 && self.header {
+                true => renderer.render_header(&row),
+                false => {
+                    let mut line = String::new();
+                    renderer.render(&mut line, i, &row);
+                    vec![line]
+                },
+            };
+            lines.extend(rendered.iter().flat_map(|line| line.split('\n')).map(String::from));
+        }
+
+        lines
+    }
+
+    /// Lay out the grid as [`render`](GridPrinter::render) would print it, but return
+    /// the fitted text, width, and style of every printed cell instead of drawing
+    /// it, so a GUI or TUI front-end can use the GridPrinter as a layout engine.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, StyleOpt};
+    ///
+    /// let grid = vec![
+    ///     vec!["Name", "Size"],
+    ///     vec!["a.txt", "1K"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .header(true)
+    ///     .col_style(1, StyleOpt::new().fg(Fg::Cyan)).unwrap()
+    ///     .build();
+    /// let structured = printer.render_structured(&grid);
+    /// assert_eq!(structured.layout.col_widths, vec![5, 4]);
+    /// assert_eq!(structured.header.unwrap()[0].text, "Name");
+    /// assert_eq!(structured.rows[0][1].style, Some(StyleOpt::new().fg(Fg::Cyan)));
+    /// ```
+    pub fn render_structured<F: Display>(&self, source: &[Vec<F>]) -> StructuredGrid {
+        if let Some(printer) = self.bind_source_names(source) {
+            return printer.render_structured(source);
+        }
+        let opts = &self.print_options;
+        let buff = self.format_grid(source, opts);
+        let (layout, header_cells, visible) = self.measure_layout(&buff, opts);
+        let col_stats = self.col_stats(&buff);
+
+        let structure_row = |row_idx: usize, row: &[String], prev: Option<&[String]>| -> Vec<StructuredCell> {
+            let repeated = self.repeated_cols(prev, row);
+            layout.col_widths.iter()
+                .enumerate()
+                .map(|(pos, col_width)| (visible.as_ref().map_or(pos, |visible| visible[pos]), col_width))
+                .filter_map(|(col_idx, col_width)| row.get(col_idx).map(|cell| (col_idx, cell, col_width)))
+                .map(|(col_idx, cell, col_width)| {
+                    let mut style = self.cell_style(row_idx, col_idx, row, &col_stats);
+                    let mut plain: String = match (raw::contains_raw(cell), self.markup) {
+                        (true, _) => raw::unwrap_raw(cell).into_owned(),
+                        (false, true) => markup::parse(cell).iter().map(|span| span.text).collect(),
+                        (false, false) => cell.clone(),
+                    };
+                    if repeated.contains(&col_idx) {
+                        match self.repeat_style.is_some() {
+                            true => style = self.repeat_cell_style(style),
+                            false => plain.clear(),
+                        }
+                    }
+                    let (text, _, marker) = self.truncate_cell(&plain, *col_width);
+                    let mut text = text.to_string();
+                    text.push_str(marker.unwrap_or_default());
+                    StructuredCell {
+                        width: width::display_width(&text, self.emoji_width),
+                        text,
+                        style,
+                    }
+                })
+                .collect()
+        };
+
+        let (header, rows) = match self.header && !buff.is_empty() {
+            true => (Some(header_cells.as_ref().unwrap_or(&buff[0])), &buff[1..]),
+            false => (None, &buff[..]),
+        };
+        let offset = usize::from(header.is_some());
+        let header = header.map(|header| structure_row(0, header, None));
+        let rows = rows.iter()
+            .enumerate()
+            .map(|(i, row)| structure_row(i + offset, row, i.checked_sub(1).map(|prev| rows[prev].as_slice())))
+            .collect();
+
+        StructuredGrid {
+            layout,
+            header,
+            rows,
+        }
+    }
+
+    /// Render rows of tuples into a [`Sink`], one line at a time.
+    pub fn render_tuples_into<S: Sink, T: RowTuple>(&self, sink: &mut S, source: &[T]) -> Result<(), S::Error> {
+        let opts = &self.print_options;
+        let buff: Vec<Vec<String>> = (0..self.row_limit(opts))
+            .map(|i| {
+                let mut row = vec![String::new(); self.cols];
+                if let Some(tuple) = source.get(i) {
+                    tuple.for_each_cell(&mut |j, el| {
+                        if let Some(cell) = row.get_mut(j) {
+                            *cell = self.format_cell(j, Cow::Owned(format!("{}", el)), i == 0 && self.header).into_owned();
+                        }
+                    });
+                }
+                row
+            })
+            .collect();
+        self.for_each_line(&buff, opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+    }
+
+    /// Render a grid stored as a single row-major slice, `cols` cells per row, into
+    /// a [`Sink`], one line at a time.
+    pub fn render_flat_into<S: Sink, F: Display>(&self, sink: &mut S, source: &[F], cols: usize) -> Result<(), S::Error> {
+        let opts = &self.print_options;
+        let buff: Vec<Vec<String>> = (0..self.row_limit(opts))
+            .map(|i| {
+                let row = source.get(i * cols..).map(|rest| &rest[..cols.min(rest.len())]);
+                self.format_row(row.filter(|row| !row.is_empty()), i == 0 && self.header)
+            })
+            .collect();
+        self.for_each_line(&buff, opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+    }
+
+    /// Render a grid stored column by column into a [`Sink`], one line at a time.
+    /// Each inner vector of `source` is a column, and is printed downwards.
+    pub fn render_columns_into<S: Sink, F: Display>(&self, sink: &mut S, source: &[Vec<F>]) -> Result<(), S::Error> {
+        let opts = &self.print_options;
+        let buff: Vec<Vec<String>> = (0..self.row_limit(opts))
+            .map(|i| (0..self.cols)
+                .map(|j| match source.get(j).and_then(|col| col.get(i)) {
+                    None => "".to_string(),
+                    Some(el) => self.format_cell(j, Cow::Owned(format!("{}", el)), i == 0 && self.header).into_owned(),
+                })
+                .collect())
+            .collect();
+        self.for_each_line(&buff, opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+    }
+
+    /// Render the grid into a [`Sink`] like [`render_into`](GridPrinter::render_into),
+    /// reporting the rows and columns of `source` which were not printed because
+    /// the configured dimensions, maximum rows, or maximum width are too small.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6],
+    ///     vec![7, 8, 9],
+    /// ];
+    /// let mut out = String::new();
+    /// let report = GridPrinter::new(2, 2).render_checked_into(&mut out, &grid).unwrap();
+    /// assert_eq!(out, "1  2  \n4  5  \n");
+    /// assert_eq!(report.to_string(), "1 row and 1 column not shown");
+    /// ```
+    pub fn render_checked_into<S: Sink, F: Display>(&self, sink: &mut S, source: &[Vec<F>]) -> Result<TruncationReport, S::Error> {
+        if let Some(printer) = self.bind_source_names(source) {
+            return printer.render_checked_into(sink, source);
+        }
+        let opts = &self.print_options;
+        let buff = self.format_grid(source, opts);
+        let printed_cols = self.render_lines(&buff, opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))?;
+
+        // Note: Hidden columns were left out by choice and derived columns aren't in
+        // the source, so only the source columns cut by the dimensions or the
+        // maximum width count as dropped.
+        let source_width = source.iter().map(Vec::len).max().unwrap_or(0);
+        let source_cols = self.source_cols();
+        let cut_cols = (0..source_width.min(source_cols))
+            .filter(|col_idx| !self.is_hidden(*col_idx) && !printed_cols.contains(col_idx))
+            .count();
+        Ok(TruncationReport {
+            dropped_rows: source.len().saturating_sub(buff.len()),
+            dropped_cols: source_width.saturating_sub(source_cols) + cut_cols,
+        })
+    }
+
+    /// Render a list of items into a [`Sink`], flowed down as many columns as fit
+    /// within `width`, like `ls`.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::sink::LineSink;
+    ///
+    /// let files = ["Cargo.toml", "README.md", "src", "examples", "target"];
+    /// let printer = GridPrinter::builder(0, 0).col_spacing(2).build();
+    ///
+    /// let mut lines = Vec::new();
+    /// printer.render_list_into(&mut LineSink(|line: &str| lines.push(line.to_string())), &files, 32).unwrap();
+    /// assert_eq!(lines, vec![
+    ///     "Cargo.toml  src       target  ",
+    ///     "README.md   examples  ",
+    /// ]);
+    /// ```
+    pub fn render_list_into<S: Sink, F: Display>(&self, sink: &mut S, items: &[F], width: usize) -> Result<(), S::Error> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let cells: Vec<String> = items.iter()
+            .map(|item| self.normalize(Cow::Owned(format!("{}", item))).into_owned())
+            .collect();
+        let cell_widths: Vec<usize> = cells.iter().map(|cell| self.cell_width(cell)).collect();
+
+        // Use the most columns whose widest cells, with spacing, fit within `width`.
+        let rows = (1..=cells.len())
+            .find(|rows| {
+                let total: usize = cell_widths.chunks(*rows)
+                    .map(|col| col.iter().max().unwrap_or(&0) + self.col_spacing)
+                    .sum();
+                total <= width
+            })
+            .unwrap_or(cells.len());
+        let cols = cells.len().div_ceil(rows);
+
+        let buff: Vec<Vec<&str>> = (0..rows)
+            .map(|i| (0..cols).filter_map(|j| cells.get(j * rows + i).map(String::as_str)).collect())
+            .collect();
+        let printer = GridPrinter {
+            rows,
+            cols,
+            header: false,
+            col_gaps: Vec::new(),
+            col_formats: BTreeMap::new(),
+            ..self.clone()
+        };
+        let opts = PrintOptions { max_width: None, ..self.print_options.clone() };
+        printer.for_each_line(&buff, &opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+    }
+
+    /// Render the grid into a [`Sink`], one line at a time.
+    ///
+    /// A `String` sink is appended to, so clearing and reusing the same `String`
+    /// across prints reuses its allocation. See
+    /// [`render_reusing`](GridPrinter::render_reusing) to reuse the formatted cells too.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let printer = GridPrinter::new(1, 2);
+    /// let mut out = String::new();
+    /// for tick in 0..3 {
+    ///     out.clear();
+    ///     printer.render_into(&mut out, &vec![vec![tick, 1]]).unwrap();
+    /// }
+    /// assert_eq!(out, "2  1  \n");
+    /// ```
+    pub fn render_into<S: Sink, F: Display>(&self, sink: &mut S, source: &[Vec<F>]) -> Result<(), S::Error> {
+        self.render_grid(sink, source, &self.print_options)
+    }
+
+    /// Render the grid of borrowed cells into a [`Sink`], one line at a time.
+    pub fn render_cells_into<S: Sink, C: CellSource>(&self, sink: &mut S, source: &[Vec<C>]) -> Result<(), S::Error> {
+        let header: Option<Vec<Cow<str>>> = source.first().map(|row| row.iter().map(CellSource::cell).collect());
+        if let Some(printer) = self.bind_names(header.as_deref()) {
+            return printer.render_cells_into(sink, source);
+        }
+        let opts = &self.print_options;
+        let buff: Vec<Vec<Cow<str>>> = (0..self.row_limit(opts))
+            .map(|i| self.borrow_row(source.get(i).map(Vec::as_slice), i == 0 && self.header))
+            .collect();
+        self.for_each_line(&buff, opts, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+    }
+
+    /// Render the rows yielded by `source` into a [`Sink`], one line at a time.
+    pub fn render_iter_into<S, F, R, I>(&self, sink: &mut S, source: I) -> Result<(), S::Error>
+    where
+        S: Sink,
+        F: Display,
+        R: AsRef<[F]>,
+        I: IntoIterator<Item = R>,
+    {
+        self.for_each_iter_line(source, &self.print_options, |line| sink.write_line_ending(line, self.line_ending.as_str()))
+    }
+
+    /// Hand each rendered line of the grid, excluding the line terminator, to `f`,
+    /// e.g. to feed a GUI widget, a TUI framework, or a syslog writer without
+    /// splitting a rendered `String`.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["a", "b"],
+    ///     vec!["c", "d"],
+    /// ];
+    /// let mut lines = Vec::new();
+    /// GridPrinter::new(2, 2).print_lines(&grid, |line| lines.push(line.to_string()));
+    /// assert_eq!(lines, vec!["a  b  ", "c  d  "]);
+    /// ```
+    pub fn print_lines<F: Display, L: FnMut(&str)>(&self, source: &[Vec<F>], f: L) {
+        let Ok(()) = self.render_into(&mut LineSink(f), source);
+    }
+
+    /// Render the grid into a `String`, terminating each line with `\n`.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec![1, 20],
+    ///     vec![300, 4],
+    /// ];
+    /// let out = GridPrinter::new(2, 2).render(&grid);
+    /// assert_eq!(out, "1    20  \n300  4   \n");
+    /// ```
+    pub fn render<F: Display>(&self, source: &[Vec<F>]) -> String {
+        let mut out = String::new();
+        let Ok(()) = self.render_into(&mut out, source);
+
+        out
+    }
+
+    /// Render the grid into a `String` for snapshot tests of CLI output.
+    ///
+    /// The output of `render_plain` is a compatibility contract: it contains no
+    /// escape codes whatever the color mode, no trailing whitespace on any line,
+    /// and every line ends with `\n`. It depends only on the source and the
+    /// printer's configuration, never on the terminal or locale. Control characters
+    /// inside cells are subject to the printer's [`ControlChars`] policy.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, StyleOpt};
+    ///
+    /// let grid = vec![
+    ///     vec!["a", "bcd"],
+    ///     vec!["ef", "g"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_style(1, StyleOpt::new().fg(Fg::Red)).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render_plain(&grid), "a   bcd\nef  g\n");
+    /// ```
+    pub fn render_plain<F: Display>(&self, source: &[Vec<F>]) -> String {
+        let mut out = String::new();
+        let opts = self.resolve(&PrintOptions::new().color_mode(ColorMode::Never));
+        let buff = self.format_grid(source, &opts);
+        let Ok(()) = self.for_each_line(&buff, &opts, |line| {
+            out.push_str(line.trim_end());
+            out.push('\n');
+            Ok::<(), core::convert::Infallible>(())
+        });
+
+        out
+    }
+
+    /// Render the grid into a `String` like [`render`](GridPrinter::render), with the
+    /// options set in `overrides` taking precedence over the printer's own.
+    pub fn render_with<F: Display>(&self, source: &[Vec<F>], overrides: &PrintOptions) -> String {
+        let mut out = String::new();
+        let Ok(()) = self.render_grid(&mut out, source, &self.resolve(overrides));
+
+        out
+    }
+
+    /// Render the grid into any [`core::fmt::Write`], terminating each line with `\n`.
+    ///
+    /// This is the rendering path available without the `std` feature, e.g. for
+    /// writing to a serial console on an embedded target.
+    pub fn render_to<W: fmt::Write, F: Display>(&self, w: &mut W, source: &[Vec<F>]) -> fmt::Result {
+        self.render_into(&mut FmtSink(w), source)
+    }
+
+    /// Render the grid of borrowed cells into any [`core::fmt::Write`], as
+    /// [`print_cells`](GridPrinter::print_cells) does for stdout.
+    pub fn render_cells_to<W: fmt::Write, C: CellSource>(&self, w: &mut W, source: &[Vec<C>]) -> fmt::Result {
+        self.render_cells_into(&mut FmtSink(w), source)
+    }
+
+    /// Render the rows yielded by `source` into any [`core::fmt::Write`], as
+    /// [`print_iter`](GridPrinter::print_iter) does for stdout.
+    pub fn render_iter_to<W, F, R, I>(&self, w: &mut W, source: I) -> fmt::Result
+    where
+        W: fmt::Write,
+        F: Display,
+        R: AsRef<[F]>,
+        I: IntoIterator<Item = R>,
+    {
+        self.render_iter_into(&mut FmtSink(w), source)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn print<F: Display>(&self, source: &[Vec<F>]) {
+        self.print_with(source, &PrintOptions::new());
+    }
+
+    /// Print the grid like [`print`](GridPrinter::print), with the options set in
+    /// `overrides` taking precedence over the printer's own. This lets one shared
+    /// printer adapt to each invocation without being rebuilt.
+    ///
+    /// ```rust
+    /// use std::io::IsTerminal;
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::PrintOptions;
+    /// use grid_printer::style::ColorMode;
+    ///
+    /// let grid = vec![
+    ///     vec!["Ford", "Pinto", "1978"],
+    ///     vec!["Toyota", "Tacoma", "2006"],
+    /// ];
+    /// let printer = GridPrinter::new(2, 3);
+    ///
+    /// let overrides = match std::io::stdout().is_terminal() {
+    ///     true => PrintOptions::new().max_width(80),
+    ///     false => PrintOptions::new().color_mode(ColorMode::Never),
+    /// };
+    /// printer.print_with(&grid, &overrides.max_rows(1));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn print_with<F: Display>(&self, source: &[Vec<F>], overrides: &PrintOptions) {
+        if let Some(printer) = self.detect_color_support() {
+            return printer.print_with(source, overrides);
+        }
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_grid(&mut sink, source, &self.resolve(overrides)).unwrap();
+        sink.0.flush().unwrap();
+    }
+
+    /// Print the grid like [`print`](GridPrinter::print), returning a report of the
+    /// rows and columns of `source` which were not shown, so that tools can warn
+    /// about hidden data.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![vec![1, 2, 3]];
+    /// let report = GridPrinter::new(1, 2).print_checked(&grid);
+    /// if report.is_truncated() {
+    ///     eprintln!("{}", report);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn print_checked<F: Display>(&self, source: &[Vec<F>]) -> TruncationReport {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        let report = self.render_checked_into(&mut sink, source).unwrap();
+        sink.0.flush().unwrap();
+
+        report
+    }
+
+    /// Print a list of items flowed down as many columns as fit the terminal, like
+    /// `ls`. The width is the configured maximum width, or else the `COLUMNS`
+    /// environment variable, or else 80 columns.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let printer = GridPrinter::builder(0, 0).build();
+    /// printer.print_list(&["alpha", "beta", "gamma", "delta"]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn print_list<F: Display>(&self, items: &[F]) {
+        let width = self.print_options.max_width
+            .or_else(|| std::env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()))
+            .unwrap_or(80);
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_list_into(&mut sink, items, width).unwrap();
+        sink.0.flush().unwrap();
+    }
+
+    /// Print the grid like [`print`](GridPrinter::print), or, when stdout is a
+    /// terminal and the grid is taller than it, page the grid in a viewport on the
+    /// alternate screen: `j` and `k` scroll a line, space and `b` a page, and `q`
+    /// quits.
+    ///
+    /// ```rust,no_run
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid: Vec<Vec<usize>> = (0..500).map(|i| vec![i, i * i]).collect();
+    /// let printer = GridPrinter::new(grid.len(), 2);
+    /// printer.print_scrollable(&grid).unwrap();
+    /// ```
+    #[cfg(feature = "scroll")]
+    pub fn print_scrollable<F: Display>(&self, source: &[Vec<F>]) -> io::Result<()> {
+        scroll::page(&self.render(source))
+    }
+
+    /// Print a grid of cells which can lend their text directly, such as `&str` and
+    /// `String`, without formatting a new `String` for every cell.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let names = vec![
+    ///     vec!["Ada".to_string(), "Lovelace".to_string()],
+    ///     vec!["Alan".to_string(), "Turing".to_string()],
+    /// ];
+    /// let printer = GridPrinter::new(names.len(), 2);
+    /// printer.print_cells(&names);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn print_cells<C: CellSource>(&self, source: &[Vec<C>]) {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_cells_into(&mut sink, source).unwrap();
+        sink.0.flush().unwrap();
+    }
+
+    /// Print rows of tuples whose elements each implement `Display`. See
+    /// [`RowTuple`].
+    #[cfg(feature = "std")]
+    pub fn print_tuples<T: RowTuple>(&self, source: &[T]) {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_tuples_into(&mut sink, source).unwrap();
+        sink.0.flush().unwrap();
+    }
+
+    /// Print a grid stored as a single row-major slice, e.g. an image-like buffer or
+    /// the storage of a matrix crate, chunked into rows of `cols` cells.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::sink::LineSink;
+    ///
+    /// let pixels = [0, 255, 255, 0, 128, 64];
+    /// let printer = GridPrinter::new(2, 3);
+    /// printer.print_flat(&pixels, 3);
+    ///
+    /// let mut lines = Vec::new();
+    /// printer.render_flat_into(&mut LineSink(|line: &str| lines.push(line.to_string())), &pixels, 3).unwrap();
+    /// assert_eq!(lines, vec!["0  255  255  ", "0  128  64   "]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn print_flat<F: Display>(&self, source: &[F], cols: usize) {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_flat_into(&mut sink, source, cols).unwrap();
+        sink.0.flush().unwrap();
+    }
+
+    /// Print a grid stored column by column, e.g. one vector per data series,
+    /// without transposing it first. The printer's rows and columns still describe
+    /// the printed grid.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::sink::LineSink;
+    ///
+    /// let series = vec![
+    ///     vec![1, 2, 3],
+    ///     vec![10, 20, 30],
+    /// ];
+    /// let printer = GridPrinter::new(3, 2);
+    /// printer.print_columns(&series);
+    ///
+    /// let mut lines = Vec::new();
+    /// printer.render_columns_into(&mut LineSink(|line: &str| lines.push(line.to_string())), &series).unwrap();
+    /// assert_eq!(lines, vec!["1  10  ", "2  20  ", "3  30  "]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn print_columns<F: Display>(&self, source: &[Vec<F>]) {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_columns_into(&mut sink, source).unwrap();
+        sink.0.flush().unwrap();
+    }
+}
+
+impl TryFrom<GridPrinterBuilder> for GridPrinter {
+    type Error = GridPrinterErr;
+
+    fn try_from(builder: GridPrinterBuilder) -> Result<Self, Self::Error> {
+        builder.try_build()
+    }
+}
+
+impl From<GridPrinter> for GridPrinterBuilder {
+    fn from(printer: GridPrinter) -> Self {
+        GridPrinterBuilder {
+            rows: printer.rows,
+            cols: printer.source_cols(),
+            col_spacing: printer.col_spacing,
+            col_styles: printer.col_styles,
+            sample_rows: printer.sample_rows,
+            print_options: printer.print_options,
+            markup: printer.markup,
+            emoji_width: printer.emoji_width,
+            tabs: printer.tabs,
+            control_chars: printer.control_chars,
+            header: printer.header,
+            header_separator: printer.header_separator,
+            repeat_header_every: printer.repeat_header_every,
+            abbreviate_headers: printer.abbreviate_headers,
+            header_short_names: printer.header_short_names,
+            vertical_headers: printer.vertical_headers,
+            col_formats: printer.col_formats,
+            default_style: printer.default_style,
+            col_separator: printer.col_separator,
+            border_style: printer.border_style,
+            fill_background: printer.fill_background,
+            highlight_rows: printer.highlight_rows,
+            highlights: printer.highlights,
+            line_ending: printer.line_ending,
+            col_gaps: printer.col_gaps,
+            uniform_columns: printer.uniform_columns,
+            palette: printer.palette,
+            col_style_names: printer.col_style_names,
+            highlight_row_names: printer.highlight_row_names,
+            ascii_only: printer.ascii_only,
+            sort_keys: printer.sort_keys,
+            truncation_marker: printer.truncation_marker,
+            truncation_style: printer.truncation_style,
+            col_outlier_styles: printer.col_outlier_styles,
+            derived_cols: printer.derived_cols,
+            rank_top_style: printer.rank_top_style,
+            suppress_repeats: printer.suppress_repeats,
+            repeat_style: printer.repeat_style,
+            key_cols: printer.key_cols,
+            wrap_columns: printer.wrap_columns,
+            column_order: printer.column_order,
+            col_footer_histograms: printer.col_footer_histograms,
+            col_units: printer.col_units,
+            col_types: printer.col_types,
+            collation: printer.collation,
+            columns: printer.columns,
+            hidden_cols: printer.hidden_cols,
+            named_cols: printer.named_cols,
+            minimize_escapes: printer.minimize_escapes,
+            color_support: printer.color_support,
+            col_overflows: printer.col_overflows,
+            preserved_whitespace: printer.preserved_whitespace,
+        }
+    }
+}
+
+/// A Builder to create/customize a GridPrinter instance
+/// ```rust
+/// use grid_printer::GridPrinter;
+/// use grid_printer::GridPrinterBuilder;
+/// 
+/// let rows = 3;
+/// let cols = 3;
+/// let printer: GridPrinter = GridPrinterBuilder::new(rows, cols)
+///     .col_spacing(4)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GridPrinterBuilder {
+    rows: usize,
+    cols: usize,
+    col_spacing: usize,
+    col_styles: Option<Vec<Option<StyleOpt>>>,
+    sample_rows: Option<usize>,
+    print_options: PrintOptions,
+    markup: bool,
+    emoji_width: EmojiWidth,
+    tabs: Option<Tabs>,
+    control_chars: ControlChars,
+    header: bool,
+    header_separator: Option<char>,
+    repeat_header_every: Option<usize>,
+    abbreviate_headers: bool,
+    header_short_names: BTreeMap<String, String>,
+    vertical_headers: bool,
+    col_formats: BTreeMap<usize, ColFormat>,
+    default_style: Option<StyleOpt>,
+    col_separator: Option<char>,
+    border_style: Option<StyleOpt>,
+    fill_background: bool,
+    highlight_rows: BTreeMap<usize, StyleOpt>,
+    highlights: Vec<(Pattern, StyleOpt)>,
+    line_ending: LineEnding,
+    col_gaps: Vec<(usize, usize, usize)>,
+    uniform_columns: bool,
+    palette: Palette,
+    col_style_names: BTreeMap<usize, String>,
+    highlight_row_names: BTreeMap<usize, String>,
+    ascii_only: bool,
+    sort_keys: Vec<(usize, SortOrder)>,
+    truncation_marker: Option<String>,
+    truncation_style: Option<StyleOpt>,
+    col_outlier_styles: BTreeMap<usize, (StyleOpt, OutlierRule)>,
+    derived_cols: Vec<DerivedCol>,
+    rank_top_style: Option<(usize, StyleOpt)>,
+    suppress_repeats: BTreeSet<usize>,
+    repeat_style: Option<StyleOpt>,
+    key_cols: BTreeSet<usize>,
+    wrap_columns: bool,
+    column_order: Option<Vec<usize>>,
+    col_footer_histograms: BTreeMap<usize, usize>,
+    col_units: BTreeMap<usize, UnitScale>,
+    col_types: BTreeMap<usize, ColType>,
+    collation: Collation,
+    columns: Vec<ColumnSpec>,
+    hidden_cols: BTreeSet<usize>,
+    named_cols: Vec<(String, NamedCol)>,
+    minimize_escapes: bool,
+    color_support: Option<ColorSupport>,
+    col_overflows: BTreeMap<usize, Overflow>,
+    preserved_whitespace: BTreeSet<usize>,
+}
+
+impl Default for GridPrinterBuilder {
+     fn default() -> Self {
+        Self {
+            rows: 1,
+            cols: 1,
+            col_spacing: 2,
+            col_styles: None,
+            sample_rows: None,
+            print_options: PrintOptions::default(),
+            markup: false,
+            emoji_width: EmojiWidth::Two,
+            tabs: None,
+            control_chars: ControlChars::Keep,
+            header: false,
+            header_separator: None,
+            repeat_header_every: None,
+            abbreviate_headers: false,
+            header_short_names: BTreeMap::new(),
+            vertical_headers: false,
+            col_formats: BTreeMap::new(),
+            default_style: None,
+            col_separator: None,
+            border_style: None,
+            fill_background: false,
+            highlight_rows: BTreeMap::new(),
+            highlights: Vec::new(),
+            line_ending: LineEnding::default(),
+            col_gaps: Vec::new(),
+            uniform_columns: false,
+            palette: Palette::new(),
+            col_style_names: BTreeMap::new(),
+            highlight_row_names: BTreeMap::new(),
+            ascii_only: false,
+            sort_keys: Vec::new(),
+            truncation_marker: None,
+            truncation_style: None,
+            col_outlier_styles: BTreeMap::new(),
+            derived_cols: Vec::new(),
+            rank_top_style: None,
+            suppress_repeats: BTreeSet::new(),
+            repeat_style: None,
+            key_cols: BTreeSet::new(),
+            wrap_columns: false,
+            column_order: None,
+            col_footer_histograms: BTreeMap::new(),
+            col_units: BTreeMap::new(),
+            col_types: BTreeMap::new(),
+            collation: Collation::Binary,
+            columns: Vec::new(),
+            hidden_cols: BTreeSet::new(),
+            named_cols: Vec::new(),
+            minimize_escapes: false,
+            color_support: None,
+            col_overflows: BTreeMap::new(),
+            preserved_whitespace: BTreeSet::new(),
+        }
+    }
+}
+
+impl GridPrinterBuilder {
+
+    pub fn new(rows: usize, cols: usize) -> Self {
+        GridPrinterBuilder {
+            rows,
+            cols,
+            ..Default::default()
+        }
+    }
+
+    pub fn col_spacing(mut self, col_spacing: usize) -> Self {
+        self.col_spacing = col_spacing;
+
+        self
+    }
+
+    /// Set the spacing between the adjacent columns `i` and `j` to `n`, overriding
+    /// the column spacing, so that related columns can sit tightly together while
+    /// groups of columns stay visually separated.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["x", "1", "2", "y", "3", "4"],
+    /// ];
+    /// let printer = GridPrinter::builder(1, 6)
+    ///     .col_spacing(1)
+    ///     .col_spacing_between(2, 3, 4).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "x 1 2    y 3 4 \n");
+    /// ```
+    pub fn col_spacing_between(mut self, i: usize, j: usize, n: usize) -> Result<Self, GridPrinterErr> {
+        if i + 1 != j || j >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_gaps.push((i, j, n));
+
+        Ok(self)
+    }
+
+    /// Sort the data rows by the values of column `col`, with numbers ordered by value
+    /// before all other values, and mark the column's header with `▲` or `▼` (`^` or `v` when
+    /// [`ascii_only`](GridPrinterBuilder::ascii_only)). The header width accounts for
+    /// the indicator. Rows streamed with [`print_iter`](GridPrinter::print_iter) are
+    /// printed in source order. [`SortOrder::Natural`] orders text containing
+    /// numbers, such as file names, by the value of those numbers.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::options::SortOrder;
+    ///
+    /// let grid = vec![
+    ///     vec!["Name", "Size"],
+    ///     vec!["a.txt", "9"],
+    ///     vec!["b.txt", "10"],
+    /// ];
+    /// let printer = GridPrinter::builder(3, 2)
+    ///     .col_spacing(1)
+    ///     .header(true)
+    ///     .sort_by_col(1, SortOrder::Descending).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "Name  Size ▼ \nb.txt 10     \na.txt 9      \n");
+    /// ```
+    pub fn sort_by_col<C: Into<ColRef>>(self, col: C, order: SortOrder) -> Result<Self, GridPrinterErr> {
+        self.sort_by(&[(col.into(), order)])
+    }
+
+    /// Sort the data rows by several columns, in priority order: rows which are equal
+    /// in the first column are ordered by the second, and so on, like SQL's
+    /// `ORDER BY a, b DESC`. The header of each column is marked as with
+    /// [`sort_by_col`](GridPrinterBuilder::sort_by_col), which this replaces.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::options::SortOrder;
+    ///
+    /// let grid = vec![
+    ///     vec!["b", "1"],
+    ///     vec!["a", "1"],
+    ///     vec!["c", "2"],
+    /// ];
+    /// let printer = GridPrinter::builder(3, 2)
+    ///     .col_spacing(1)
+    ///     .sort_by(&[(1, SortOrder::Descending), (0, SortOrder::Ascending)]).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "c 2 \na 1 \nb 1 \n");
+    /// ```
+    pub fn sort_by<C: Into<ColRef> + Clone>(mut self, keys: &[(C, SortOrder)]) -> Result<Self, GridPrinterErr> {
+        let cols = self.cols;
+        if keys.iter().any(|(col, _)| matches!(col.clone().into(), ColRef::Index(idx) if idx >= cols)) {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.set_sort_by(keys);
+
+        Ok(self)
+    }
+
+    /// Style the numeric cells of the column at `idx` which are outliers under `rule`,
+    /// with statistics measured over the column's data cells as the grid is measured.
+    /// This makes slow runs or spikes stand out in benchmark and metrics tables.
+    ///
+    /// When printing with [`print_iter`](GridPrinter::print_iter) and
+    /// [`sample_rows`](GridPrinterBuilder::sample_rows), only the sampled rows are measured.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::options::OutlierRule;
+    /// use grid_printer::style::{StyleOpt, Fg};
+    ///
+    /// let timings = vec![
+    ///     vec!["parse", "12"],
+    ///     vec!["check", "11"],
+    ///     vec!["lower", "13"],
+    ///     vec!["codegen", "95"],
+    /// ];
+    /// let printer = GridPrinter::builder(timings.len(), 2)
+    ///     .col_outlier_style(1, StyleOpt::new().fg(Fg::Red), OutlierRule::StdDev(1.5))
+    ///     .unwrap()
+    ///     .build();
+    /// printer.print(&timings);
+    /// ```
+    pub fn col_outlier_style(mut self, idx: usize, style: StyleOpt, rule: OutlierRule) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_outlier_styles.insert(idx, (style, rule));
+
+        Ok(self)
+    }
+
+    /// Print a histogram of the numeric cells of the column at `idx` beneath it, as
+    /// `buckets` block characters from the smallest value to the largest, each as
+    /// tall as the share of values falling in its range. This gives an at-a-glance
+    /// summary of the distribution, e.g. of the timings in a benchmark table.
+    ///
+    /// The histogram is not printed when streaming rows with
+    /// [`print_iter`](GridPrinter::print_iter).
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let timings = vec![
+    ///     vec!["parse", "1"],
+    ///     vec!["check", "2"],
+    ///     vec!["lower", "2"],
+    ///     vec!["codegen", "4"],
+    /// ];
+    /// let printer = GridPrinter::builder(timings.len(), 2)
+    ///     .col_footer_histogram(1, 3)
+    ///     .unwrap()
+    ///     .build();
+    /// assert_eq!(
+    ///     printer.render(&timings),
+    ///     "parse    1    \ncheck    2    \nlower    2    \ncodegen  4    \n         ▄█▄  \n",
+    /// );
+    /// ```
+    pub fn col_footer_histogram(mut self, idx: usize, buckets: usize) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_footer_histograms.insert(idx, buckets);
+
+        Ok(self)
+    }
+
+    /// Append a column showing each row's share of the total of the numeric column
+    /// at `idx`, as a percentage with one decimal place. The percentages are right
+    /// aligned, and the header, if any, is `% of` followed by the header of `idx`.
+    ///
+    /// Derived columns follow the source columns, and are computed when the whole grid
+    /// is printed at once, e.g. with [`print`](GridPrinter::print) or
+    /// [`render`](GridPrinter::render); they are left empty when streaming rows.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["Region", "Sales"],
+    ///     vec!["North", "300"],
+    ///     vec!["South", "25"],
+    ///     vec!["West", "175"],
+    /// ];
+    /// let printer = GridPrinter::builder(grid.len(), 2)
+    ///     .header(true)
+    ///     .col_spacing(1)
+    ///     .derived_col_percent_of(1)
+    ///     .unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "\
+    /// Region Sales % of Sales \n\
+    /// North  300   60.0%      \n\
+    /// South  25     5.0%      \n\
+    /// West   175   35.0%      \n");
+    /// ```
+    pub fn derived_col_percent_of(mut self, idx: usize) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.derived_cols.push(DerivedCol::PercentOf(idx));
+
+        Ok(self)
+    }
+
+    /// Append a column under `header` computed by `f` from the formatted cells of each
+    /// data row before it, e.g. a price times a quantity, so that display-only
+    /// calculations don't need to be stored in the source.
+    ///
+    /// Like [`derived_col_percent_of`](GridPrinterBuilder::derived_col_percent_of), the
+    /// column is computed when the whole grid is printed at once. A printer with
+    /// computed columns cannot be serialized, as the function cannot be.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["Item", "Price", "Qty"],
+    ///     vec!["Apple", "0.50", "4"],
+    ///     vec!["Pear", "0.75", "2"],
+    /// ];
+    /// let printer = GridPrinter::builder(grid.len(), 3)
+    ///     .header(true)
+    ///     .col_spacing(1)
+    ///     .derived_col("Total", |row| {
+    ///         let price: f64 = row[1].parse().unwrap_or(0.0);
+    ///         let qty: f64 = row[2].parse().unwrap_or(0.0);
+    ///         format!("{:.2}", price * qty)
+    ///     })
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "\
+    /// Item  Price Qty Total \n\
+    /// Apple 0.50  4   2.00  \n\
+    /// Pear  0.75  2   1.50  \n");
+    /// ```
+    pub fn derived_col<F>(mut self, header: &str, f: F) -> Self
+    where
+        F: Fn(&[String]) -> String + Send + Sync + 'static,
+    {
+        self.derived_cols.push(DerivedCol::Fn(header.to_string(), Arc::new(f)));
+
+        self
+    }
+
+    /// Append a column ranking each row by the numeric column at `idx`, the largest
+    /// value ranked `1`. Tied rows share a rank. The header, if any, is `Rank`.
+    ///
+    /// Like [`derived_col_percent_of`](GridPrinterBuilder::derived_col_percent_of), the
+    /// column is computed when the whole grid is printed at once.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{StyleOpt, Sgr};
+    ///
+    /// let grid = vec![
+    ///     vec!["Query", "ms"],
+    ///     vec!["users", "12"],
+    ///     vec!["orders", "340"],
+    ///     vec!["items", "85"],
+    /// ];
+    /// let printer = GridPrinter::builder(grid.len(), 2)
+    ///     .header(true)
+    ///     .col_spacing(1)
+    ///     .rank_by_col(1)
+    ///     .unwrap()
+    ///     .rank_top_style(1, StyleOpt::new().sgr(Sgr::Bold))
+    ///     .build();
+    /// printer.print(&grid);
+    /// ```
+    pub fn rank_by_col(mut self, idx: usize) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.derived_cols.push(DerivedCol::Rank(idx));
+
+        Ok(self)
+    }
+
+    /// Style the rows ranked in the top `n` by the column appended with
+    /// [`rank_by_col`](GridPrinterBuilder::rank_by_col), e.g. to make the slowest
+    /// queries of a report stand out.
+    pub fn rank_top_style(mut self, n: usize, style: StyleOpt) -> Self {
+        self.rank_top_style = Some((n, style));
+
+        self
+    }
+
+    /// Blank each cell of the column at `idx` which equals the cell printed above it,
+    /// for the grouped look of a report, e.g. with each date shown only on its first
+    /// row. Set a [`repeat_style`](GridPrinterBuilder::repeat_style) to dim the repeats
+    /// instead. Accessible output keeps every value.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["2024-05-01", "deploy"],
+    ///     vec!["2024-05-01", "rollback"],
+    ///     vec!["2024-05-02", "deploy"],
+    /// ];
+    /// let printer = GridPrinter::builder(grid.len(), 2)
+    ///     .col_spacing(1)
+    ///     .suppress_repeats(0)
+    ///     .unwrap()
+    ///     .build();
+    /// let rendered = printer.render(&grid);
+    /// let lines: Vec<&str> = rendered.lines().collect();
+    /// assert_eq!(lines, vec![
+    ///     "2024-05-01 deploy   ",
+    ///     "           rollback ",
+    ///     "2024-05-02 deploy   ",
+    /// ]);
+    /// ```
+    pub fn suppress_repeats(mut self, idx: usize) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.suppress_repeats.insert(idx);
+
+        Ok(self)
+    }
+
+    /// Style the repeated cells of the columns set with
+    /// [`suppress_repeats`](GridPrinterBuilder::suppress_repeats), e.g. faint, rather
+    /// than blanking them.
+    pub fn repeat_style(mut self, repeat_style: StyleOpt) -> Self {
+        self.repeat_style = Some(repeat_style);
+
+        self
+    }
+
+    /// Keep the columns at `idxs`, e.g. a name column, when the grid is wider than the
+    /// [`max_width`](GridPrinterBuilder::max_width), dropping the other columns which
+    /// don't fit instead. The columns stay in their order.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["id", "region", "cpu", "host"],
+    ///     vec!["1", "us-east-1", "93%", "web-1"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 4)
+    ///     .col_spacing(1)
+    ///     .max_width(20)
+    ///     .key_cols(&[3])
+    ///     .unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "id region    host  \n1  us-east-1 web-1 \n");
+    /// ```
+    pub fn key_cols(mut self, idxs: &[usize]) -> Result<Self, GridPrinterErr> {
+        if idxs.iter().any(|idx| *idx >= self.cols) {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.key_cols.extend(idxs);
+
+        Ok(self)
+    }
+
+    /// Print the columns in `order`, which names every column by its index exactly
+    /// once, including the columns appended by derived columns. Unlike the other
+    /// options taking column indices, the order is checked by
+    /// [`try_build`](GridPrinterBuilder::try_build), since derived columns may be
+    /// appended after it is set; an invalid order leaves the columns in place.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["Price", "Qty", "Item"],
+    ///     vec!["0.50", "4", "Apple"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 3)
+    ///     .header(true)
+    ///     .col_spacing(1)
+    ///     .derived_col("Total", |row| row[1].clone() + " x " + &row[0])
+    ///     .column_order(&[2, 3, 0, 1])
+    ///     .try_build()
+    ///     .unwrap();
+    /// assert_eq!(printer.render(&grid), "Item  Total    Price Qty \nApple 4 x 0.50 0.50  4   \n");
+    /// ```
+    pub fn column_order(mut self, order: &[usize]) -> Self {
+        self.column_order = Some(order.to_vec());
+
+        self
+    }
+
+    /// Describe every column with a [`ColumnSpec`], in the order of the source's
+    /// columns, instead of configuring them one index at a time. The style and format
+    /// of each spec are set as with [`col_style`](GridPrinterBuilder::col_style) and
+    /// [`col_format`](GridPrinterBuilder::col_format).
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::column::{Align, ColumnSpec};
+    ///
+    /// let grid = vec![
+    ///     vec!["", "", ""],
+    ///     vec!["Lamp", "L-100", "24.50"],
+    ///     vec!["Desk", "D-220", "180.00"],
+    /// ];
+    /// let printer = GridPrinter::builder(grid.len(), 3)
+    ///     .header(true)
+    ///     .columns(vec![
+    ///         ColumnSpec::new("Item"),
+    ///         ColumnSpec::new("SKU").hidden(true),
+    ///         ColumnSpec::new("Price").align(Align::Right).width(7),
+    ///     ])
+    ///     .unwrap()
+    ///     .col_spacing(1)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "Item   Price \nLamp   24.50 \nDesk  180.00 \n");
+    /// ```
+    pub fn columns(mut self, columns: Vec<ColumnSpec>) -> Result<Self, GridPrinterErr> {
+        if columns.len() != self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.set_columns(columns);
+
+        Ok(self)
+    }
+
+    pub fn col_styles(mut self, col_styles: Vec<Option<StyleOpt>>) -> Result<Self, GridPrinterErr> {
+        match col_styles.len() == self.cols {
+            false => Err(GridPrinterErr::DimensionErr),
+            true => {
+                self.col_styles = Some(col_styles);
+
+                Ok(self)
+            }
+        }
+    }
+
+    /// Set the style of the column `col`, referred to by index or by name.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, StyleOpt};
+    ///
+    /// let grid = vec![
+    ///     vec!["Item", "Price"],
+    ///     vec!["Lamp", "24.50"],
+    /// ];
+    /// let printer = GridPrinter::builder(grid.len(), 2)
+    ///     .header(true)
+    ///     .col_style("Price", StyleOpt::new().fg(Fg::Green)).unwrap()
+    ///     .build();
+    /// printer.print(&grid);
+    /// ```
+    pub fn col_style<C: Into<ColRef>>(mut self, col: C, opt: StyleOpt) -> Result<Self, GridPrinterErr> {
+        let idx = match col.into() {
+            ColRef::Index(idx) => idx,
+            ColRef::Name(name) => {
+                self.named_cols.push((name, NamedCol::Style(opt)));
+                return Ok(self);
+            },
+        };
+        // Note: The size check here is somewhat redundant given the subsequent logic; however,
+        // performing the check here guarantees we don't mutate the GridPrinterBuilder by adding
+        // a Vec for an index that is outside the column range.
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+
+        let col_styles = self.col_styles.get_or_insert(vec![None; self.cols]);
+        let col_style = col_styles.get_mut(idx)
+            .ok_or(GridPrinterErr::DimensionErr)?;
+        *col_style = Some(opt);
+
+        Ok(self)
+    }
+
+    /// Leave the column `col`, referred to by index or by name, out of the printed grid.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["Item", "SKU", "Price"],
+    ///     vec!["Lamp", "L-100", "24.50"],
+    /// ];
+    /// let printer = GridPrinter::builder(grid.len(), 3)
+    ///     .header(true)
+    ///     .hide_column("SKU").unwrap()
+    ///     .col_spacing(1)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "Item Price \nLamp 24.50 \n");
+    /// ```
+    pub fn hide_column<C: Into<ColRef>>(mut self, col: C) -> Result<Self, GridPrinterErr> {
+        match col.into() {
+            ColRef::Index(idx) if idx >= self.cols => return Err(GridPrinterErr::DimensionErr),
+            ColRef::Index(idx) => {
+                self.hidden_cols.insert(idx);
+            },
+            ColRef::Name(name) => self.named_cols.push((name, NamedCol::Hide)),
+        }
+
+        Ok(self)
+    }
+
+    /// Set the style for a single column to the [`palette`](GridPrinterBuilder::palette)
+    /// style `name`, which is looked up when printing.
+    pub fn col_style_named(mut self, idx: usize, name: &str) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_style_names.insert(idx, name.to_string());
+
+        Ok(self)
+    }
+
+    /// Convert every cell of a column, excluding the header row, with the given
+    /// [`ColFormat`]. Cells the conversion does not recognize are left unchanged.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::format::Humanize;
+    ///
+    /// let grid = vec![
+    ///     vec!["backup", "1288490188", "7980"],
+    ///     vec!["logs", "512", "45"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 3)
+    ///     .col_spacing(1)
+    ///     .col_format(1, Humanize::Bytes).unwrap()
+    ///     .col_format(2, Humanize::Duration).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "backup 1.2 GiB 2h 13m \nlogs   512 B   45s    \n");
+    /// ```
+    pub fn col_format<C: Into<ColFormat>>(mut self, idx: usize, col_format: C) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_formats.insert(idx, col_format.into());
+
+        Ok(self)
+    }
+
+    /// Set what happens to the cells of the column at `idx` which are wider than it,
+    /// so each column can size to its content, be cut at a width, or wrap. Lines are
+    /// broken at spaces or after hyphens, or, with the `textwrap` feature, where the
+    /// Unicode line breaking algorithm allows. Only cells of plain text wrap; cells
+    /// with markup, raw text, or kept escape sequences are cut instead.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::column::Overflow;
+    ///
+    /// let grid = vec![
+    ///     vec!["lint", "a description to wrap", "a message to cut"],
+    /// ];
+    /// let printer = GridPrinter::builder(1, 3)
+    ///     .col_spacing(1)
+    ///     .max_width(30)
+    ///     .col_overflow(1, Overflow::Wrap).unwrap()
+    ///     .col_overflow(2, Overflow::Truncate(8)).unwrap()
+    ///     .truncation_marker("…")
+    ///     .build();
+    /// let lines: Vec<String> = printer.render(&grid).lines().map(String::from).collect();
+    /// assert_eq!(lines, vec![
+    ///     "lint a description   a messa… ",
+    ///     "     to wrap                  ",
+    /// ]);
+    /// ```
+    pub fn col_overflow(mut self, idx: usize, overflow: Overflow) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_overflows.insert(idx, overflow);
+
+        Ok(self)
+    }
+
+    /// Preserve the whitespace of the cells of the column at `idx`, such as the
+    /// indentation of code or the leading space of unchanged diff lines, so that it
+    /// isn't mistaken for padding. Tabs are expanded at stops of 8 columns, unless
+    /// set with [`tabs`](GridPrinterBuilder::tabs), so indentation is measured, and a
+    /// wrapping column is cut at its width rather than reflowed at spaces.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["+", "fn main() {"],
+    ///     vec!["+", "\tprintln!();"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_spacing(1)
+    ///     .preserve_whitespace(1, true).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "+ fn main() {         \n+         println!(); \n");
+    /// ```
+    pub fn preserve_whitespace(mut self, idx: usize, preserve: bool) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.set_preserve_whitespace(idx, preserve);
+
+        Ok(self)
+    }
+
+    /// Scale the values of the column at `idx`, which may be written in different
+    /// units, to a common unit, and note it in the header, e.g. `latency (ms)`.
+    ///
+    /// The column is not scaled when streaming rows with
+    /// [`print_iter`](GridPrinter::print_iter).
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::format::{Units, UnitScale};
+    ///
+    /// let requests = vec![
+    ///     vec!["path", "latency"],
+    ///     vec!["/", "1.2s"],
+    ///     vec!["/login", "250ms"],
+    /// ];
+    /// let printer = GridPrinter::builder(requests.len(), 2)
+    ///     .header(true)
+    ///     .col_units(1, UnitScale::new(Units::Time)).unwrap()
+    ///     .col_spacing(1)
+    ///     .build();
+    /// assert_eq!(printer.render(&requests), "path   latency (ms) \n/      1200         \n/login 250          \n");
+    /// ```
+    pub fn col_units(mut self, idx: usize, unit_scale: UnitScale) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_units.insert(idx, unit_scale);
+
+        Ok(self)
+    }
+
+    /// Set the kind of values the column at `idx` holds, which decides how rows are
+    /// ordered when sorting by it with [`sort_by_col`](GridPrinterBuilder::sort_by_col).
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::options::SortOrder;
+    /// use grid_printer::sort::ColType;
+    ///
+    /// let hosts = vec![
+    ///     vec!["web", "10.0.0.10"],
+    ///     vec!["db", "10.0.0.9:5432"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_spacing(1)
+    ///     .col_type(1, ColType::Ip { align_octets: true }).unwrap()
+    ///     .sort_by_col(1, SortOrder::Ascending).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&hosts), "db   10.  0.  0.  9:5432 \nweb  10.  0.  0. 10      \n");
+    /// ```
+    pub fn col_type(mut self, idx: usize, col_type: ColType) -> Result<Self, GridPrinterErr> {
+        if idx >= self.cols {
+            return Err(GridPrinterErr::DimensionErr);
+        }
+        self.col_types.insert(idx, col_type);
+
+        Ok(self)
+    }
+
+    /// Set the order of text when sorting with [`sort_by_col`](GridPrinterBuilder::sort_by_col),
+    /// e.g. ignoring case. Text is ordered by code point by default.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::options::SortOrder;
+    /// use grid_printer::sort::Collation;
+    ///
+    /// let grid = vec![vec!["zoe"], vec!["Bob"], vec!["alice"]];
+    /// let printer = GridPrinter::builder(3, 1)
+    ///     .col_spacing(0)
+    ///     .sort_by_col(0, SortOrder::Ascending).unwrap()
+    ///     .collation(Collation::CaseInsensitive)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "alice\nBob  \nzoe  \n");
+    /// ```
+    pub fn collation(mut self, collation: Collation) -> Self {
+        self.collation = collation;
+
+        self
+    }
+
+    /// Render the truthy and falsey values of a column in the given [`BoolStyle`].
+    /// Values which are not recognized as booleans are left unchanged, as is the
+    /// header row.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::format::BoolStyle;
+    ///
+    /// let grid = vec![
+    ///     vec!["std", "true"],
+    ///     vec!["simd", "0"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_spacing(1)
+    ///     .col_bool(1, BoolStyle::Check).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "std  ✓ \nsimd ✗ \n");
+    /// ```
+    pub fn col_bool(self, idx: usize, bool_style: BoolStyle) -> Result<Self, GridPrinterErr> {
+        self.col_format(idx, bool_style)
+    }
+
+    /// Render the known status keywords of a column as colored badges.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::format::StatusRenderer;
+    ///
+    /// let grid = vec![
+    ///     vec!["build", "pass"],
+    ///     vec!["lint", "skipped"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_spacing(1)
+    ///     .col_status(1, StatusRenderer::default()).unwrap()
+    ///     .color_mode(grid_printer::style::ColorMode::Never)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "build  PASS   \nlint  skipped \n");
+    /// ```
+    pub fn col_status(self, idx: usize, status: StatusRenderer) -> Result<Self, GridPrinterErr> {
+        self.col_format(idx, status)
+    }
+
+    /// Render the amounts of a column as money, with a currency symbol, thousands
+    /// separators, and rounded decimals. Negative amounts are written as set by
+    /// [`CurrencyOpts::negative`].
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::format::{CurrencyOpts, NegativeStyle};
+    ///
+    /// let grid = vec![
+    ///     vec!["rent", "-1850"],
+    ///     vec!["salary", "4200.5"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_spacing(1)
+    ///     .col_currency(1, CurrencyOpts::default().negative(NegativeStyle::Parens)).unwrap()
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "rent   ($1,850.00) \nsalary $4,200.50   \n");
+    /// ```
+    pub fn col_currency(self, idx: usize, currency: CurrencyOpts) -> Result<Self, GridPrinterErr> {
+        self.col_format(idx, currency)
+    }
+
+    /// Set the style applied to every cell, including the header row. Column styles
+    /// and column formats override the options they set, so the whole grid can be
+    /// dimmed while single columns stand out.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, Sgr, StyleOpt};
+    ///
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .default_style(StyleOpt::new().fg(Fg::BrightBlack).sgr(Sgr::Faint))
+    ///     .col_style(1, StyleOpt::new().fg(Fg::Red)).unwrap()
+    ///     .build();
+    /// printer.print(&vec![vec!["a", "b"], vec!["c", "d"]]);
+    /// ```
+    pub fn default_style(mut self, default_style: StyleOpt) -> Self {
+        self.default_style = Some(default_style);
+
+        self
+    }
+
+    /// Set the [`Palette`] of named styles referenced by
+    /// [`col_style_named`](GridPrinterBuilder::col_style_named) and
+    /// [`highlight_rows_named`](GridPrinterBuilder::highlight_rows_named).
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+
+        self
+    }
+
+    /// Apply the appearance settings of environment variables over those set so far,
+    /// so the end users of a tool can adjust its grids without changes to its code.
+    /// Call it after the other options. Values which don't parse are ignored.
+    ///
+    /// - `NO_COLOR`: when set and not empty, never apply styles.
+    /// - `GRID_PRINTER_COLOR`: `always`, `never`, or `auto`, taking precedence over
+    ///   `NO_COLOR`.
+    /// - `GRID_PRINTER_THEME`: `dark` or `light`, for the [`Palette::theme`] of named
+    ///   styles, replacing those of the same names and keeping the others.
+    /// - `GRID_PRINTER_MAXWIDTH`: the [`max_width`](GridPrinterBuilder::max_width).
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .max_width(120)
+    ///     .env_overrides()
+    ///     .build();
+    /// printer.print(&vec![vec!["a", "b"], vec!["c", "d"]]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn env_overrides(mut self) -> Self {
+        self.set_env_overrides();
+
+        self
+    }
+
+    /// Measure the column widths from only the first `n` rows when printing with
+    /// [`print_iter`](GridPrinter::print_iter), streaming the rest with those widths.
+    /// Later cells which are too wide are cut with the
+    /// [`truncation_marker`](GridPrinterBuilder::truncation_marker).
+    pub fn sample_rows(mut self, n: usize) -> Self {
+        self.sample_rows = Some(n);
+
+        self
+    }
+
+    /// Set when the column styles are applied.
+    pub fn color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.print_options.color_mode = Some(color_mode);
+
+        self
+    }
+
+    /// Drop the trailing columns which do not fit within `max_width` characters.
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.print_options.max_width = Some(max_width);
+
+        self
+    }
+
+    /// Print at most `max_rows` rows.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.print_options.max_rows = Some(max_rows);
+
+        self
+    }
+
+    /// Print each row as a `Header: value; Header: value` line instead of aligned
+    /// columns, which reads far better through a screen reader. Without a header
+    /// row, the columns are labelled `Column 1`, `Column 2`, and so on. The mode can
+    /// also be chosen for a single print with [`PrintOptions::accessible`].
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["Name", "Size"],
+    ///     vec!["a.txt", "1K"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .header(true)
+    ///     .accessible(true)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "Name: a.txt; Size: 1K\n");
+    /// ```
+    pub fn accessible(mut self, accessible: bool) -> Self {
+        self.print_options.accessible = Some(accessible);
+
+        self
+    }
+
+    /// Parse inline style markup in cell values, e.g. `[red]FAILED[/]`.
+    ///
+    /// A tag such as `[red]`, `[bold]`, or `[black on yellow]` styles the text
+    /// which follows it until the matching `[/]`, over any column style. Tags nest,
+    /// `[[` produces a literal `[`, and a bracketed word which is not a style name
+    /// (see the `FromStr` impls of [`Fg`](style::Fg), [`Bg`](style::Bg), and
+    /// [`Sgr`](style::Sgr)) is left untouched. Tags do not count towards the
+    /// column widths.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let results = vec![
+    ///     vec!["parse", "[green]ok[/]"],
+    ///     vec!["render", "[bold red]FAILED[/]"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .markup(true)
+    ///     .build();
+    /// printer.print(&results);
+    /// ```
+    pub fn markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+
+        self
+    }
+
+    /// Set how many columns wide emoji are measured as, for terminals which draw
+    /// them one column wide instead of two.
+    pub fn emoji_width(mut self, emoji_width: EmojiWidth) -> Self {
+        self.emoji_width = emoji_width;
+
+        self
+    }
+
+    /// Expand tab characters in cell values to the next multiple of `tab_width`
+    /// columns, so that they can't break the alignment of the grid.
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tabs = Some(Tabs::Expand(tab_width));
+
+        self
+    }
+
+    /// Set how tab characters in cell values are handled.
+    pub fn tabs(mut self, tabs: Tabs) -> Self {
+        self.tabs = Some(tabs);
+
+        self
+    }
+
+    /// Set how control characters in cell values, such as `\r` or `\x1b`, are
+    /// handled. Tabs are expanded first when [`tab_width`](GridPrinterBuilder::tab_width) is set.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::text::ControlChars;
+    ///
+    /// let grid = vec![
+    ///     vec!["ok", "\x1b[2Jcleared\r"],
+    /// ];
+    /// let printer = GridPrinter::builder(1, 2)
+    ///     .control_chars(ControlChars::Strip)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "ok  cleared  \n");
+    /// ```
+    pub fn control_chars(mut self, control_chars: ControlChars) -> Self {
+        self.control_chars = control_chars;
+
+        self
+    }
+
+    /// Replace every Unicode glyph drawn by the printer, such as box-drawing
+    /// separators, the truncation ellipsis, check marks, and progress bar blocks,
+    /// with its single-column [ASCII equivalent](crate::text::ascii_glyph), for dumb
+    /// terminals, CI logs, and serial consoles.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::format::BoolStyle;
+    ///
+    /// let grid = vec![
+    ///     vec!["std", "true"],
+    ///     vec!["simd", "false"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_spacing(3)
+    ///     .col_separator('│')
+    ///     .col_bool(1, BoolStyle::Check).unwrap()
+    ///     .ascii_only(true)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "std  | v   \nsimd | x   \n");
+    /// ```
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+
+        self
+    }
+
+    /// Write only the style attributes which change between adjacent cells, rather
+    /// than opening and resetting the style of every cell, which shrinks heavily
+    /// styled output, e.g. for logs and slow terminals. Each line still ends with
+    /// the style reset.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, StyleOpt};
+    ///
+    /// let grid = vec![
+    ///     vec!["a", "b"],
+    ///     vec!["c", "d"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .highlight_rows(&[0], StyleOpt::new().fg(Fg::Red))
+    ///     .minimize_escapes(true)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "\x1b[1;31ma  b  \x1b[0m\nc  d  \n");
+    /// ```
+    pub fn minimize_escapes(mut self, minimize_escapes: bool) -> Self {
+        self.minimize_escapes = minimize_escapes;
+
+        self
+    }
+
+    /// Set the colors the terminal supports, which the 256-color and RGB colors of
+    /// the printed styles, including the escape sequences kept in cells, are
+    /// downgraded to. When it is not set, [`print`](GridPrinter::print) and
+    /// `par_print` read the terminal's support with [`ColorSupport::detect`], while
+    /// rendering into a `String` or [`Sink`] prints every color as it is.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{ColorSupport, Fg, StyleOpt};
+    ///
+    /// let printer = GridPrinter::builder(1, 1)
+    ///     .col_styles(vec![Some(StyleOpt::new().fg(Fg::Rgb(255, 135, 0)))]).unwrap()
+    ///     .color_support(ColorSupport::Ansi256)
+    ///     .build();
+    /// assert_eq!(printer.render(&[vec!["a"]]), "\x1b[1;38;5;208ma\x1b[1;0m  \n");
+    /// ```
+    pub fn color_support(mut self, color_support: ColorSupport) -> Self {
+        self.color_support = Some(color_support);
+
+        self
+    }
+
+    /// Treat the first row of the source as a header row.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+
+        self
+    }
+
+    /// Draw a rule of `sep` characters beneath the header row, e.g. `'-'`.
+    pub fn header_separator(mut self, sep: char) -> Self {
+        self.header_separator = Some(sep);
+
+        self
+    }
+
+    /// Style the source rows at the given indices with `style_opt`, on top of their
+    /// normal styling, e.g. to emphasize the current item or search matches. The
+    /// header row, if any, is row `0`. Later calls take precedence for the rows
+    /// they share.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Bg, Fg, StyleOpt};
+    ///
+    /// let grid = vec![
+    ///     vec!["a", "1"],
+    ///     vec!["b", "2"],
+    ///     vec!["c", "3"],
+    /// ];
+    /// let printer = GridPrinter::builder(3, 2)
+    ///     .highlight_rows(&[1], StyleOpt::new().fg(Fg::Black).bg(Bg::Yellow))
+    ///     .fill_background(true)
+    ///     .build();
+    /// printer.print(&grid);
+    /// ```
+    pub fn highlight_rows(mut self, rows: &[usize], style_opt: StyleOpt) -> Self {
+        for row in rows {
+            self.highlight_rows.insert(*row, style_opt.clone());
+        }
+
+        self
+    }
+
+    /// Highlight the source rows at the given indices with the
+    /// [`palette`](GridPrinterBuilder::palette) style `name`.
+    pub fn highlight_rows_named(mut self, rows: &[usize], name: &str) -> Self {
+        self.set_highlight_rows_named(rows, name);
+
+        self
+    }
+
+    /// Style the substrings of the cells matched by `pattern` with `style_opt`, on
+    /// top of the cells' own style. The pattern is either text or, with the `regex`
+    /// feature, a [`regex::Regex`]. Highlighting never changes the width of a cell.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, Sgr, StyleOpt};
+    ///
+    /// let grid = vec![
+    ///     vec!["src/lib.rs", "fn render"],
+    ///     vec!["src/render.rs", "struct LineRenderer"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .highlight_matches("render", StyleOpt::new().fg(Fg::Red).sgr(Sgr::Bold))
+    ///     .build();
+    /// printer.print(&grid);
+    /// ```
+    pub fn highlight_matches<P: Into<Pattern>>(mut self, pattern: P, style_opt: StyleOpt) -> Self {
+        self.highlights.push((pattern.into(), style_opt));
+
+        self
+    }
+
+    /// Terminate each line with `line_ending` instead of `\n`, e.g. for output
+    /// destined for Windows files, SMTP bodies, or embedded protocols.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::sink::LineEnding;
+    ///
+    /// let grid = vec![
+    ///     vec!["a", "b"],
+    ///     vec!["c", "d"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .line_ending(LineEnding::CrLf)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "a  b  \r\nc  d  \r\n");
+    /// ```
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+
+        self
+    }
+
+    /// Carry the background color of a cell across its padding, so that the whole
+    /// cell is highlighted rather than only its text. The column spacing is left
+    /// uncolored.
+    pub fn fill_background(mut self, fill_background: bool) -> Self {
+        self.fill_background = fill_background;
+
+        self
+    }
+
+    /// Rather than dropping the columns which don't fit within the
+    /// [`max_width`](GridPrinterBuilder::max_width), split the grid into stacked
+    /// sub-tables of the columns which fit, separated by blank lines, as pandas wraps
+    /// wide DataFrames. Each sub-table repeats the
+    /// [`key_cols`](GridPrinterBuilder::key_cols), and the header row, if any.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["host", "cpu", "mem", "disk"],
+    ///     vec!["web-1", "93%", "41%", "70%"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 4)
+    ///     .header(true)
+    ///     .col_spacing(1)
+    ///     .max_width(14)
+    ///     .key_cols(&[0])
+    ///     .unwrap()
+    ///     .wrap_columns(true)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "\
+    /// host  cpu mem \n\
+    /// web-1 93% 41% \n\
+    /// \n\
+    /// host  disk \n\
+    /// web-1 70%  \n");
+    /// ```
+    pub fn wrap_columns(mut self, wrap_columns: bool) -> Self {
+        self.wrap_columns = wrap_columns;
+
+        self
+    }
+
+    /// Size every column to the widest cell in the grid, producing a true grid for
+    /// calendars, matrices, and game boards.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 50, 6],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 3)
+    ///     .col_spacing(1)
+    ///     .uniform_columns(true)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "1  2  3  \n4  50 6  \n");
+    /// ```
+    pub fn uniform_columns(mut self, uniform_columns: bool) -> Self {
+        self.uniform_columns = uniform_columns;
+
+        self
+    }
+
+    /// Draw `sep` between the columns, centered in the column spacing, e.g. `'|'`.
+    /// The column spacing must be at least one.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["Name", "Qty"],
+    ///     vec!["apple", "3"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_spacing(3)
+    ///     .header(true)
+    ///     .header_separator('-')
+    ///     .col_separator('|')
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "Name  | Qty   \n----- | ---   \napple | 3     \n");
+    /// ```
+    pub fn col_separator(mut self, sep: char) -> Self {
+        self.col_separator = Some(sep);
+
+        self
+    }
+
+    /// Set the style of the structural characters, i.e. the column separators and
+    /// the header separator, independently of the cells.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, Sgr, StyleOpt};
+    ///
+    /// let printer = GridPrinter::builder(2, 2)
+    ///     .col_separator('│')
+    ///     .border_style(StyleOpt::new().fg(Fg::BrightBlack).sgr(Sgr::Faint))
+    ///     .build();
+    /// printer.print(&vec![vec!["a", "b"], vec!["c", "d"]]);
+    /// ```
+    pub fn border_style(mut self, border_style: StyleOpt) -> Self {
+        self.border_style = Some(border_style);
+
+        self
+    }
+
+    /// Print the header row (and its separator) again after every `n` data rows,
+    /// so the columns stay identifiable while scrolling through long output.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let mut grid = vec![vec!["Id".to_string(), "Square".to_string()]];
+    /// grid.extend((0..100).map(|i| vec![i.to_string(), (i * i).to_string()]));
+    ///
+    /// let printer = GridPrinter::builder(grid.len(), 2)
+    ///     .header(true)
+    ///     .header_separator('-')
+    ///     .repeat_header_every(20)
+    ///     .build();
+    /// printer.print(&grid);
+    /// ```
+    pub fn repeat_header_every(mut self, n: usize) -> Self {
+        self.repeat_header_every = Some(n);
+
+        self
+    }
+
+    /// When the grid is wider than its [`max_width`](GridPrinterBuilder::max_width),
+    /// abbreviate the headers which are wider than their column's data before any
+    /// columns are dropped. Headers are shortened with the
+    /// [`truncation_marker`](GridPrinterBuilder::truncation_marker), unless a short name
+    /// was given with [`header_short_names`](GridPrinterBuilder::header_short_names).
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["Identifier", "Description", "Enabled"],
+    ///     vec!["1", "A short one", "y"],
+    /// ];
+    /// let printer = GridPrinter::builder(2, 3)
+    ///     .header(true)
+    ///     .max_width(24)
+    ///     .abbreviate_headers(true)
+    ///     .header_short_names(vec![("Identifier", "Id")])
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "Id  Description  E…  \n1   A short one  y   \n");
+    /// ```
+    pub fn abbreviate_headers(mut self, abbreviate_headers: bool) -> Self {
+        self.abbreviate_headers = abbreviate_headers;
+
+        self
+    }
+
+    /// Set the short names used in place of long headers when they are abbreviated.
+    pub fn header_short_names<I, K, V>(mut self, short_names: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.header_short_names.extend(short_names.into_iter().map(|(k, v)| (k.into(), v.into())));
+
+        self
+    }
+
+    /// Set the marker drawn in place of the text cut from a cell which is too wide for
+    /// its column, e.g. `"…"` or `"[+]"`. By default, such cells are cut without a
+    /// marker.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![vec!["Description"], vec!["A short one"]];
+    /// let printer = GridPrinter::builder(2, 1)
+    ///     .max_width(9)
+    ///     .truncation_marker("...")
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), "Desc...  \nA sh...  \n");
+    /// ```
+    pub fn truncation_marker(mut self, marker: &str) -> Self {
+        self.truncation_marker = Some(marker.to_string());
+
+        self
+    }
+
+    /// Set the style of the truncation marker, layered over the style of the cell it
+    /// cuts, e.g. to draw it faint so it stands apart from the cell's text.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{StyleOpt, Sgr};
+    ///
+    /// let printer = GridPrinter::builder(1, 1)
+    ///     .max_width(6)
+    ///     .truncation_marker("…")
+    ///     .truncation_style(StyleOpt::new().sgr(Sgr::Faint))
+    ///     .build();
+    /// printer.print(&[vec!["Description"]]);
+    /// ```
+    pub fn truncation_style(mut self, truncation_style: StyleOpt) -> Self {
+        self.truncation_style = Some(truncation_style);
+
+        self
+    }
+
+    /// Draw the header row vertically, one character per line, so that the columns
+    /// only need to be as wide as their data. This suits feature matrices of narrow
+    /// boolean or flag columns.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    ///
+    /// let grid = vec![
+    ///     vec!["", "Linux", "Mac"],
+    ///     vec!["std", "y", "y"],
+    ///     vec!["simd", "y", "n"],
+    /// ];
+    /// let printer = GridPrinter::builder(3, 3)
+    ///     .col_spacing(1)
+    ///     .header(true)
+    ///     .vertical_headers(true)
+    ///     .build();
+    /// assert_eq!(printer.render(&grid), concat!(
+    ///     "     L   \n",
+    ///     "     i   \n",
+    ///     "     n M \n",
+    ///     "     u a \n",
+    ///     "     x c \n",
+    ///     "std  y y \n",
+    ///     "simd y n \n",
+    /// ));
+    /// ```
+    pub fn vertical_headers(mut self, vertical_headers: bool) -> Self {
+        self.vertical_headers = vertical_headers;
+
+        self
+    }
+
+    /// Set the column spacing without consuming the builder.
+    pub fn set_col_spacing(&mut self, col_spacing: usize) -> &mut Self {
+        self.col_spacing = col_spacing;
+
+        self
+    }
+
+    /// Set the spacing between two adjacent columns without consuming the builder.
+    ///
+    /// Unlike [`col_spacing_between`](GridPrinterBuilder::col_spacing_between), columns
+    /// which are not adjacent, or outside the column range, are not rejected here;
+    /// they are reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_spacing_between(&mut self, i: usize, j: usize, n: usize) -> &mut Self {
+        self.col_gaps.push((i, j, n));
+
+        self
+    }
+
+    /// Sort the data rows by a column without consuming the builder.
+    ///
+    /// Unlike [`sort_by_col`](GridPrinterBuilder::sort_by_col), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_sort_by_col<C: Into<ColRef>>(&mut self, col: C, order: SortOrder) -> &mut Self {
+        self.set_sort_by(&[(col.into(), order)])
+    }
+
+    /// Sort the data rows by several columns without consuming the builder.
+    ///
+    /// Unlike [`sort_by`](GridPrinterBuilder::sort_by), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_sort_by<C: Into<ColRef> + Clone>(&mut self, keys: &[(C, SortOrder)]) -> &mut Self {
+        self.named_cols.retain(|(_, named_col)| !matches!(named_col, NamedCol::Sort(..)));
+        self.sort_keys.clear();
+        for (priority, (col, order)) in keys.iter().enumerate() {
+            match col.clone().into() {
+                ColRef::Index(idx) => self.sort_keys.push((idx, *order)),
+                ColRef::Name(name) => self.named_cols.push((name, NamedCol::Sort(*order, priority))),
+            }
+        }
+
+        self
+    }
+
+    /// Set the outlier style of a column without consuming the builder.
+    ///
+    /// Unlike [`col_outlier_style`](GridPrinterBuilder::col_outlier_style), an index
+    /// outside the column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_outlier_style(&mut self, idx: usize, style: StyleOpt, rule: OutlierRule) -> &mut Self {
+        self.col_outlier_styles.insert(idx, (style, rule));
+
+        self
+    }
+
+    /// Describe every column with a [`ColumnSpec`] without consuming the builder.
+    ///
+    /// Unlike [`columns`](GridPrinterBuilder::columns), a number of specs other than
+    /// the number of columns is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_columns(&mut self, columns: Vec<ColumnSpec>) -> &mut Self {
+        for (idx, column) in columns.iter().enumerate().take(self.cols) {
+            if let Some(style) = column.style.clone() {
+                self.set_col_style(idx, style);
+            }
+            if let Some(format) = column.format.clone() {
+                self.set_col_format(idx, format);
+            }
+        }
+        self.columns = columns;
+
+        self
+    }
+
+    /// Set the overflow of a column without consuming the builder.
+    ///
+    /// Unlike [`col_overflow`](GridPrinterBuilder::col_overflow), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_overflow(&mut self, idx: usize, overflow: Overflow) -> &mut Self {
+        self.col_overflows.insert(idx, overflow);
+
+        self
+    }
+
+    /// Set whether a column's whitespace is preserved without consuming the builder.
+    ///
+    /// Unlike [`preserve_whitespace`](GridPrinterBuilder::preserve_whitespace), an
+    /// index outside the column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_preserve_whitespace(&mut self, idx: usize, preserve: bool) -> &mut Self {
+        match preserve {
+            true => self.preserved_whitespace.insert(idx),
+            false => self.preserved_whitespace.remove(&idx),
+        };
+
+        self
+    }
+
+    /// Set the unit scale of a column without consuming the builder.
+    ///
+    /// Unlike [`col_units`](GridPrinterBuilder::col_units), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_units(&mut self, idx: usize, unit_scale: UnitScale) -> &mut Self {
+        self.col_units.insert(idx, unit_scale);
+
+        self
+    }
+
+    /// Set a histogram footer of a column without consuming the builder.
+    ///
+    /// Unlike [`col_footer_histogram`](GridPrinterBuilder::col_footer_histogram), an
+    /// index outside the column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_footer_histogram(&mut self, idx: usize, buckets: usize) -> &mut Self {
+        self.col_footer_histograms.insert(idx, buckets);
+
+        self
+    }
+
+    /// Append a percent-of-total column without consuming the builder.
+    ///
+    /// Unlike [`derived_col_percent_of`](GridPrinterBuilder::derived_col_percent_of), an
+    /// index outside the column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_derived_col_percent_of(&mut self, idx: usize) -> &mut Self {
+        self.derived_cols.push(DerivedCol::PercentOf(idx));
+
+        self
+    }
+
+    /// Append a computed column without consuming the builder.
+    pub fn set_derived_col<F>(&mut self, header: &str, f: F) -> &mut Self
+    where
+        F: Fn(&[String]) -> String + Send + Sync + 'static,
+    {
+        self.derived_cols.push(DerivedCol::Fn(header.to_string(), Arc::new(f)));
+
+        self
+    }
+
+    /// Append a rank column without consuming the builder.
+    ///
+    /// Unlike [`rank_by_col`](GridPrinterBuilder::rank_by_col), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_rank_by_col(&mut self, idx: usize) -> &mut Self {
+        self.derived_cols.push(DerivedCol::Rank(idx));
+
+        self
+    }
+
+    /// Set the style of the top ranked rows without consuming the builder.
+    pub fn set_rank_top_style(&mut self, n: usize, style: StyleOpt) -> &mut Self {
+        self.rank_top_style = Some((n, style));
+
+        self
+    }
+
+    /// Suppress the repeats of a column without consuming the builder.
+    ///
+    /// Unlike [`suppress_repeats`](GridPrinterBuilder::suppress_repeats), an index outside
+    /// the column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_suppress_repeats(&mut self, idx: usize) -> &mut Self {
+        self.suppress_repeats.insert(idx);
+
+        self
+    }
+
+    /// Set the style of repeated cells without consuming the builder.
+    pub fn set_repeat_style(&mut self, repeat_style: StyleOpt) -> &mut Self {
+        self.repeat_style = Some(repeat_style);
+
+        self
+    }
+
+    /// Set whether too-wide grids are split into stacked sub-tables without consuming
+    /// the builder.
+    pub fn set_wrap_columns(&mut self, wrap_columns: bool) -> &mut Self {
+        self.wrap_columns = wrap_columns;
+
+        self
+    }
+
+    /// Set the order the columns are printed in without consuming the builder.
+    pub fn set_column_order(&mut self, order: &[usize]) -> &mut Self {
+        self.column_order = Some(order.to_vec());
+
+        self
+    }
+
+    /// Set the key columns without consuming the builder.
+    ///
+    /// Unlike [`key_cols`](GridPrinterBuilder::key_cols), indices outside the column
+    /// range are not rejected here; they are reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_key_cols(&mut self, idxs: &[usize]) -> &mut Self {
+        self.key_cols.extend(idxs);
+
+        self
+    }
+
+    /// Leave a column out of the printed grid without consuming the builder.
+    ///
+    /// Unlike [`hide_column`](GridPrinterBuilder::hide_column), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_hide_column<C: Into<ColRef>>(&mut self, col: C) -> &mut Self {
+        match col.into() {
+            ColRef::Index(idx) => {
+                self.hidden_cols.insert(idx);
+            },
+            ColRef::Name(name) => self.named_cols.push((name, NamedCol::Hide)),
+        }
+
+        self
+    }
+
+    /// Set the style for every column without consuming the builder.
+    ///
+    /// Unlike [`col_styles`](GridPrinterBuilder::col_styles), the length of `col_styles`
+    /// is not checked here; mismatches are reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_styles(&mut self, col_styles: Vec<Option<StyleOpt>>) -> &mut Self {
+        self.col_styles = Some(col_styles);
+
+        self
+    }
+
+    /// Set the style for a single column without consuming the builder.
+    ///
+    /// Unlike [`col_style`](GridPrinterBuilder::col_style), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_style<C: Into<ColRef>>(&mut self, col: C, opt: StyleOpt) -> &mut Self {
+        let idx = match col.into() {
+            ColRef::Index(idx) => idx,
+            ColRef::Name(name) => {
+                self.named_cols.push((name, NamedCol::Style(opt)));
+                return self;
+            },
+        };
+        let col_styles = self.col_styles.get_or_insert(vec![None; self.cols]);
+        if idx >= col_styles.len() {
+            col_styles.resize(idx + 1, None);
+        }
+        col_styles[idx] = Some(opt);
+
+        self
+    }
+
+    /// Set the named style for a single column without consuming the builder.
+    ///
+    /// Unlike [`col_style_named`](GridPrinterBuilder::col_style_named), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_style_named(&mut self, idx: usize, name: &str) -> &mut Self {
+        self.col_style_names.insert(idx, name.to_string());
+
+        self
+    }
+
+    /// Set the format of a column without consuming the builder.
+    ///
+    /// Unlike [`col_format`](GridPrinterBuilder::col_format), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_format<C: Into<ColFormat>>(&mut self, idx: usize, col_format: C) -> &mut Self {
+        self.col_formats.insert(idx, col_format.into());
+
+        self
+    }
+
+    /// Set the kind of values of a column without consuming the builder.
+    ///
+    /// Unlike [`col_type`](GridPrinterBuilder::col_type), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_type(&mut self, idx: usize, col_type: ColType) -> &mut Self {
+        self.col_types.insert(idx, col_type);
+
+        self
+    }
+
+    /// Set the order of text when sorting without consuming the builder.
+    pub fn set_collation(&mut self, collation: Collation) -> &mut Self {
+        self.collation = collation;
+
+        self
+    }
+
+    /// Set the boolean style of a column without consuming the builder.
+    ///
+    /// Unlike [`col_bool`](GridPrinterBuilder::col_bool), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_bool(&mut self, idx: usize, bool_style: BoolStyle) -> &mut Self {
+        self.set_col_format(idx, bool_style)
+    }
+
+    /// Set the status badges of a column without consuming the builder.
+    ///
+    /// Unlike [`col_status`](GridPrinterBuilder::col_status), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_status(&mut self, idx: usize, status: StatusRenderer) -> &mut Self {
+        self.set_col_format(idx, status)
+    }
+
+    /// Set the currency of a column without consuming the builder.
+    ///
+    /// Unlike [`col_currency`](GridPrinterBuilder::col_currency), an index outside the
+    /// column range is not rejected here; it is reported by [`try_build`](GridPrinterBuilder::try_build).
+    pub fn set_col_currency(&mut self, idx: usize, currency: CurrencyOpts) -> &mut Self {
+        self.set_col_format(idx, currency)
+    }
+
+    /// Set the style applied to every cell without consuming the builder.
+    pub fn set_default_style(&mut self, default_style: StyleOpt) -> &mut Self {
+        self.default_style = Some(default_style);
+
+        self
+    }
+
+    /// Set the Palette of named styles without consuming the builder.
+    pub fn set_palette(&mut self, palette: Palette) -> &mut Self {
+        self.palette = palette;
+
+        self
+    }
+
+    /// Apply the environment variables' appearance settings without consuming the
+    /// builder. See [`env_overrides`](GridPrinterBuilder::env_overrides).
+    #[cfg(feature = "std")]
+    pub fn set_env_overrides(&mut self) -> &mut Self {
+        self.apply_env(|name| std::env::var(name).ok())
+    }
+
+    /// Apply the appearance settings of the variables looked up with `var`.
+    #[cfg(feature = "std")]
+    fn apply_env<V: Fn(&str) -> Option<String>>(&mut self, var: V) -> &mut Self {
+        if var("NO_COLOR").is_some_and(|no_color| !no_color.is_empty()) {
+            self.set_color_mode(ColorMode::Never);
+        }
+        match var("GRID_PRINTER_COLOR").as_deref() {
+            Some("always") => self.set_color_mode(ColorMode::Always),
+            Some("never") => self.set_color_mode(ColorMode::Never),
+            Some("auto") => self.set_color_mode(ColorMode::Auto),
+            _ => self,
+        };
+        let background = match var("GRID_PRINTER_THEME").as_deref() {
+            Some("dark") => Some(style::Background::Dark),
+            Some("light") => Some(style::Background::Light),
+            _ => None,
+        };
+        if let Some(background) = background {
+            self.palette.merge(Palette::theme(background));
+        }
+        if let Some(max_width) = var("GRID_PRINTER_MAXWIDTH").and_then(|max_width| max_width.parse().ok()) {
+            self.set_max_width(max_width);
+        }
+
+        self
+    }
+
+    /// Set the number of sampled rows without consuming the builder.
+    pub fn set_sample_rows(&mut self, n: usize) -> &mut Self {
+        self.sample_rows = Some(n);
+
+        self
+    }
+
+    /// Set the color mode without consuming the builder.
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) -> &mut Self {
+        self.print_options.color_mode = Some(color_mode);
+
+        self
+    }
+
+    /// Set the maximum line width without consuming the builder.
+    pub fn set_max_width(&mut self, max_width: usize) -> &mut Self {
+        self.print_options.max_width = Some(max_width);
+
+        self
+    }
+
+    /// Set the maximum number of printed rows without consuming the builder.
+    pub fn set_max_rows(&mut self, max_rows: usize) -> &mut Self {
+        self.print_options.max_rows = Some(max_rows);
+
+        self
+    }
+
+    /// Set whether rows are printed for screen readers without consuming the builder.
+    pub fn set_accessible(&mut self, accessible: bool) -> &mut Self {
+        self.print_options.accessible = Some(accessible);
+
+        self
+    }
+
+    /// Set whether inline style markup is parsed without consuming the builder.
+    pub fn set_markup(&mut self, markup: bool) -> &mut Self {
+        self.markup = markup;
+
+        self
+    }
+
+    /// Set the width of emoji without consuming the builder.
+    pub fn set_emoji_width(&mut self, emoji_width: EmojiWidth) -> &mut Self {
+        self.emoji_width = emoji_width;
+
+        self
+    }
+
+    /// Set how tab characters are handled without consuming the builder.
+    pub fn set_tabs(&mut self, tabs: Tabs) -> &mut Self {
+        self.tabs = Some(tabs);
+
+        self
+    }
+
+    /// Set how control characters are handled without consuming the builder.
+    pub fn set_control_chars(&mut self, control_chars: ControlChars) -> &mut Self {
+        self.control_chars = control_chars;
+
+        self
+    }
+
+    /// Set whether glyphs are replaced with ASCII without consuming the builder.
+    pub fn set_ascii_only(&mut self, ascii_only: bool) -> &mut Self {
+        self.ascii_only = ascii_only;
+
+        self
+    }
+
+    /// Set whether only changed style attributes are written without consuming the
+    /// builder.
+    pub fn set_minimize_escapes(&mut self, minimize_escapes: bool) -> &mut Self {
+        self.minimize_escapes = minimize_escapes;
+
+        self
+    }
+
+    /// Set the colors the terminal supports without consuming the builder.
+    pub fn set_color_support(&mut self, color_support: ColorSupport) -> &mut Self {
+        self.color_support = Some(color_support);
+
+        self
+    }
+
+    /// Set whether the first row is a header without consuming the builder.
+    pub fn set_header(&mut self, header: bool) -> &mut Self {
+        self.header = header;
+
+        self
+    }
+
+    /// Set the header separator without consuming the builder.
+    pub fn set_header_separator(&mut self, sep: char) -> &mut Self {
+        self.header_separator = Some(sep);
+
+        self
+    }
+
+    /// Highlight the source rows at the given indices without consuming the builder.
+    pub fn set_highlight_rows(&mut self, rows: &[usize], style_opt: StyleOpt) -> &mut Self {
+        for row in rows {
+            self.highlight_rows.insert(*row, style_opt.clone());
+        }
+
+        self
+    }
+
+    /// Highlight the source rows at the given indices with a named style without
+    /// consuming the builder.
+    pub fn set_highlight_rows_named(&mut self, rows: &[usize], name: &str) -> &mut Self {
+        for row in rows {
+            self.highlight_row_names.insert(*row, name.to_string());
+        }
+
+        self
+    }
+
+    /// Highlight the substrings matched by `pattern` without consuming the builder.
+    pub fn set_highlight_matches<P: Into<Pattern>>(&mut self, pattern: P, style_opt: StyleOpt) -> &mut Self {
+        self.highlights.push((pattern.into(), style_opt));
+
+        self
+    }
+
+    /// Set the line terminator without consuming the builder.
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) -> &mut Self {
+        self.line_ending = line_ending;
+
+        self
+    }
+
+    /// Set whether cell backgrounds fill their padding without consuming the builder.
+    pub fn set_fill_background(&mut self, fill_background: bool) -> &mut Self {
+        self.fill_background = fill_background;
+
+        self
+    }
+
+    /// Set whether every column has the same width without consuming the builder.
+    pub fn set_uniform_columns(&mut self, uniform_columns: bool) -> &mut Self {
+        self.uniform_columns = uniform_columns;
+
+        self
+    }
+
+    /// Set the column separator without consuming the builder.
+    pub fn set_col_separator(&mut self, sep: char) -> &mut Self {
+        self.col_separator = Some(sep);
+
+        self
+    }
+
+    /// Set the style of the structural characters without consuming the builder.
+    pub fn set_border_style(&mut self, border_style: StyleOpt) -> &mut Self {
+        self.border_style = Some(border_style);
+
+        self
+    }
+
+    /// Set how often the header is repeated without consuming the builder.
+    pub fn set_repeat_header_every(&mut self, n: usize) -> &mut Self {
+        self.repeat_header_every = Some(n);
+
+        self
+    }
+
+    /// Set whether headers are abbreviated without consuming the builder.
+    pub fn set_abbreviate_headers(&mut self, abbreviate_headers: bool) -> &mut Self {
+        self.abbreviate_headers = abbreviate_headers;
+
+        self
+    }
+
+    /// Add a short name for a header without consuming the builder.
+    pub fn set_header_short_name(&mut self, header: &str, short_name: &str) -> &mut Self {
+        self.header_short_names.insert(header.to_string(), short_name.to_string());
+
+        self
+    }
+
+    /// Set the truncation marker without consuming the builder.
+    pub fn set_truncation_marker(&mut self, marker: &str) -> &mut Self {
+        self.truncation_marker = Some(marker.to_string());
+
+        self
+    }
+
+    /// Set the style of the truncation marker without consuming the builder.
+    pub fn set_truncation_style(&mut self, truncation_style: StyleOpt) -> &mut Self {
+        self.truncation_style = Some(truncation_style);
+
+        self
+    }
+
+    /// Set whether the header row is drawn vertically without consuming the builder.
+    pub fn set_vertical_headers(&mut self, vertical_headers: bool) -> &mut Self {
+        self.vertical_headers = vertical_headers;
+
+        self
+    }
+
+    /// Check the whole configuration, returning every violation found.
+    pub fn validate(&self) -> Vec<ConfigViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(col_styles) = self.col_styles.as_ref() {
+            if col_styles.len() < self.cols {
+                violations.push(ConfigViolation::ColStylesLen { len: col_styles.len(), cols: self.cols });
+            }
+            for (idx, style_opt) in col_styles.iter().enumerate().skip(self.cols) {
+                if style_opt.is_some() {
+                    violations.push(ConfigViolation::ColStyleOutOfRange { idx, cols: self.cols });
+                }
+            }
+        }
+
+        for idx in self.col_formats.keys().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::ColFormatOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for (idx, name) in self.col_style_names.iter() {
+            if *idx >= self.cols {
+                violations.push(ConfigViolation::ColStyleOutOfRange { idx: *idx, cols: self.cols });
+            }
+            if self.palette.get(name).is_none() {
+                violations.push(ConfigViolation::UnknownStyleName(name.clone()));
+            }
+        }
+        for name in self.highlight_row_names.values().filter(|name| self.palette.get(name).is_none()) {
+            violations.push(ConfigViolation::UnknownStyleName(name.clone()));
+        }
+
+        for (idx, _) in self.sort_keys.iter().filter(|(idx, _)| *idx >= self.cols) {
+            violations.push(ConfigViolation::SortColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for idx in self.col_outlier_styles.keys().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::OutlierColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for idx in self.hidden_cols.iter().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::HiddenColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        if !self.columns.is_empty() && self.columns.len() != self.cols {
+            violations.push(ConfigViolation::ColumnSpecsLen { len: self.columns.len(), cols: self.cols });
+        }
+
+        for idx in self.col_overflows.keys().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::OverflowColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for idx in self.preserved_whitespace.iter().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::WhitespaceColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for idx in self.col_units.keys().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::UnitsColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for idx in self.col_types.keys().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::TypeColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for (idx, buckets) in self.col_footer_histograms.iter() {
+            if *idx >= self.cols {
+                violations.push(ConfigViolation::HistogramColOutOfRange { idx: *idx, cols: self.cols });
+            }
+            if *buckets == 0 {
+                violations.push(ConfigViolation::ZeroHistogramBuckets { idx: *idx });
+            }
+        }
+
+        if let Some(order) = self.column_order.as_ref() {
+            let cols = self.cols + self.derived_cols.len();
+            if !is_permutation(order, cols) {
+                violations.push(ConfigViolation::InvalidColumnOrder { order: order.clone(), cols });
+            }
+        }
+
+        for idx in self.key_cols.iter().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::KeyColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for idx in self.suppress_repeats.iter().filter(|idx| **idx >= self.cols) {
+            violations.push(ConfigViolation::SuppressRepeatsColOutOfRange { idx: *idx, cols: self.cols });
+        }
+
+        for derived_col in self.derived_cols.iter() {
+            match derived_col {
+                DerivedCol::PercentOf(idx) | DerivedCol::Rank(idx) if *idx >= self.cols => {
+                    violations.push(ConfigViolation::DerivedColOutOfRange { idx: *idx, cols: self.cols });
+                },
+                _ => (),
+            }
+        }
+
+        for (i, j, _) in self.col_gaps.iter() {
+            if *i + 1 != *j || *j >= self.cols {
+                violations.push(ConfigViolation::ColSpacingBetween { i: *i, j: *j, cols: self.cols });
+            }
+        }
+
+        if self.sample_rows == Some(0) {
+            violations.push(ConfigViolation::ZeroSampleRows);
+        }
+        match self.repeat_header_every {
+            Some(0) => violations.push(ConfigViolation::ZeroRepeatHeaderEvery),
+            Some(_) if !self.header => violations.push(ConfigViolation::RepeatHeaderWithoutHeader),
+            _ => (),
+        }
+        if self.header_separator.is_some() && !self.header {
+            violations.push(ConfigViolation::HeaderSeparatorWithoutHeader);
+        }
+        if self.rank_top_style.is_some() && !self.derived_cols.iter().any(|derived_col| matches!(derived_col, DerivedCol::Rank(_))) {
+            violations.push(ConfigViolation::RankTopStyleWithoutRank);
+        }
+        if self.abbreviate_headers && !self.header {
+            violations.push(ConfigViolation::AbbreviateHeadersWithoutHeader);
+        }
+        if self.vertical_headers && !self.header {
+            violations.push(ConfigViolation::VerticalHeadersWithoutHeader);
+        }
+        if self.col_separator.is_some() && self.col_spacing == 0 {
+            violations.push(ConfigViolation::ColSeparatorWithoutSpacing);
+        }
+
+        violations
+    }
+
+    /// Check the configuration for options which are valid but will have no effect,
+    /// returning every lint found. Unlike [`validate`](GridPrinterBuilder::validate),
+    /// these do not stop the printer from being built.
+    ///
+    /// ```rust
+    /// use grid_printer::{ConfigLint, GridPrinter};
+    /// use grid_printer::style::{Fg, StyleOpt};
+    ///
+    /// let builder = GridPrinter::builder(3, 2)
+    ///     .col_style(1, StyleOpt::new().fg(Fg::Red)).unwrap()
+    ///     .hide_column(1).unwrap()
+    ///     .wrap_columns(true);
+    /// assert_eq!(builder.lints(), vec![
+    ///     ConfigLint::StyleOnHiddenCol { idx: 1 },
+    ///     ConfigLint::WrapColumnsWithoutMaxWidth,
+    /// ]);
+    /// ```
+    pub fn lints(&self) -> Vec<ConfigLint> {
+        let mut lints = Vec::new();
+        let is_hidden = |idx: usize| {
+            self.hidden_cols.contains(&idx) || self.columns.get(idx).is_some_and(|column| column.hidden)
+        };
+
+        let styled = self.col_styles.iter()
+            .flat_map(|col_styles| col_styles.iter().enumerate())
+            .filter(|(_, style_opt)| style_opt.is_some())
+            .map(|(idx, _)| idx)
+            .chain(self.col_style_names.keys().copied())
+            .chain(self.col_formats.keys().copied())
+            .collect::<BTreeSet<usize>>();
+        for idx in styled.into_iter().filter(|idx| is_hidden(*idx)) {
+            lints.push(ConfigLint::StyleOnHiddenCol { idx });
+        }
+
+        let max_width = self.print_options.max_width;
+        if let Some(max_width) = max_width {
+            for (idx, column) in self.columns.iter().enumerate() {
+                match column.width {
+                    Some(width) if width > max_width => lints.push(ConfigLint::ColWidthExceedsMaxWidth { idx, width, max_width }),
+                    _ => (),
+                }
+            }
+        }
+        if max_width.is_none() {
+            if self.wrap_columns {
+                lints.push(ConfigLint::WrapColumnsWithoutMaxWidth);
+            }
+            if !self.key_cols.is_empty() {
+                lints.push(ConfigLint::KeyColsWithoutMaxWidth);
+            }
+            if self.abbreviate_headers {
+                lints.push(ConfigLint::AbbreviateHeadersWithoutMaxWidth);
+            }
+        }
+        if self.wrap_columns && self.abbreviate_headers {
+            lints.push(ConfigLint::AbbreviateHeadersWithWrapColumns);
+        }
+        if !self.header_short_names.is_empty() && !self.abbreviate_headers {
+            lints.push(ConfigLint::ShortNamesWithoutAbbreviateHeaders);
+        }
+        if self.repeat_style.is_some() && self.suppress_repeats.is_empty() {
+            lints.push(ConfigLint::RepeatStyleWithoutSuppressRepeats);
+        }
+
+        lints
+    }
+
+    /// Build the GridPrinter, failing with every configuration violation found.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Fg, StyleOpt};
+    ///
+    /// let mut builder = GridPrinter::builder(3, 2);
+    /// builder.set_col_spacing(4);
+    /// builder.set_col_style(5, StyleOpt::new().fg(Fg::Red));
+    /// assert!(builder.try_build().is_err());
+    /// ```
+    pub fn try_build(self) -> Result<GridPrinter, GridPrinterErr> {
+        let violations = self.validate();
+        match violations.is_empty() {
+            true => Ok(self.build()),
+            false => Err(GridPrinterErr::ConfigErr(violations)),
+        }
+    }
+
+    pub fn build(self) -> GridPrinter {
+        GridPrinter {
+            rows: self.rows,
+            cols: self.cols + self.derived_cols.len(),
+            col_spacing: self.col_spacing,
+            col_styles: self.col_styles,
+            sample_rows: self.sample_rows,
+            print_options: self.print_options,
+            markup: self.markup,
+            emoji_width: self.emoji_width,
+            tabs: self.tabs,
+            control_chars: self.control_chars,
+            header: self.header,
+            header_separator: self.header_separator,
+            repeat_header_every: self.repeat_header_every,
+            abbreviate_headers: self.abbreviate_headers,
+            header_short_names: self.header_short_names,
+            vertical_headers: self.vertical_headers,
+            col_formats: self.col_formats,
+            default_style: self.default_style,
+            col_separator: self.col_separator,
+            border_style: self.border_style,
+            fill_background: self.fill_background,
+            highlight_rows: self.highlight_rows,
+            highlights: self.highlights,
+            line_ending: self.line_ending,
+            col_gaps: self.col_gaps,
+            uniform_columns: self.uniform_columns,
+            palette: self.palette,
+            col_style_names: self.col_style_names,
+            highlight_row_names: self.highlight_row_names,
+            ascii_only: self.ascii_only,
+            sort_keys: self.sort_keys,
+            truncation_marker: self.truncation_marker,
+            truncation_style: self.truncation_style,
+            col_outlier_styles: self.col_outlier_styles,
+            derived_cols: self.derived_cols,
+            rank_top_style: self.rank_top_style,
+            suppress_repeats: self.suppress_repeats,
+            repeat_style: self.repeat_style,
+            key_cols: self.key_cols,
+            wrap_columns: self.wrap_columns,
+            column_order: self.column_order,
+            col_footer_histograms: self.col_footer_histograms,
+            col_units: self.col_units,
+            col_types: self.col_types,
+            collation: self.collation,
+            columns: self.columns,
+            hidden_cols: self.hidden_cols,
+            named_cols: self.named_cols,
+            minimize_escapes: self.minimize_escapes,
+            color_support: self.color_support,
+            col_overflows: self.col_overflows,
+            preserved_whitespace: self.preserved_whitespace,
+        }
+    }
+
+}
+
+#[derive(Debug)]
+pub enum GridPrinterErr {
+    DimensionErr,
+    ConfigErr(Vec<ConfigViolation>),
+    StyleErr(String),
+}
+
+impl Display for GridPrinterErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridPrinterErr::DimensionErr => {
+                write!(f, "DimensionErr. Caused by mismatch in dimension size between method calls.")
+            },
+            GridPrinterErr::ConfigErr(violations) => {
+                write!(f, "ConfigErr. Caused by an invalid GridPrinter configuration:")?;
+                for violation in violations {
+                    write!(f, " {};", violation)?;
+                }
+                Ok(())
+            },
+            GridPrinterErr::StyleErr(name) => {
+                write!(f, "StyleErr. Caused by an unrecognized style name: {}", name)
+            },
+        }
+    }
+}
+
+/// A single problem found when validating a GridPrinterBuilder configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigViolation {
+    /// Fewer column styles were supplied than the printer has columns.
+    ColStylesLen { len: usize, cols: usize },
+    /// A column style was set for an index outside the printer's columns.
+    ColStyleOutOfRange { idx: usize, cols: usize },
+    /// A column format, such as a boolean style, was set for an index outside the
+    /// printer's columns.
+    ColFormatOutOfRange { idx: usize, cols: usize },
+    /// A spacing was set between two columns which are not adjacent, or are outside
+    /// the printer's columns.
+    ColSpacingBetween { i: usize, j: usize, cols: usize },
+    /// Rows were to be sorted by a column outside the printer's columns.
+    SortColOutOfRange { idx: usize, cols: usize },
+    /// An outlier style was set for a column outside the printer's columns.
+    OutlierColOutOfRange { idx: usize, cols: usize },
+    /// A column outside the printer's columns was to be hidden.
+    HiddenColOutOfRange { idx: usize, cols: usize },
+    /// The number of column specs differs from the number of columns.
+    ColumnSpecsLen { len: usize, cols: usize },
+    /// An overflow was set for a column outside the printer's columns.
+    OverflowColOutOfRange { idx: usize, cols: usize },
+    /// Whitespace was to be preserved in a column outside the printer's columns.
+    WhitespaceColOutOfRange { idx: usize, cols: usize },
+    /// A unit scale was set for a column outside the printer's columns.
+    UnitsColOutOfRange { idx: usize, cols: usize },
+    /// A column type was set for a column outside the printer's columns.
+    TypeColOutOfRange { idx: usize, cols: usize },
+    /// A histogram footer was set for a column outside the printer's columns.
+    HistogramColOutOfRange { idx: usize, cols: usize },
+    /// A histogram footer of zero buckets was set for the column at the index.
+    ZeroHistogramBuckets { idx: usize },
+    /// The column order does not name each of the printer's columns exactly once.
+    InvalidColumnOrder { order: Vec<usize>, cols: usize },
+    /// A key column was set outside the printer's columns.
+    KeyColOutOfRange { idx: usize, cols: usize },
+    /// Repeats were to be suppressed in a column outside the printer's columns.
+    SuppressRepeatsColOutOfRange { idx: usize, cols: usize },
+    /// A derived column was to be computed from a column outside the printer's columns.
+    DerivedColOutOfRange { idx: usize, cols: usize },
+    /// Column widths were to be sampled from zero rows.
+    ZeroSampleRows,
+    /// The header was to be repeated every zero rows.
+    ZeroRepeatHeaderEvery,
+    /// The header was to be repeated, but no header row was enabled.
+    RepeatHeaderWithoutHeader,
+    /// A header separator was set, but no header row was enabled.
+    HeaderSeparatorWithoutHeader,
+    /// The top ranked rows were to be styled, but no rank column was appended.
+    RankTopStyleWithoutRank,
+    /// Headers were to be abbreviated, but no header row was enabled.
+    AbbreviateHeadersWithoutHeader,
+    /// Headers were to be drawn vertically, but no header row was enabled.
+    VerticalHeadersWithoutHeader,
+    /// A column separator was set, but there is no column spacing to draw it in.
+    ColSeparatorWithoutSpacing,
+    /// A named style was referenced which the palette does not have.
+    UnknownStyleName(String),
+}
+
+impl Display for ConfigViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigViolation::ColStylesLen { len, cols } => {
+                write!(f, "{} column styles given for {} columns", len, cols)
+            },
+            ConfigViolation::ColStyleOutOfRange { idx, cols } => {
+                write!(f, "column style index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::ColFormatOutOfRange { idx, cols } => {
+                write!(f, "column format index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::ColSpacingBetween { i, j, cols } => {
+                write!(f, "columns {} and {} are not adjacent columns of {}", i, j, cols)
+            },
+            ConfigViolation::SortColOutOfRange { idx, cols } => {
+                write!(f, "sort column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::OutlierColOutOfRange { idx, cols } => {
+                write!(f, "outlier column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::HiddenColOutOfRange { idx, cols } => {
+                write!(f, "hidden column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::ColumnSpecsLen { len, cols } => {
+                write!(f, "{} column specs given for {} columns", len, cols)
+            },
+            ConfigViolation::OverflowColOutOfRange { idx, cols } => {
+                write!(f, "overflow column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::WhitespaceColOutOfRange { idx, cols } => {
+                write!(f, "whitespace column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::UnitsColOutOfRange { idx, cols } => {
+                write!(f, "unit scale column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::TypeColOutOfRange { idx, cols } => {
+                write!(f, "column type index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::HistogramColOutOfRange { idx, cols } => {
+                write!(f, "histogram column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::ZeroHistogramBuckets { idx } => {
+                write!(f, "histogram of column {} has zero buckets", idx)
+            },
+            ConfigViolation::InvalidColumnOrder { order, cols } => {
+                write!(f, "column order {:?} does not name each of {} columns once", order, cols)
+            },
+            ConfigViolation::KeyColOutOfRange { idx, cols } => {
+                write!(f, "key column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::SuppressRepeatsColOutOfRange { idx, cols } => {
+                write!(f, "repeat suppression column index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::DerivedColOutOfRange { idx, cols } => {
+                write!(f, "derived column source index {} is outside {} columns", idx, cols)
+            },
+            ConfigViolation::ZeroSampleRows => {
+                write!(f, "column widths cannot be sampled from zero rows")
+            },
+            ConfigViolation::ZeroRepeatHeaderEvery => {
+                write!(f, "the header cannot be repeated every zero rows")
+            },
+            ConfigViolation::RepeatHeaderWithoutHeader => {
+                write!(f, "the header is repeated but no header row is enabled")
+            },
+            ConfigViolation::RankTopStyleWithoutRank => {
+                write!(f, "top ranked rows are styled but no rank column is appended")
+            },
+            ConfigViolation::HeaderSeparatorWithoutHeader => {
+                write!(f, "a header separator is set but no header row is enabled")
+            },
+            ConfigViolation::AbbreviateHeadersWithoutHeader => {
+                write!(f, "headers are abbreviated but no header row is enabled")
+            },
+            ConfigViolation::VerticalHeadersWithoutHeader => {
+                write!(f, "headers are vertical but no header row is enabled")
+            },
+            ConfigViolation::ColSeparatorWithoutSpacing => {
+                write!(f, "a column separator is set but the column spacing is zero")
+            },
+            ConfigViolation::UnknownStyleName(name) => {
+                write!(f, "the style {:?} is not in the palette", name)
+            },
+        }
+    }
+}
+
+/// A configuration option found by [`GridPrinterBuilder::lints`] which is valid, but
+/// will have no effect.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigLint {
+    /// A style or format was set for a hidden column, which is never printed.
+    StyleOnHiddenCol { idx: usize },
+    /// A column's fixed width is wider than the maximum line width, so it is always
+    /// narrowed or dropped.
+    ColWidthExceedsMaxWidth { idx: usize, width: usize, max_width: usize },
+    /// Columns were to be wrapped, but no maximum width was set to wrap them at.
+    WrapColumnsWithoutMaxWidth,
+    /// Key columns were set, but no maximum width was set for them to be kept at.
+    KeyColsWithoutMaxWidth,
+    /// Headers were to be abbreviated, but no maximum width was set to fit them to.
+    AbbreviateHeadersWithoutMaxWidth,
+    /// Headers were to be abbreviated, but a grid which is too wide is wrapped into
+    /// sub-tables instead.
+    AbbreviateHeadersWithWrapColumns,
+    /// Short names were given for headers, but headers are not abbreviated.
+    ShortNamesWithoutAbbreviateHeaders,
+    /// A repeat style was set, but no column suppresses its repeats.
+    RepeatStyleWithoutSuppressRepeats,
+}
+
+impl Display for ConfigLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigLint::StyleOnHiddenCol { idx } => {
+                write!(f, "column {} is styled but hidden", idx)
+            },
+            ConfigLint::ColWidthExceedsMaxWidth { idx, width, max_width } => {
+                write!(f, "column {} is {} wide, wider than the maximum width of {}", idx, width, max_width)
+            },
+            ConfigLint::WrapColumnsWithoutMaxWidth => {
+                write!(f, "columns are wrapped, but no maximum width is set")
+            },
+            ConfigLint::KeyColsWithoutMaxWidth => {
+                write!(f, "key columns are set, but no maximum width is set")
+            },
+            ConfigLint::AbbreviateHeadersWithoutMaxWidth => {
+                write!(f, "headers are abbreviated, but no maximum width is set")
+            },
+            ConfigLint::AbbreviateHeadersWithWrapColumns => {
+                write!(f, "headers are abbreviated, but columns are wrapped instead")
+            },
+            ConfigLint::ShortNamesWithoutAbbreviateHeaders => {
+                write!(f, "header short names are given, but headers are not abbreviated")
+            },
+            ConfigLint::RepeatStyleWithoutSuppressRepeats => {
+                write!(f, "a repeat style is set, but no column suppresses repeats")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for GridPrinterErr {}
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::style::{Fg, Bg, Sgr};
+
+    #[test]
+    fn test_2d_arr() {
+        let v = vec![
+            vec![1, 20, 3, ],
+            vec![40, 5, 6, ],
+            vec![7, 800, 9, ],
+        ];
+
+        let rows = v.len();
+        let cols = v[0].len();
+        let printer = GridPrinterBuilder::new(rows, cols)
+            .col_spacing(20)
+            .build();
+        printer.print(&v);
+    }
+
+    #[test]
+    fn test_try_build_violations() {
+        let mut builder = GridPrinterBuilder::new(2, 2);
+        builder
+            .set_col_styles(vec![None])
+            .set_col_style(3, StyleOpt::new());
+
+        match builder.try_build() {
+            Err(GridPrinterErr::ConfigErr(violations)) => assert_eq!(violations, vec![
+                ConfigViolation::ColStyleOutOfRange { idx: 3, cols: 2 },
+            ]),
+            _ => panic!("expected ConfigErr"),
+        }
+
+        let mut builder = GridPrinterBuilder::new(2, 2);
+        builder.set_col_styles(vec![None]);
+        assert_eq!(builder.validate(), vec![ConfigViolation::ColStylesLen { len: 1, cols: 2 }]);
+    }
+
+    #[test]
+    fn test_sample_rows_truncates() {
+        let v = vec![
+            vec!["a", "bb"],
+            vec!["ccc", "d"],
+            vec!["中", "e"],
+        ];
+        let printer = GridPrinterBuilder::new(usize::MAX, 2)
+            .col_spacing(1)
+            .sample_rows(1)
+            .build();
+
+        let mut out = String::new();
+        printer.render_iter_to(&mut out, &v).unwrap();
+        assert_eq!(out, "a bb \nc d  \n  e  \n");
+    }
+
+    #[test]
+    fn test_borrowed_cells_match_formatted() {
+        let v = [
+            vec!["Make", "Model"],
+            vec!["Lamborghini", "Diablo"],
+        ];
+        let printer = GridPrinterBuilder::new(3, 2).build();
+
+        let formatted: Vec<Vec<String>> = (0..3).map(|i| printer.format_row(v.get(i).map(Vec::as_slice), false)).collect();
+        let borrowed: Vec<Vec<Cow<str>>> = (0..3).map(|i| printer.borrow_row(v.get(i).map(Vec::as_slice), false)).collect();
+        assert!(borrowed.iter().flatten().all(|cell| matches!(cell, Cow::Borrowed(_))));
+
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        printer.for_each_line(&formatted, &printer.print_options, |line| {
+            a.push(line.to_string());
+            Ok::<(), ()>(())
+        }).unwrap();
+        printer.for_each_line(&borrowed, &printer.print_options, |line| {
+            b.push(line.to_string());
+            Ok::<(), ()>(())
+        }).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_print_options_override() {
+        let v = vec![
+            vec!["aaaa", "bb", "c"],
+            vec!["d", "e", "f"],
+        ];
+        let printer = GridPrinterBuilder::new(2, 3)
+            .col_style(0, StyleOpt::new().fg(crate::style::Fg::Red)).unwrap()
+            .max_width(9)
+            .build();
+
+        assert_eq!(
+            printer.render_with(&v, &PrintOptions::new().color_mode(ColorMode::Never)),
+            "aaaa  \nd     \n",
+        );
+        assert_eq!(
+            printer.render_with(&v, &PrintOptions::new().color_mode(ColorMode::Never).max_width(10).max_rows(1)),
+            "aaaa  bb  \n",
+        );
+        assert!(printer.render(&v).contains(crate::style::Fg::Red.escape_code()));
+    }
+
+    #[test]
+    fn test_markup_width() {
+        let v = vec![
+            vec!["[red]FAILED[/]", "x"],
+            vec!["ok", "y"],
+        ];
+        let printer = GridPrinterBuilder::new(2, 2)
+            .markup(true)
+            .build();
+
+        let plain = printer.render_with(&v, &PrintOptions::new().color_mode(ColorMode::Never));
+        assert_eq!(plain, "FAILED  x  \nok      y  \n");
+        let styled = printer.render(&v);
+        assert!(styled.starts_with(&crate::style::stylize("FAILED", &StyleOpt::new().fg(crate::style::Fg::Red))));
+    }
+
+    #[test]
+    fn test_emoji_alignment() {
+        let v = vec![
+            vec!["🇺🇸", "a"],
+            vec!["❤️x", "b"],
+        ];
+        let printer = GridPrinterBuilder::new(2, 2).build();
+        assert_eq!(printer.render(&v), "🇺🇸   a  \n❤️x  b  \n");
+
+        let printer = GridPrinterBuilder::new(2, 2)
+            .emoji_width(EmojiWidth::One)
+            .build();
+        assert_eq!(printer.render(&v), "🇺🇸   a  \n❤️x  b  \n");
+    }
+
+    #[test]
+    fn test_tab_width() {
+        let v = vec![
+            vec!["a\tb", "c"],
+            vec!["dddd", "e"],
+        ];
+        let printer = GridPrinterBuilder::new(2, 2)
+            .tab_width(4)
+            .build();
+        assert_eq!(printer.render(&v), "a   b  c  \ndddd   e  \n");
+    }
+
+    #[test]
+    fn test_repeat_header_every() {
+        let v = vec![
+            vec!["H", "I"],
+            vec!["a", "b"],
+            vec!["c", "d"],
+            vec!["e", "f"],
+        ];
+        let printer = GridPrinterBuilder::new(4, 2)
+            .col_spacing(1)
+            .header(true)
+            .header_separator('-')
+            .repeat_header_every(2)
+            .build();
+        assert_eq!(printer.render(&v), "H I \n- - \na b \nc d \nH I \n- - \ne f \n");
+
+        let builder = GridPrinterBuilder::new(4, 2).repeat_header_every(0);
+        assert_eq!(builder.validate(), vec![ConfigViolation::ZeroRepeatHeaderEvery]);
+    }
+
+    #[test]
+    fn test_vertical_headers() {
+        let v = vec![
+            vec!["ab", "c"],
+            vec!["x", "yy"],
+        ];
+        let printer = GridPrinterBuilder::new(2, 2)
+            .col_spacing(1)
+            .header(true)
+            .header_separator('-')
+            .vertical_headers(true)
+            .repeat_header_every(1)
+            .build();
+        assert_eq!(printer.render(&v), "a    \nb c  \n- -- \nx yy \n");
+        assert_eq!(printer.layout(&v).row_heights, vec![2, 1]);
+
+        let builder = GridPrinterBuilder::new(2, 2).vertical_headers(true);
+        assert_eq!(builder.validate(), vec![ConfigViolation::VerticalHeadersWithoutHeader]);
+    }
+
+    #[test]
+    fn test_col_bool() {
+        let v = vec![
+            vec!["ok"],
+            vec!["yes"],
+            vec!["off"],
+            vec!["n/a"],
+        ];
+        let printer = GridPrinterBuilder::new(4, 1)
+            .col_spacing(0)
+            .header(true)
+            .col_bool(0, BoolStyle::Dots).unwrap()
+            .build();
+        assert_eq!(printer.render(&v), "ok \n\u{1b}[1;32m●\u{1b}[1;0m  \n\u{1b}[1;31m○\u{1b}[1;0m  \nn/a\n");
+
+        let mut builder = GridPrinterBuilder::new(4, 1);
+        builder.set_col_bool(1, BoolStyle::YesNo);
+        assert_eq!(builder.validate(), vec![ConfigViolation::ColFormatOutOfRange { idx: 1, cols: 1 }]);
+    }
+
+    #[test]
+    fn test_default_style() {
+        let v = vec![vec!["a", "b"]];
+        let printer = GridPrinterBuilder::new(1, 2)
+            .col_spacing(0)
+            .default_style(StyleOpt::new().fg(Fg::Blue).bg(Bg::White))
+            .col_style(1, StyleOpt::new().fg(Fg::Red)).unwrap()
+            .build();
+        let expected = format!(
+            "{}{}\n",
+            style::stylize("a", &StyleOpt::new().fg(Fg::Blue).bg(Bg::White)),
+            style::stylize("b", &StyleOpt::new().fg(Fg::Red).bg(Bg::White)),
+        );
+        assert_eq!(printer.render(&v), expected);
+    }
+
+    #[test]
+    fn test_fill_background() {
+        let v = vec![vec!["ab"], vec!["c"]];
+        let highlight = StyleOpt::new().bg(Bg::Yellow);
+        let printer = GridPrinterBuilder::new(2, 1)
+            .col_spacing(1)
+            .col_style(0, highlight.clone()).unwrap()
+            .fill_background(true)
+            .build();
+        let expected = format!(
+            "{} \n{}{} \n",
+            style::stylize("ab", &highlight),
+            style::stylize("c", &highlight),
+            style::stylize(" ", &highlight),
+        );
+        assert_eq!(printer.render(&v), expected);
+    }
+
+    #[test]
+    fn test_highlight_rows() {
+        let v = vec![vec!["H"], vec!["a"], vec!["b"]];
+        let highlight = StyleOpt::new().fg(Fg::Red);
+        let printer = GridPrinterBuilder::new(3, 1)
+            .col_spacing(0)
+            .header(true)
+            .highlight_rows(&[2], highlight.clone())
+            .build();
+        let expected = format!("H\na\n{}\n", style::stylize("b", &highlight));
+        assert_eq!(printer.render(&v), expected);
+    }
+
+    #[test]
+    fn test_highlight_rows_sorted() {
+        let v = vec![vec!["b", "2"], vec!["a", "1"], vec!["c", "3"]];
+        let highlight = StyleOpt::new().fg(Fg::Red);
+        let printer = GridPrinterBuilder::new(3, 2)
+            .col_spacing(0)
+            .sort_by_col(0, SortOrder::Ascending).unwrap()
+            .highlight_rows(&[0], highlight.clone())
+            .build();
+        let expected = format!("a1\n{}{}\nc3\n", style::stylize("b", &highlight), style::stylize("2", &highlight));
+        assert_eq!(printer.render(&v), expected);
+    }
+
+    #[test]
+    fn test_dropped_cols_not_formatted() {
+        struct Counted<'a>(&'a str, &'a core::cell::Cell<usize>);
+        impl Display for Counted<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.1.set(self.1.get() + 1);
+                write!(f, "{}", self.0)
+            }
+        }
+
+        let calls = core::cell::Cell::new(0);
+        let v: Vec<Vec<Counted>> = (0..3)
+            .map(|_| vec![Counted("aaaa", &calls), Counted("bbbb", &calls), Counted("cccc", &calls)])
+            .collect();
+        let printer = GridPrinterBuilder::new(3, 3)
+            .col_spacing(1)
+            .max_width(7)
+            .build();
+        assert_eq!(printer.render(&v), "aaaa \naaaa \naaaa \n");
+        assert_eq!(calls.get(), 6);
+    }
+
+    #[test]
+    fn test_palette() {
+        let v = vec![vec!["H"], vec!["a"], vec!["b"]];
+        let highlight = StyleOpt::new().fg(Fg::Red);
+        let builder = GridPrinterBuilder::new(3, 1)
+            .col_spacing(0)
+            .header(true)
+            .highlight_rows_named(&[2], "warning");
+        assert_eq!(builder.validate(), vec![ConfigViolation::UnknownStyleName("warning".to_string())]);
+
+        let printer = builder.palette(style::Palette::new().style("warning", highlight.clone())).build();
+        let expected = format!("H\na\n{}\n", style::stylize("b", &highlight));
+        assert_eq!(printer.render(&v), expected);
+    }
+
+    #[test]
+    fn test_col_outlier_style() {
+        let v = vec![vec!["ms"], vec!["10"], vec!["11"], vec!["9"], vec!["10"], vec!["50"]];
+        let outlier = StyleOpt::new().fg(Fg::Red);
+        let printer = GridPrinterBuilder::new(6, 1)
+            .col_spacing(0)
+            .header(true)
+            .col_outlier_style(0, outlier.clone(), options::OutlierRule::StdDev(1.5))
+            .unwrap()
+            .build();
+        let expected = format!("ms\n10\n11\n9 \n10\n{}\n", style::stylize("50", &outlier));
+        assert_eq!(printer.render(&v), expected);
+
+        let mut builder = GridPrinterBuilder::new(6, 1);
+        builder.set_col_outlier_style(1, outlier, options::OutlierRule::StdDev(2.0));
+        assert_eq!(builder.validate(), vec![ConfigViolation::OutlierColOutOfRange { idx: 1, cols: 1 }]);
+    }
+
+    #[test]
+    fn test_truncation_keeps_styles() {
+        let v = vec![vec!["\x1b[31mab\x1b[1mcdef\x1b[0m"]];
+        let printer = GridPrinterBuilder::new(1, 1)
+            .col_spacing(0)
+            .max_width(4)
+            .control_chars(ControlChars::KeepStyles)
+            .truncation_marker("…")
+            .build();
+        assert_eq!(printer.render(&v), "\x1b[31mab\x1b[1mc\x1b[0m…\n");
+
+        // A style left open is closed even when the cell fits.
+        let v = vec![vec!["\x1b[31mab"]];
+        assert_eq!(printer.render(&v), "\x1b[31mab\x1b[0m\n");
+
+        let v = vec![vec!["abcdef"]];
+        let red = StyleOpt::new().fg(Fg::Red);
+        let faint = StyleOpt::new().sgr(Sgr::Faint);
+        let printer = GridPrinterBuilder::new(1, 1)
+            .col_spacing(0)
+            .max_width(4)
+            .col_styles(vec![Some(red.clone())])
+            .unwrap()
+            .truncation_marker("…")
+            .truncation_style(faint.clone())
+            .build();
+        let expected = format!("{}{}\n", style::stylize("abc", &red), style::stylize("…", &red.merge(&faint)));
+        assert_eq!(printer.render(&v), expected);
+    }
+
+    #[test]
+    fn test_column_order() {
+        let v = vec![vec!["a", "bb", "ccc"]];
+        let builder = GridPrinterBuilder::new(1, 3)
+            .col_spacing(1)
+            .column_order(&[2, 0]);
+        assert_eq!(builder.validate(), vec![ConfigViolation::InvalidColumnOrder { order: vec![2, 0], cols: 3 }]);
+
+        let printer = builder.column_order(&[2, 0, 1])
+            .max_width(7)
+            .key_cols(&[1])
+            .unwrap()
+            .build();
+        assert_eq!(printer.render(&v), "ccc bb \n");
+    }
+
+    #[test]
+    fn test_col_ref_by_name() {
+        let printer = GridPrinterBuilder::new(3, 2)
+            .col_spacing(1)
+            .header(true)
+            .sort_by_col("size", SortOrder::Ascending)
+            .unwrap()
+            .hide_column("name")
+            .unwrap()
+            .build();
+        let v = vec![vec!["name", "size"], vec!["a", "2"], vec!["b", "1"]];
+        assert_eq!(printer.render(&v), "size ▲ \n1      \n2      \n");
+        // The names follow the columns when the source's columns are reordered.
+        let v = vec![vec!["size", "name"], vec!["2", "a"], vec!["1", "b"]];
+        assert_eq!(printer.render(&v), "size ▲ \n1      \n2      \n");
+    }
+
+    #[test]
+    fn test_sort_by_keys() {
+        let v = vec![vec!["dept", "name"], vec!["ops", "bo"], vec!["dev", "cy"], vec!["ops", "al"], vec!["dev", "di"]];
+        let printer = GridPrinterBuilder::new(5, 2)
+            .col_spacing(1)
+            .header(true)
+            .sort_by(&[("dept", SortOrder::Descending), ("name", SortOrder::Ascending)])
+            .unwrap()
+            .build();
+        assert_eq!(printer.render(&v), "dept ▼ name ▲ \nops    al     \nops    bo     \ndev    cy     \ndev    di     \n");
+
+        // Keys by index and by name keep their priority once the names are looked up.
+        let printer = GridPrinterBuilder::new(4, 2)
+            .col_spacing(1)
+            .header(true)
+            .sort_by_col(0, SortOrder::Ascending)
+            .unwrap()
+            .sort_by(&[(ColRef::from("name"), SortOrder::Descending), (ColRef::from(0), SortOrder::Ascending)])
+            .unwrap()
+            .build();
+        let v = vec![vec!["dept", "name"], vec!["ops", "bo"], vec!["dev", "bo"], vec!["ops", "al"]];
+        assert_eq!(printer.render(&v), "dept ▲ name ▼ \ndev    bo     \nops    bo     \nops    al     \n");
+    }
+
+    #[test]
+    fn test_sort_natural() {
+        let v = vec![vec!["name"], vec!["file10"], vec!["file9"], vec!["file1"]];
+        let printer = GridPrinterBuilder::new(4, 1)
+            .header(true)
+            .sort_by_col(0, SortOrder::Natural)
+            .unwrap()
+            .build();
+        assert_eq!(printer.render(&v), "name ▲  \nfile1   \nfile9   \nfile10  \n");
+    }
+
+    #[test]
+    fn test_suppress_repeats() {
+        let v = vec![vec!["b", "1"], vec!["a", "2"], vec!["b", "3"]];
+        let repeat = StyleOpt::new().sgr(Sgr::Faint);
+        let printer = GridPrinterBuilder::new(3, 2)
+            .col_spacing(1)
+            .sort_by_col(0, SortOrder::Ascending)
+            .unwrap()
+            .suppress_repeats(0)
+            .unwrap()
+            .repeat_style(repeat.clone())
+            .build();
+        let expected = format!("a 2 \nb 1 \n{} 3 \n", style::stylize("b", &repeat));
+        assert_eq!(printer.render(&v), expected);
+    }
+
+    #[test]
+    fn test_rank_by_col() {
+        let v = vec![vec!["a", "3"], vec!["b", "10"], vec!["c", "3"], vec!["d", "-"]];
+        let top = StyleOpt::new().fg(Fg::Red);
+        let printer = GridPrinterBuilder::new(4, 2)
+            .col_spacing(1)
+            .rank_by_col(1)
+            .unwrap()
+            .rank_top_style(1, top.clone())
+            .build();
+        let expected = format!("a 3  2 \n{} {} {} \nc 3  2 \nd -    \n", style::stylize("b", &top), style::stylize("10", &top), style::stylize("1", &top));
+        assert_eq!(printer.render(&v), expected);
+
+        let builder = GridPrinterBuilder::new(4, 2).rank_top_style(1, top);
+        assert_eq!(builder.validate(), vec![ConfigViolation::RankTopStyleWithoutRank]);
+    }
+
+    #[test]
+    fn test_highlight_matches() {
+        let v = vec![vec!["abcab", "x"]];
+        let hit = StyleOpt::new().fg(Fg::Red);
+        let printer = GridPrinterBuilder::new(1, 2)
+            .col_spacing(1)
+            .highlight_matches("ab", hit.clone())
+            .build();
+        let expected = format!("{}c{} x \n", style::stylize("ab", &hit), style::stylize("ab", &hit));
+        assert_eq!(printer.render(&v), expected);
+
+        let plain = PrintOptions::new().color_mode(ColorMode::Never);
+        assert_eq!(printer.render_with(&v, &plain), "abcab x \n");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_env_overrides() {
+        let env = |name: &str| match name {
+            "NO_COLOR" => Some("1".to_string()),
+            "GRID_PRINTER_MAXWIDTH" => Some("40".to_string()),
+            "GRID_PRINTER_THEME" => Some("sepia".to_string()),
+            _ => None,
+        };
+        let mut builder = GridPrinterBuilder::new(1, 1).max_width(120);
+        builder.apply_env(env);
+        assert_eq!(builder.print_options.color_mode, Some(ColorMode::Never));
+        assert_eq!(builder.print_options.max_width, Some(40));
+        assert_eq!(builder.palette, Palette::new());
+
+        let env = |name: &str| match name {
+            "GRID_PRINTER_THEME" => Some("light".to_string()),
+            _ => None,
+        };
+        let brand = StyleOpt::new().fg(crate::style::Fg::Magenta);
+        let mut builder = GridPrinterBuilder::new(1, 1)
+            .palette(Palette::new().style("brand", brand.clone()).style("header", brand.clone()));
+        builder.apply_env(env);
+        let theme = Palette::theme(crate::style::Background::Light);
+        assert_eq!(builder.palette.get("brand"), Some(&brand));
+        assert_eq!(builder.palette.get("header"), theme.get("header"));
+        assert_eq!(builder.palette.get("zebra"), theme.get("zebra"));
+    }
+
+    #[cfg(feature = "textwrap")]
+    #[test]
+    fn test_textwrap_wrap() {
+        use crate::text::wrap;
+        use crate::width::EmojiWidth;
+
+        assert_eq!(wrap("well-known value", 8, EmojiWidth::Two), vec!["well-", "known", "value"]);
+        assert_eq!(wrap("abcdefghij xy", 4, EmojiWidth::Two), vec!["abcd", "efgh", "ij", "xy"]);
+        assert_eq!(wrap("ab\ncd", 8, EmojiWidth::Two), vec!["ab", "cd"]);
+        assert_eq!(wrap("", 8, EmojiWidth::Two), vec![""]);
+        assert_eq!(wrap("🎉🎉🎉 ok", 3, EmojiWidth::One), vec!["🎉🎉🎉", "ok"]);
+        assert_eq!(wrap("🎉🎉🎉 ok", 3, EmojiWidth::Two), vec!["🎉", "🎉", "🎉", "ok"]);
+        assert_eq!(wrap("中", 1, EmojiWidth::Two), vec!["中"]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_detect_color_support() {
+        let printer = GridPrinterBuilder::new(1, 1).build();
+        let detected = printer.detect_color_support().map(|printer| printer.color_support);
+        match ColorSupport::detect() {
+            ColorSupport::TrueColor => assert_eq!(detected, None),
+            color_support => assert_eq!(detected, Some(Some(color_support))),
+        }
+
+        let printer = GridPrinterBuilder::new(1, 1).color_support(ColorSupport::TrueColor).build();
+        assert!(printer.detect_color_support().is_none());
+    }
+
+    #[test]
+    fn test_accessible_column_order() {
+        let v = vec![vec!["H1", "H2", "H3"], vec!["x", "y", "z"]];
+        let printer = GridPrinterBuilder::new(2, 3)
+            .header(true)
+            .column_order(&[1, 0, 2])
+            .hide_column(2)
+            .unwrap()
+            .accessible(true)
+            .build();
+        assert_eq!(printer.render(&v), "H2: y; H1: x\n");
+    }
+
+    #[test]
+    fn test_layout_wrapped_row_heights() {
+        let v = vec![vec!["well-known value"], vec!["short"]];
+        let printer = GridPrinterBuilder::new(2, 1)
+            .col_spacing(0)
+            .max_width(8)
+            .col_overflow(0, Overflow::Wrap)
+            .unwrap()
+            .build();
+        assert_eq!(printer.layout(&v).row_heights, vec![3, 1]);
+        assert_eq!(printer.measure(&[&v]).row_heights, vec![3, 1]);
+        assert_eq!(printer.render(&v).lines().count(), 4);
+    }
+
+    #[test]
+    fn test_wrap_breaks_after_hyphens() {
+        let v = vec![vec!["well-known value"]];
+        let printer = GridPrinterBuilder::new(1, 1)
+            .col_spacing(0)
+            .max_width(8)
+            .col_overflow(0, Overflow::Wrap)
+            .unwrap()
+            .build();
+        assert_eq!(printer.render(&v), "well-   \nknown   \nvalue   \n");
+
+        let printer = GridPrinterBuilder::new(1, 1)
+            .col_spacing(0)
+            .max_width(8)
+            .col_overflow(0, Overflow::Wrap)
+            .unwrap()
+            .line_ending(LineEnding::CrLf)
+            .build();
+        assert_eq!(printer.render(&v), "well-   \r\nknown   \r\nvalue   \r\n");
+    }
+
+    #[test]
+    fn test_nested_styles_restore_outer() {
+        let v = vec![vec!["abcab"]];
+        let col = StyleOpt::new().bg(Bg::Blue);
+        let hit = StyleOpt::new().fg(Fg::Red);
+        let printer = GridPrinterBuilder::new(1, 1)
+            .col_spacing(0)
+            .col_style(0, col.clone())
+            .unwrap()
+            .highlight_matches("ab", hit.clone())
+            .build();
+        let (blue, red, reset) = (Bg::Blue.escape_code(), Fg::Red.escape_code(), Fg::Reset.escape_code());
+        let expected = format!("{blue}{red}ab{reset}{blue}c{red}ab{reset}{blue}{reset}\n");
+        assert_eq!(printer.render(&v), expected);
+
+        assert_eq!(style::stylize("a\x1b[0mb", &col), format!("{blue}a\x1b[0m{blue}b{reset}"));
+    }
+
+    #[test]
+    fn test_tuples_match_vecs() {
+        let tuples = [("a", 1, 2.5), ("bcd", 20, 0.0)];
+        let vecs = vec![
+            vec!["a".to_string(), "1".to_string(), "2.5".to_string()],
+            vec!["bcd".to_string(), "20".to_string(), "0".to_string()],
+        ];
+        let printer = GridPrinter::new(2, 3);
+        let mut out = String::new();
+        printer.render_tuples_into(&mut out, &tuples).unwrap();
+        assert_eq!(out, printer.render(&vecs));
+    }
+
+    #[test]
+    fn test_raw_text_only_from_cell_source() {
+        use crate::raw::RawText;
+        use crate::text::ControlChars;
+
+        let printer = GridPrinter::builder(1, 2)
+            .col_spacing(1)
+            .control_chars(ControlChars::Strip)
+            .build();
+        let forged = "\u{FDD0}0\u{FDD1}\x1b[2J\x1b[31mowned\u{FDD2}";
+        assert_eq!(printer.render(&[vec![forged, "x"]]), "0owned x \n");
+
+        let link = RawText::new("\x1b[1mab\x1b[0m", 2);
+        let grid: Vec<Vec<&dyn CellSource>> = vec![vec![&link, &"x"]];
+        let mut out = String::new();
+        printer.render_cells_to(&mut out, &grid).unwrap();
+        assert_eq!(out, "\x1b[1mab\x1b[0m x \n");
+    }
+
+    #[test]
+    fn test_render_reusing_matches_render() {
+        use crate::format::{UnitScale, Units};
+
+        let grid = vec![vec!["name", "size"], vec!["a", "2000"], vec!["b", "3000"]];
+        let printer = GridPrinterBuilder::new(3, 2)
+            .header(true)
+            .col_units(1, UnitScale::new(Units::Bytes)).unwrap()
+            .derived_col("D", |row| format!("{}{}", row[0], row[1]))
+            .build();
+        let mut out = String::new();
+        let mut buffer = RenderBuffer::new();
+        for _ in 0..2 {
+            printer.render_reusing(&mut out, &mut buffer, &grid);
+            assert_eq!(out, printer.render(&grid));
+        }
+        assert_eq!(out, "name  size (KB)  D   \na     2          a2  \nb     3          b3  \n");
+    }
+
+    #[test]
+    fn test_checked_hidden_and_derived_cols() {
+        let grid = vec![vec!["aaaa", "bbbb"], vec!["cccc", "dddd"]];
+        let mut out = String::new();
+        let printer = GridPrinterBuilder::new(2, 2)
+            .hide_column(1).unwrap()
+            .build();
+        let report = printer.render_checked_into(&mut out, &grid).unwrap();
+        assert_eq!(report, TruncationReport { dropped_rows: 0, dropped_cols: 0 });
+
+        out.clear();
+        let printer = GridPrinterBuilder::new(2, 2)
+            .col_spacing(1)
+            .derived_col("D", |row| row[0].to_uppercase())
+            .column_order(&[2, 0, 1])
+            .max_width(10)
+            .build();
+        let report = printer.render_checked_into(&mut out, &grid).unwrap();
+        assert_eq!(out, "AAAA aaaa \nCCCC cccc \n");
+        assert_eq!(report, TruncationReport { dropped_rows: 0, dropped_cols: 1 });
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GridPrinter>();
+        assert_send_sync::<GridPrinterBuilder>();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let printer = GridPrinterBuilder::new(2, 3)
+            .col_spacing(5)
+            .col_style(1, StyleOpt::new().fg(crate::style::Fg::Red)).unwrap()
+            .build();
+
+        let json = serde_json::to_string(&printer).unwrap();
+        let printer: GridPrinter = serde_json::from_str(&json).unwrap();
+        assert_eq!(printer.col_spacing, 5);
+        assert_eq!(printer.cols, 3);
+
+        let printer = GridPrinterBuilder::new(2, 3)
+            .header(true)
+            .derived_col_percent_of(1).unwrap()
+            .column_order(&[3, 0, 1, 2])
+            .build();
+        let json = serde_json::to_string(&printer).unwrap();
+        let grid = vec![vec!["Region", "Sales", "Units"], vec!["East", "30", "2"]];
+        assert_eq!(serde_json::from_str::<GridPrinter>(&json).unwrap().render(&grid), printer.render(&grid));
+
+        let printer = GridPrinterBuilder::new(2, 3).derived_col("Total", |_| String::new()).build();
+        assert!(serde_json::to_string(&printer).is_err());
+
+        let printer = GridPrinterBuilder::new(1, 1)
+            .highlight_matches("ok", StyleOpt::new().fg(crate::style::Fg::Green))
+            .build();
+        let json = serde_json::to_string(&printer).unwrap();
+        let grid = vec![vec!["ok: 200"]];
+        assert!(printer.render(&grid).contains('\x1b'));
+        assert_eq!(serde_json::from_str::<GridPrinter>(&json).unwrap().render(&grid), printer.render(&grid));
+
+        #[cfg(feature = "regex")]
+        {
+            let printer = GridPrinterBuilder::new(1, 1)
+                .highlight_matches(regex::Regex::new("[0-9]+").unwrap(), StyleOpt::new().fg(crate::style::Fg::Red))
+                .build();
+            let json = serde_json::to_string(&printer).unwrap();
+            assert_eq!(serde_json::from_str::<GridPrinter>(&json).unwrap().render(&grid), printer.render(&grid));
+        }
+
+        let json = r#"{"rows": 1, "cols": 2, "col_styles": [null, null, {"fg": "Red"}]}"#;
+        assert!(serde_json::from_str::<GridPrinterBuilder>(json).is_ok());
+        assert!(serde_json::from_str::<GridPrinter>(json).is_err());
+
+        let builder: GridPrinterBuilder = serde_json::from_str(r#"{"cols": 4}"#).unwrap();
+        assert_eq!(builder.rows, 1);
+        assert_eq!(builder.col_spacing, 2);
     }
 
 }
@@ -0,0 +1,121 @@
+//! A small inline markup language for styling parts of a cell, enabled with
+//! [`GridPrinterBuilder::markup`](crate::GridPrinterBuilder::markup).
+//!
+//! A tag such as `[red]`, `[bold]`, or `[black on yellow]` styles the text which
+//! follows it until the matching `[/]`. Tags nest, and `[[` produces a literal `[`.
+//! A bracketed word which is not a recognized style is left untouched, so data such
+//! as `[1]` prints as-is.
+
+use alloc::vec::Vec;
+
+use crate::style::{Bg, Fg, Sgr, StyleOpt};
+
+/// A run of cell text sharing a single style.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Span<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) style: Option<StyleOpt>,
+}
+
+/// Parse the words of a tag, e.g. `bold red on white`, into a StyleOpt.
+fn parse_tag(tag: &str) -> Option<StyleOpt> {
+    let mut opt = StyleOpt::new();
+    let mut words = tag.split_whitespace().peekable();
+    words.peek()?;
+    while let Some(word) = words.next() {
+        if word == "on" {
+            opt.bg = Some(words.next()?.parse::<Bg>().ok()?);
+        } else if let Ok(fg) = word.parse::<Fg>() {
+            opt.fg = Some(fg);
+        } else {
+            opt.sgr = Some(word.parse::<Sgr>().ok()?);
+        }
+    }
+
+    Some(opt)
+}
+
+/// Split `cell` into styled spans, removing the markup tags.
+pub(crate) fn parse(cell: &str) -> Vec<Span<'_>> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut stack: Vec<StyleOpt> = Vec::new();
+    let current = |stack: &[StyleOpt]| stack.last().cloned();
+
+    let mut text_start = 0;
+    let mut i = 0;
+    while let Some(offset) = cell[i..].find('[') {
+        let open = i + offset;
+        if cell[open + 1..].starts_with('[') {
+            // An escaped `[[`: end the current span just after the first bracket.
+            spans.push(Span { text: &cell[text_start..open + 1], style: current(&stack) });
+            text_start = open + 2;
+            i = open + 2;
+            continue;
+        }
+        let close = match cell[open..].find(']') {
+            None => break,
+            Some(close) => open + close,
+        };
+        let tag = &cell[open + 1..close];
+        let next = if tag == "/" {
+            match stack.is_empty() {
+                true => None,
+                false => Some(None),
+            }
+        } else {
+            parse_tag(tag).map(Some)
+        };
+        match next {
+            // Not a recognized tag; leave it in the text.
+            None => i = open + 1,
+            Some(next) => {
+                if open > text_start {
+                    spans.push(Span { text: &cell[text_start..open], style: current(&stack) });
+                }
+                match next {
+                    None => { stack.pop(); },
+                    Some(opt) => {
+                        let opt = match stack.last() {
+                            None => opt,
+                            Some(outer) => outer.merge(&opt),
+                        };
+                        stack.push(opt);
+                    },
+                }
+                text_start = close + 1;
+                i = close + 1;
+            },
+        }
+    }
+    if text_start < cell.len() {
+        spans.push(Span { text: &cell[text_start..], style: current(&stack) });
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let red = StyleOpt::new().fg(Fg::Red);
+        assert_eq!(parse("[red]FAILED[/] x"), vec![
+            Span { text: "FAILED", style: Some(red.clone()) },
+            Span { text: " x", style: None },
+        ]);
+        assert_eq!(parse("[red]a[bold]b[/][/]"), vec![
+            Span { text: "a", style: Some(red.clone()) },
+            Span { text: "b", style: Some(red.sgr(Sgr::Bold)) },
+        ]);
+        assert_eq!(parse("[1] [[x] [/]"), vec![
+            Span { text: "[1] [", style: None },
+            Span { text: "x] [/]", style: None },
+        ]);
+        assert_eq!(parse("[black on yellow]!"), vec![
+            Span { text: "!", style: Some(StyleOpt::new().fg(Fg::Black).bg(Bg::Yellow)) },
+        ]);
+    }
+}
@@ -0,0 +1,165 @@
+//! The matrix module provides [`MatrixPrinter`], a printer for dense grids of
+//! numbers or single characters, such as game boards and heat maps.
+//!
+//! Unlike a GridPrinter, a MatrixPrinter does not measure each column: every cell
+//! has the same width, either fixed or the width of the widest cell, and cells are
+//! right aligned.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::matrix::MatrixPrinter;
+//!
+//! let board = vec![
+//!     vec!['X', 'O', ' '],
+//!     vec![' ', 'X', ' '],
+//!     vec!['O', ' ', 'X'],
+//! ];
+//! let printer = MatrixPrinter::new()
+//!     .row_labels(true)
+//!     .col_labels(true);
+//! assert_eq!(printer.render(&board), concat!(
+//!     "  0 1 2\n",
+//!     "0 X O  \n",
+//!     "1   X  \n",
+//!     "2 O   X\n",
+//! ));
+//! ```
+
+use core::fmt::Display;
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+use crate::sink::Sink;
+use crate::width::{self, EmojiWidth};
+
+/// A printer for dense grids whose cells all share one width.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatrixPrinter {
+    cell_width: Option<usize>,
+    spacing: usize,
+    row_labels: bool,
+    col_labels: bool,
+}
+
+impl Default for MatrixPrinter {
+    fn default() -> Self {
+        Self {
+            cell_width: None,
+            spacing: 1,
+            row_labels: false,
+            col_labels: false,
+        }
+    }
+}
+
+impl MatrixPrinter {
+
+    /// Create a MatrixPrinter with cells as wide as the widest cell, separated by
+    /// one space, and no labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give every cell the fixed width `cell_width`, truncating wider cells, so the
+    /// grid is not measured at all.
+    pub fn cell_width(self, cell_width: usize) -> Self {
+        Self { cell_width: Some(cell_width), ..self }
+    }
+
+    /// Set the number of spaces between cells.
+    pub fn spacing(self, spacing: usize) -> Self {
+        Self { spacing, ..self }
+    }
+
+    /// Label each row with its index.
+    pub fn row_labels(self, row_labels: bool) -> Self {
+        Self { row_labels, ..self }
+    }
+
+    /// Label each column with its index, in a row above the grid. The cells are
+    /// widened to fit the labels.
+    pub fn col_labels(self, col_labels: bool) -> Self {
+        Self { col_labels, ..self }
+    }
+
+    /// Render the matrix into a [`Sink`], one line at a time.
+    pub fn render_into<S: Sink, F: Display>(&self, sink: &mut S, source: &[Vec<F>]) -> Result<(), S::Error> {
+        let cells: Vec<Vec<String>> = source.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        let cols = cells.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut cell_width = match self.cell_width {
+            Some(cell_width) => cell_width,
+            None => cells.iter()
+                .flatten()
+                .map(|cell| width::display_width(cell, EmojiWidth::default()))
+                .max()
+                .unwrap_or(0),
+        };
+        if self.col_labels {
+            cell_width = cell_width.max(digits(cols.saturating_sub(1)));
+        }
+        let label_width = digits(cells.len().saturating_sub(1));
+
+        let mut line = String::new();
+        if self.col_labels {
+            let labels: Vec<String> = (0..cols).map(|col| col.to_string()).collect();
+            self.render_row(&mut line, None, &labels, cell_width, label_width);
+            sink.write_line(&line)?;
+        }
+        for (row_idx, row) in cells.iter().enumerate() {
+            line.clear();
+            self.render_row(&mut line, Some(row_idx), row, cell_width, label_width);
+            sink.write_line(&line)?;
+        }
+
+        Ok(())
+    }
+
+    /// Render the matrix into a `String`, terminating each line with `\n`.
+    pub fn render<F: Display>(&self, source: &[Vec<F>]) -> String {
+        let mut out = String::new();
+        let Ok(()) = self.render_into(&mut out, source);
+
+        out
+    }
+
+    /// Print the matrix to stdout.
+    #[cfg(feature = "std")]
+    pub fn print<F: Display>(&self, source: &[Vec<F>]) {
+        print!("{}", self.render(source));
+    }
+
+    /// Append a row of right aligned cells to `line`, preceded by its label if the
+    /// rows are labelled. The column label row has no label of its own.
+    fn render_row(&self, line: &mut String, row_idx: Option<usize>, row: &[String], cell_width: usize, label_width: usize) {
+        if self.row_labels {
+            let label = row_idx.map(|row_idx| row_idx.to_string()).unwrap_or_default();
+            line.push_str(&format!("{:<1$}", label, label_width));
+            line.extend(core::iter::repeat_n(' ', self.spacing));
+        }
+        for (col_idx, cell) in row.iter().enumerate() {
+            if col_idx > 0 {
+                line.extend(core::iter::repeat_n(' ', self.spacing));
+            }
+            let (cell, width) = width::truncate(cell, cell_width, EmojiWidth::default());
+            line.extend(core::iter::repeat_n(' ', cell_width - width));
+            line.push_str(cell);
+        }
+    }
+}
+
+/// The number of decimal digits in `n`.
+fn digits(n: usize) -> usize {
+    let mut n = n;
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+
+    digits
+}
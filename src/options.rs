@@ -0,0 +1,119 @@
+//! The options module provides [`PrintOptions`], a set of settings which can be
+//! configured on a GridPrinter, and overridden for a single call with
+//! [`GridPrinter::print_with`](crate::GridPrinter::print_with).
+
+use crate::style::ColorMode;
+
+/// Settings which may be overridden for a single print, without rebuilding the
+/// GridPrinter. Unset options fall back to those the GridPrinter was built with.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrintOptions {
+    pub color_mode: Option<ColorMode>,
+    pub max_width: Option<usize>,
+    pub max_rows: Option<usize>,
+    pub accessible: Option<bool>,
+}
+
+impl PrintOptions {
+
+    /// Create a new PrintOptions with no options set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set when the column styles are applied.
+    pub fn color_mode(self, color_mode: ColorMode) -> Self {
+        Self { color_mode: Some(color_mode), ..self }
+    }
+
+    /// Set the maximum line width; trailing columns which do not fit are dropped.
+    pub fn max_width(self, max_width: usize) -> Self {
+        Self { max_width: Some(max_width), ..self }
+    }
+
+    /// Set the maximum number of rows printed.
+    pub fn max_rows(self, max_rows: usize) -> Self {
+        Self { max_rows: Some(max_rows), ..self }
+    }
+
+    /// Set whether each row is printed as a `Header: value; Header: value` line
+    /// instead of aligned columns, for screen readers.
+    pub fn accessible(self, accessible: bool) -> Self {
+        Self { accessible: Some(accessible), ..self }
+    }
+}
+
+/// An enumeration of the orders rows may be sorted in, as set with
+/// [`GridPrinterBuilder::sort_by_col`](crate::GridPrinterBuilder::sort_by_col).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortOrder {
+    /// Smallest first, marked with `▲` in the header.
+    #[default]
+    Ascending,
+    /// Largest first, marked with `▼` in the header.
+    Descending,
+    /// Smallest first, comparing runs of digits within text by their value, so
+    /// that `file2` sorts before `file10`. Marked with `▲` in the header.
+    Natural,
+}
+
+impl SortOrder {
+
+    /// The indicator appended to the header of the sorted column.
+    pub fn indicator(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending | SortOrder::Natural => " ▲",
+            SortOrder::Descending => " ▼",
+        }
+    }
+}
+
+/// An enumeration of the rules which decide whether a numeric cell is an outlier
+/// of its column, as set with
+/// [`GridPrinterBuilder::col_outlier_style`](crate::GridPrinterBuilder::col_outlier_style).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutlierRule {
+    /// Further than the given number of standard deviations from the column's mean.
+    StdDev(f64),
+}
+
+/// The statistics of a numeric column, measured over its data cells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ColStats {
+    mean: f64,
+    variance: f64,
+}
+
+impl ColStats {
+
+    /// Measure the cells of a column which parse as numbers, if there are any.
+    pub(crate) fn measure<'c, I: IntoIterator<Item = &'c str>>(cells: I) -> Option<Self> {
+        let (mut n, mut sum, mut sum_sq) = (0usize, 0.0, 0.0);
+        for value in cells.into_iter().filter_map(|cell| cell.trim().parse::<f64>().ok()) {
+            n += 1;
+            sum += value;
+            sum_sq += value * value;
+        }
+        if n == 0 {
+            return None;
+        }
+        let mean = sum / n as f64;
+
+        Some(Self { mean, variance: (sum_sq / n as f64 - mean * mean).max(0.0) })
+    }
+
+    /// Whether `cell` is a number which is an outlier of the column under `rule`.
+    pub(crate) fn is_outlier(&self, cell: &str, rule: OutlierRule) -> bool {
+        let value = match cell.trim().parse::<f64>() {
+            Ok(value) => value,
+            Err(_) => return false,
+        };
+        match rule {
+            // Note: Compare squares, as `f64::sqrt` is unavailable without std.
+            OutlierRule::StdDev(n) => (value - self.mean) * (value - self.mean) > n * n * self.variance,
+        }
+    }
+}
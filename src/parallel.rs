@@ -0,0 +1,143 @@
+//! A `rayon`-backed printing path for very large grids, enabled by the `rayon` feature.
+
+use std::io;
+use std::io::Write;
+use std::fmt::Display;
+
+use rayon::prelude::*;
+
+use crate::GridPrinter;
+use crate::render::LineRenderer;
+use crate::sink::{IoSink, Sink};
+
+impl GridPrinter {
+
+    /// Format and render every row of `source` in parallel, without printing. The
+    /// LineRenderer for the measured columns and the rendered header lines, if any,
+    /// are returned alongside the rendered data rows and the footer row, if any.
+    #[allow(clippy::type_complexity)]
+    fn par_render_lines<F: Display + Sync>(&self, source: &[Vec<F>], styled: bool) -> (LineRenderer<'_>, Option<Vec<String>>, Vec<String>, Option<Vec<String>>) {
+        let opts = &self.print_options;
+        let mut buff: Vec<Vec<String>> = (0..self.row_limit(opts))
+            .into_par_iter()
+            .map(|i| self.format_row(source.get(i).map(Vec::as_slice), i == 0 && self.header))
+            .collect();
+        self.scale_units(&mut buff);
+        self.derive_cols(&mut buff);
+
+        let (header, rows) = match self.header && !buff.is_empty() {
+            true => (Some(&buff[0]), &buff[1..]),
+            false => (None, &buff[..]),
+        };
+
+        let mut max_widths = rows.par_iter()
+            .fold(|| vec![0; self.cols], |mut max_widths, row| {
+                self.measure_row(&mut max_widths, row);
+                max_widths
+            })
+            .reduce(|| vec![0; self.cols], |mut a, b| {
+                for (a, b) in a.iter_mut().zip(b) {
+                    *a = (*a).max(b);
+                }
+                a
+            });
+
+        if let Some(header) = header {
+            self.measure_header(&mut max_widths, header);
+        }
+        let footer = self.footer_row(&buff);
+        if let Some(footer) = footer.as_ref() {
+            self.measure_row(&mut max_widths, footer);
+        }
+
+        let (header_cells, visible) = self.fit_layout(&buff, &mut max_widths, opts);
+        let renderer = LineRenderer::new(self, max_widths, styled, header_cells)
+            .visible(visible)
+            .col_stats(self.col_stats(&buff));
+
+        let header = header.map(|header| renderer.render_header(header));
+        let offset = usize::from(header.is_some());
+        let order = self.row_order(&buff);
+        let order = &order[offset.min(order.len())..];
+        let lines = order.par_iter()
+            .enumerate()
+            .map(|(k, i)| {
+                let mut line = String::new();
+                let prev = k.checked_sub(1).map(|prev| buff[order[prev]].as_slice());
+                renderer.render_after(&mut line, *i, &buff[*i], prev);
+                line
+            })
+            .collect();
+
+        (renderer, header, lines, footer)
+    }
+
+    /// Print `source` like [`print`](GridPrinter::print), but measure and format the
+    /// rows in parallel on the `rayon` thread pool before writing them sequentially.
+    ///
+    /// This pays off for grids with hundreds of thousands of cells; for small grids
+    /// [`print`](GridPrinter::print) is usually faster.
+    pub fn par_print<F: Display + Sync>(&self, source: &[Vec<F>]) {
+        if let Some(printer) = self.bind_source_names(source) {
+            return printer.par_print(source);
+        }
+        if let Some(printer) = self.detect_color_support() {
+            return printer.par_print(source);
+        }
+        // Accessible lines are cheap to build, so they are printed sequentially, as
+        // are the stacked sub-tables of wrapped columns.
+        let wraps_cols = self.wrap_columns && self.print_options.max_width.is_some();
+        if self.print_options.accessible == Some(true) || wraps_cols {
+            return self.print(source);
+        }
+        let styled = self.print_options.color_mode.clone().unwrap_or_default().enabled();
+        let (mut renderer, header, lines, footer) = self.par_render_lines(source, styled);
+
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        if let Some(header) = header {
+            renderer.push_header(header, &mut |line| sink.write_line_ending(line, self.line_ending.as_str())).unwrap();
+        }
+        for line in lines.iter() {
+            renderer.push_line(line, &mut |line| sink.write_line_ending(line, self.line_ending.as_str())).unwrap();
+        }
+        if let Some(footer) = footer {
+            renderer.push_footer(&footer, &mut |line| sink.write_line_ending(line, self.line_ending.as_str())).unwrap();
+        }
+        sink.0.flush().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::options::SortOrder;
+
+    #[test]
+    fn test_par_render_matches_sequential() {
+        let v: Vec<Vec<usize>> = (0..500)
+            .map(|i| vec![i, i * 1000, i % 7])
+            .collect();
+        let printer = GridPrinter::builder(v.len(), 3).build();
+
+        let buff: Vec<Vec<String>> = v.iter().map(|row| printer.format_row(Some(row.as_slice()), false)).collect();
+        let max_widths = printer.max_widths(&buff);
+        let expected: Vec<String> = buff.iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut line = String::new();
+                printer.render_row(&mut line, i, row, &max_widths, &Default::default(), true);
+                line
+            })
+            .collect();
+        assert_eq!(printer.par_render_lines(&v, true).2, expected);
+
+        let printer = GridPrinter::builder(v.len(), 3)
+            .sort_by_col(2, SortOrder::Descending).unwrap()
+            .suppress_repeats(2).unwrap()
+            .build();
+        let lines = printer.par_render_lines(&v, false).2;
+        assert_eq!(lines.join("\n") + "\n", printer.render(&v));
+    }
+}
@@ -0,0 +1,130 @@
+//! The progress module provides [`ProgressGrid`], a grid in which one column is a
+//! progress bar per row, redrawn in place as the progress of each row changes,
+//! e.g. for download managers and batch jobs.
+//!
+//! # Example
+//! ```rust,no_run
+//! use grid_printer::GridPrinter;
+//! use grid_printer::progress::ProgressGrid;
+//!
+//! let jobs = vec![
+//!     vec!["backup".to_string(), String::new()],
+//!     vec!["upload".to_string(), String::new()],
+//! ];
+//! let printer = GridPrinter::builder(jobs.len(), 2).build();
+//! let mut grid = ProgressGrid::new(printer, jobs, 1, 20);
+//!
+//! let mut stdout = std::io::stdout();
+//! for step in 0..=10 {
+//!     grid.set_progress(0, step as f64 / 10.0);
+//!     grid.set_progress(1, step as f64 / 20.0);
+//!     grid.redraw(&mut stdout).unwrap();
+//! }
+//! ```
+
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::GridPrinter;
+#[cfg(feature = "std")]
+use crate::ansi::{CsiSequence, EraseLine};
+
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Draw `fraction` (clamped to `0.0..=1.0`) as a bar of `width` columns of block
+/// characters, with eighth-column resolution.
+///
+/// ```rust
+/// use grid_printer::progress::bar;
+///
+/// assert_eq!(bar(0.5, 4), "██░░");
+/// assert_eq!(bar(0.3, 4), "█▏░░");
+/// ```
+pub fn bar(fraction: f64, width: usize) -> String {
+    let fraction = if fraction.is_nan() { 0.0 } else { fraction.clamp(0.0, 1.0) };
+    let eighths = (fraction * (width * 8) as f64) as usize;
+    let (full, partial) = (eighths / 8, eighths % 8);
+
+    let mut bar: String = core::iter::repeat_n('█', full).collect();
+    if partial > 0 {
+        bar.push(PARTIAL_BLOCKS[partial]);
+    }
+    let drawn = full + usize::from(partial > 0);
+    bar.extend(core::iter::repeat_n('░', width.saturating_sub(drawn)));
+
+    bar
+}
+
+/// A grid with a progress bar column, redrawn in place.
+#[derive(Debug, Clone)]
+pub struct ProgressGrid {
+    printer: GridPrinter,
+    rows: Vec<Vec<String>>,
+    progress: Vec<Option<f64>>,
+    bar_col: usize,
+    bar_width: usize,
+    #[cfg(feature = "std")]
+    drawn_lines: usize,
+}
+
+impl ProgressGrid {
+
+    /// Create a ProgressGrid of `rows`, printed with `printer`, whose column
+    /// `bar_col` shows a bar `bar_width` columns wide and a percentage for each row
+    /// with progress set. The cells of rows without progress are printed as given.
+    pub fn new(printer: GridPrinter, rows: Vec<Vec<String>>, bar_col: usize, bar_width: usize) -> Self {
+        let progress = alloc::vec![None; rows.len()];
+        Self {
+            printer,
+            rows,
+            progress,
+            bar_col,
+            bar_width,
+            #[cfg(feature = "std")]
+            drawn_lines: 0,
+        }
+    }
+
+    /// Set the progress of row `row_idx`, from `0.0` to `1.0`.
+    pub fn set_progress(&mut self, row_idx: usize, fraction: f64) {
+        if let Some(progress) = self.progress.get_mut(row_idx) {
+            *progress = Some(fraction);
+        }
+    }
+
+    /// Render the current state of the grid into a `String`.
+    pub fn render(&self) -> String {
+        let rows: Vec<Vec<String>> = self.rows.iter()
+            .zip(self.progress.iter())
+            .map(|(row, progress)| {
+                let mut row = row.clone();
+                if let (Some(fraction), Some(cell)) = (progress, row.get_mut(self.bar_col)) {
+                    let percent = (fraction.clamp(0.0, 1.0) * 100.0) as usize;
+                    *cell = format!("{} {:>3}%", bar(*fraction, self.bar_width), percent);
+                }
+                row
+            })
+            .collect();
+
+        self.printer.render(&rows)
+    }
+
+    /// Write the current state of the grid to `w`, first moving the cursor up over
+    /// the previously drawn state so that it is replaced in place.
+    #[cfg(feature = "std")]
+    pub fn redraw<W: io::Write>(&mut self, w: &mut W) -> io::Result<()> {
+        if self.drawn_lines > 0 {
+            write!(w, "{}\r", CsiSequence::cursor_up(self.drawn_lines))?;
+        }
+        let frame = self.render();
+        for line in frame.lines() {
+            writeln!(w, "{}{}", CsiSequence::erase_line(EraseLine::All), line)?;
+        }
+        self.drawn_lines = frame.lines().count();
+
+        w.flush()
+    }
+}
@@ -0,0 +1,84 @@
+//! The qr module, enabled by the `qr` feature, draws cell values such as URLs and
+//! enrollment tokens as QR codes of block characters, each spanning several lines
+//! of a grid.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::qr::expand_qr_col;
+//!
+//! let devices = vec![
+//!     vec!["Device", "Enroll"],
+//!     vec!["laptop", "https://example.com/enroll/1"],
+//!     vec!["phone", "https://example.com/enroll/2"],
+//! ];
+//! let grid = expand_qr_col(&devices, 1, true, true).unwrap();
+//! let printer = GridPrinter::builder(grid.len(), 2).header(true).build();
+//! printer.print(&grid);
+//! ```
+
+use std::fmt::Display;
+
+pub use qrcode::types::QrError;
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
+
+/// Draw `data` as a QR code of half block characters, two modules tall per line,
+/// surrounded by the quiet zone scanners need.
+///
+/// When `invert` is set the light modules are drawn as blocks, so the code scans
+/// as dark on light on a terminal with a dark background.
+///
+/// ```rust
+/// use grid_printer::qr::qr_lines;
+///
+/// // The smallest QR code is 21 modules square, plus a quiet zone of 4 each side.
+/// let lines = qr_lines(b"hi", true).unwrap();
+/// assert_eq!(lines.len(), 15);
+/// assert!(lines.iter().all(|line| line.chars().count() == 29));
+/// ```
+pub fn qr_lines(data: &[u8], invert: bool) -> Result<Vec<String>, QrError> {
+    let code = QrCode::new(data)?;
+    let (dark, light) = match invert {
+        false => (Dense1x2::Dark, Dense1x2::Light),
+        true => (Dense1x2::Light, Dense1x2::Dark),
+    };
+    let image = code.render::<Dense1x2>()
+        .dark_color(dark)
+        .light_color(light)
+        .quiet_zone(true)
+        .build();
+
+    Ok(image.lines().map(String::from).collect())
+}
+
+/// Expand the rows of `source` so the cell of each row at `col_idx` is drawn as a
+/// QR code, one line of it per row. The other cells of the row are printed beside
+/// the first line of the code, with blank cells below them. When `header` is set,
+/// the first row is kept as text.
+///
+/// Build the GridPrinter for the number of rows returned. Fails if a cell holds
+/// more data than a QR code can.
+pub fn expand_qr_col<F: Display>(source: &[Vec<F>], col_idx: usize, header: bool, invert: bool) -> Result<Vec<Vec<String>>, QrError> {
+    let mut expanded = Vec::new();
+    for (row_idx, row) in source.iter().enumerate() {
+        let cells: Vec<String> = row.iter().map(ToString::to_string).collect();
+        let value = match cells.get(col_idx) {
+            Some(value) if !(header && row_idx == 0) => value,
+            _ => {
+                expanded.push(cells);
+                continue;
+            },
+        };
+        for (i, line) in qr_lines(value.as_bytes(), invert)?.into_iter().enumerate() {
+            let mut line_cells = match i {
+                0 => cells.clone(),
+                _ => vec![String::new(); cells.len()],
+            };
+            line_cells[col_idx] = line;
+            expanded.push(line_cells);
+        }
+    }
+
+    Ok(expanded)
+}
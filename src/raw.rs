@@ -0,0 +1,150 @@
+//! The raw module provides [`RawText`], an escape hatch for cell content which a
+//! GridPrinter must pass through untouched, such as cursor movement or the escape
+//! sequences of inline terminal images.
+//!
+//! Raw text is neither measured nor sanitized: it is printed as-is, and counted
+//! as the number of columns it declares. **You own the alignment of such a cell.**
+//! If the declared width is wrong, the columns after it will be misaligned.
+//!
+//! A cell is only raw when it is a [`RawText`] passed through the [`CellSource`]
+//! methods, such as [`print_cells`](crate::GridPrinter::print_cells). Formatted
+//! into a `String`, it is ordinary text, which is measured and sanitized like any
+//! other, so that no cell text can make itself raw.
+
+use core::fmt;
+use core::fmt::Display;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::cell::CellSource;
+
+/// Marks the start of a raw segment, followed by its declared width.
+const RAW_START: char = '\u{FDD0}';
+/// Separates the declared width of a raw segment from its text.
+const RAW_TEXT: char = '\u{FDD1}';
+/// Marks the end of a raw segment.
+const RAW_END: char = '\u{FDD2}';
+
+/// Cell content printed verbatim, bypassing width measurement and sanitization.
+///
+/// ```rust
+/// use grid_printer::GridPrinter;
+/// use grid_printer::cell::CellSource;
+/// use grid_printer::raw::RawText;
+///
+/// // A hyperlink escape sequence, drawn as the 4 columns of its label.
+/// let link = RawText::new("\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\", 4);
+/// let grid: Vec<Vec<&dyn CellSource>> = vec![
+///     vec![&link, &"manual"],
+///     vec![&"readme", &"intro"],
+/// ];
+/// let printer = GridPrinter::builder(2, 2).col_spacing(1).build();
+/// let mut rendered = String::new();
+/// printer.render_cells_to(&mut rendered, &grid).unwrap();
+/// assert!(rendered.starts_with("\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\   manual"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawText<T> {
+    text: T,
+    width: usize,
+}
+
+impl<T: Display> RawText<T> {
+
+    /// Wrap `text`, which the terminal draws `width` columns wide.
+    pub fn new(text: T, width: usize) -> Self {
+        Self { text, width }
+    }
+}
+
+impl<T: Display> Display for RawText<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.text.fmt(f)
+    }
+}
+
+impl<T: Display> CellSource for RawText<T> {
+    fn cell(&self) -> Cow<'_, str> {
+        Cow::Owned(self.text.to_string())
+    }
+
+    fn raw_width(&self) -> Option<usize> {
+        Some(self.width)
+    }
+}
+
+/// Mark `text` as a raw segment, which is counted as `width` columns wide.
+pub(crate) fn mark_raw(text: &str, width: usize) -> String {
+    format!("{}{}{}{}{}", RAW_START, width, RAW_TEXT, text, RAW_END)
+}
+
+/// Whether `c` is one of the characters marking raw segments.
+fn is_marker(c: char) -> bool {
+    matches!(c, RAW_START | RAW_TEXT | RAW_END)
+}
+
+/// Remove the characters marking raw segments from cell text which is not raw,
+/// so that it cannot pass itself off as raw.
+pub(crate) fn strip_markers(s: Cow<'_, str>) -> Cow<'_, str> {
+    match s.contains(is_marker) {
+        false => s,
+        true => Cow::Owned(s.replace(is_marker, "")),
+    }
+}
+
+/// A writer which drops the characters marking raw segments on the way to `W`.
+pub(crate) struct StripMarkers<W>(pub(crate) W);
+
+impl<W: fmt::Write> fmt::Write for StripMarkers<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.split(is_marker).try_for_each(|text| self.0.write_str(text))
+    }
+}
+
+/// Whether `s` contains raw text.
+pub(crate) fn contains_raw(s: &str) -> bool {
+    s.contains(RAW_START)
+}
+
+/// Split `s` into runs of ordinary text, and the raw segments along with their
+/// declared widths.
+pub(crate) fn raw_segments(s: &str) -> impl Iterator<Item = (&str, Option<usize>)> {
+    let mut rest = s;
+    core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let raw = rest.strip_prefix(RAW_START).and_then(|raw| {
+            let (width, raw) = raw.split_once(RAW_TEXT)?;
+            let (text, remaining) = raw.split_once(RAW_END)?;
+            Some((width.parse::<usize>().ok()?, text, remaining))
+        });
+        if let Some((width, text, remaining)) = raw {
+            rest = remaining;
+            return Some((text, Some(width)));
+        }
+
+        let first = rest.chars().next().map_or(1, char::len_utf8);
+        let end = rest[first..].find(RAW_START).map_or(rest.len(), |i| i + first);
+        let (text, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some((text, None))
+    })
+}
+
+/// The text of `s` as printed, without the markers around its raw segments.
+pub(crate) fn unwrap_raw(s: &str) -> Cow<'_, str> {
+    match contains_raw(s) {
+        false => Cow::Borrowed(s),
+        true => Cow::Owned(raw_segments(s).map(|(text, _)| text).collect()),
+    }
+}
+
+/// The number of columns `s` occupies, measuring its ordinary text with `measure`
+/// and counting its raw segments as their declared widths.
+pub(crate) fn raw_width<F: Fn(&str) -> usize>(s: &str, measure: F) -> usize {
+    raw_segments(s)
+        .map(|(text, width)| width.unwrap_or_else(|| measure(text)))
+        .sum()
+}
@@ -0,0 +1,339 @@
+//! The line renderer, which turns measured rows into the lines of a printed grid,
+//! inserting the header and its separator where configured.
+
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+use alloc::collections::BTreeMap;
+
+use crate::GridPrinter;
+use crate::markup;
+use crate::raw;
+use crate::width;
+use crate::options::ColStats;
+use crate::column::Overflow;
+
+/// Buffers kept between renders by
+/// [`GridPrinter::render_reusing`](crate::GridPrinter::render_reusing), so that
+/// printing the same shape of grid repeatedly reuses the formatted cell strings
+/// rather than allocating new ones.
+#[derive(Debug, Clone, Default)]
+pub struct RenderBuffer {
+    pub(crate) cells: Vec<Vec<String>>,
+}
+
+impl RenderBuffer {
+
+    /// Create a new, empty RenderBuffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The state a row is rendered in beyond its own cells: the statistics outliers
+/// are judged by, the columns whose cell repeats the one above it, and the indices
+/// of the printed columns when they aren't the leading columns.
+#[derive(Default)]
+pub(crate) struct RowContext<'r> {
+    pub(crate) col_stats: Option<&'r BTreeMap<usize, ColStats>>,
+    pub(crate) repeated: Vec<usize>,
+    pub(crate) visible: Option<&'r [usize]>,
+}
+
+/// Renders the rows of a grid into lines and hands each line, excluding the line
+/// terminator, to a callback.
+pub(crate) struct LineRenderer<'p> {
+    printer: &'p GridPrinter,
+    max_widths: Vec<usize>,
+    styled: bool,
+    line: String,
+    header: Option<Vec<String>>,
+    header_cells: Option<Vec<String>>,
+    data_rows: usize,
+    labels: Option<Vec<String>>,
+    col_stats: BTreeMap<usize, ColStats>,
+    prev_row: Option<Vec<String>>,
+    visible: Option<Vec<usize>>,
+}
+
+impl<'p> LineRenderer<'p> {
+
+    /// Create a LineRenderer for columns of the given widths. When `header_cells`
+    /// is given, those cells are rendered in place of the source's header row.
+    pub(crate) fn new(printer: &'p GridPrinter, max_widths: Vec<usize>, styled: bool, header_cells: Option<Vec<String>>) -> Self {
+        Self {
+            printer,
+            max_widths,
+            styled,
+            line: String::new(),
+            header: None,
+            header_cells,
+            data_rows: 0,
+            labels: None,
+            col_stats: BTreeMap::new(),
+            prev_row: None,
+            visible: None,
+        }
+    }
+
+    /// Render each row as a `Header: value; Header: value` line for screen readers,
+    /// rather than as aligned columns.
+    pub(crate) fn accessible(mut self, accessible: bool) -> Self {
+        if accessible {
+            let labels = (0..self.printer.cols)
+                .map(|col_idx| match self.printer.columns().get(col_idx) {
+                    Some(column) => column.name.clone(),
+                    None => format!("Column {}", col_idx + 1),
+                })
+                .collect();
+            self.labels = Some(labels);
+        }
+
+        self
+    }
+
+    /// Print the columns at the given indices, rather than the leading columns, when
+    /// key columns were kept in place of earlier ones.
+    pub(crate) fn visible(mut self, visible: Option<Vec<usize>>) -> Self {
+        self.visible = visible;
+
+        self
+    }
+
+    /// Whether the column at `col_idx` is printed.
+    fn is_printed(&self, col_idx: usize) -> bool {
+        match self.visible.as_ref() {
+            None => col_idx < self.max_widths.len(),
+            Some(visible) => visible[..self.max_widths.len()].contains(&col_idx),
+        }
+    }
+
+    /// Judge outliers by the statistics of the columns measured with the rows.
+    pub(crate) fn col_stats(mut self, col_stats: BTreeMap<usize, ColStats>) -> Self {
+        self.col_stats = col_stats;
+
+        self
+    }
+
+    /// The text of a cell, without any markup tags.
+    fn plain_text(&self, cell: &str) -> String {
+        if raw::contains_raw(cell) {
+            return raw::unwrap_raw(cell).into_owned();
+        }
+        match self.printer.markup {
+            true => markup::parse(cell).iter().map(|span| span.text).collect(),
+            false => cell.to_string(),
+        }
+    }
+
+    /// Render a formatted row as a labelled line for screen readers, or take its
+    /// cells as the labels if it is the header row.
+    fn push_accessible<S, E, L>(&mut self, row: &[S], f: &mut L) -> Result<(), E>
+    where
+        S: AsRef<str>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        if self.expects_header() {
+            let labels = row.iter().map(|cell| self.plain_text(cell.as_ref())).collect();
+            self.labels = Some(labels);
+            self.header = Some(Vec::new());
+            return Ok(());
+        }
+
+        // Note: Label the cells in the order the grid prints its columns.
+        let labels = self.labels.as_deref().unwrap_or_default();
+        let line = self.printer.col_order()
+            .into_iter()
+            .filter_map(|col_idx| Some((labels.get(col_idx)?, row.get(col_idx)?)))
+            .map(|(label, cell)| format!("{}: {}", label, self.plain_text(cell.as_ref())))
+            .collect::<Vec<String>>()
+            .join("; ");
+        self.data_rows += 1;
+
+        f(&line)
+    }
+
+    /// Take back the column widths the renderer was created with.
+    pub(crate) fn into_widths(self) -> Vec<usize> {
+        self.max_widths
+    }
+
+    /// Whether the next row pushed is the header row.
+    pub(crate) fn expects_header(&self) -> bool {
+        self.printer.header && self.header.is_none()
+    }
+
+    /// Render a formatted row, the `row_idx`th of the source, into a line.
+    pub(crate) fn render<S: AsRef<str>>(&self, line: &mut String, row_idx: usize, row: &[S]) {
+        self.render_after::<S, S>(line, row_idx, row, None);
+    }
+
+    /// The number of lines a formatted row, the `row_idx`th of the source, renders
+    /// into: more than one when its cells wrap.
+    pub(crate) fn row_height<S: AsRef<str>>(&self, row_idx: usize, row: &[S]) -> usize {
+        if !self.printer.col_overflows.values().any(|overflow| *overflow == Overflow::Wrap) {
+            return 1;
+        }
+        let mut line = String::new();
+        self.render(&mut line, row_idx, row);
+
+        line.split('\n').count()
+    }
+
+    /// Render a formatted row into a line like [`render`](LineRenderer::render),
+    /// suppressing the cells which repeat those of `prev`, the row printed above it.
+    pub(crate) fn render_after<S, P>(&self, line: &mut String, row_idx: usize, row: &[S], prev: Option<&[P]>)
+    where
+        S: AsRef<str>,
+        P: AsRef<str>,
+    {
+        let ctx = RowContext {
+            col_stats: Some(&self.col_stats),
+            repeated: self.printer.repeated_cols(prev, row),
+            visible: self.visible.as_deref(),
+        };
+        self.printer.render_row(line, row_idx, row, &self.max_widths, &ctx, self.styled);
+    }
+
+    /// Render the header row into its lines: a single line, or one line per
+    /// character when the headers are vertical.
+    pub(crate) fn render_header<S: AsRef<str>>(&self, row: &[S]) -> Vec<String> {
+        let mut line = String::new();
+        if !self.printer.vertical_headers {
+            match self.header_cells.as_ref() {
+                Some(header_cells) => self.render(&mut line, 0, header_cells),
+                None => self.render(&mut line, 0, row),
+            }
+            return vec![line];
+        }
+
+        // Split each header into the clusters drawn on each line, bottom aligned.
+        let columns: Vec<Vec<&str>> = row.iter()
+            .enumerate()
+            .map(|(col_idx, cell)| {
+                let cell = match self.is_printed(col_idx) {
+                    true => cell.as_ref(),
+                    false => "",
+                };
+                let spans = match self.printer.markup {
+                    true => markup::parse(cell).into_iter().map(|span| span.text).collect(),
+                    false => vec![cell],
+                };
+                spans.into_iter()
+                    .flat_map(|span| width::clusters(span, self.printer.emoji_width).map(|(cluster, _)| cluster))
+                    .filter(|cluster| !cluster.trim().is_empty())
+                    .collect()
+            })
+            .collect();
+        let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+
+        (0..height)
+            .map(|i| {
+                let cells: Vec<&str> = columns.iter()
+                    .map(|clusters| match (i + clusters.len()).checked_sub(height) {
+                        Some(j) => clusters[j],
+                        None => "",
+                    })
+                    .collect();
+                let mut line = String::new();
+                self.render(&mut line, 0, &cells);
+                line
+            })
+            .collect()
+    }
+
+    /// Render the header separator, if configured, and hand it to `f`.
+    fn separator<E, L>(&mut self, f: &mut L) -> Result<(), E>
+    where
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        let sep = match self.printer.header_separator {
+            None => return Ok(()),
+            Some(sep) => sep,
+        };
+        self.line.clear();
+        self.printer.render_separator(&mut self.line, sep, &self.max_widths, self.styled);
+
+        f(&self.line)
+    }
+
+    /// Hand the rendered header lines to `f`, followed by the separator, and keep
+    /// them to be repeated.
+    pub(crate) fn push_header<E, L>(&mut self, header: Vec<String>, f: &mut L) -> Result<(), E>
+    where
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        for line in header.iter().flat_map(|line| line.split('\n')) {
+            f(line)?;
+        }
+        self.header = Some(header);
+
+        self.separator(f)
+    }
+
+    /// Hand an already rendered data row to `f`, preceded by the header when it is
+    /// due to be repeated.
+    pub(crate) fn push_line<E, L>(&mut self, line: &str, f: &mut L) -> Result<(), E>
+    where
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        if let (Some(n), Some(header)) = (self.printer.repeat_header_every, self.header.as_ref()) {
+            if self.data_rows > 0 && self.data_rows.is_multiple_of(n) {
+                for line in header.iter().flat_map(|line| line.split('\n')) {
+                    f(line)?;
+                }
+                self.separator(f)?;
+            }
+        }
+        self.data_rows += 1;
+
+        // Note: Each line of a wrapped row is handed to `f` on its own, so that it
+        // ends with the printer's line ending.
+        line.split('\n').try_for_each(f)
+    }
+
+    /// Render the footer row beneath the data rows, unstyled, and hand it to `f`.
+    /// Lines for screen readers have no footer.
+    pub(crate) fn push_footer<S, E, L>(&mut self, row: &[S], f: &mut L) -> Result<(), E>
+    where
+        S: AsRef<str>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        if self.labels.is_some() {
+            return Ok(());
+        }
+        self.line.clear();
+        let ctx = RowContext { visible: self.visible.as_deref(), ..Default::default() };
+        self.printer.render_row(&mut self.line, usize::MAX, row, &self.max_widths, &ctx, false);
+
+        self.line.split('\n').try_for_each(f)
+    }
+
+    /// Render a formatted row, the `row_idx`th of the source, or the header row if it
+    /// is next, and hand it to `f`.
+    pub(crate) fn push_row<S, E, L>(&mut self, row_idx: usize, row: &[S], f: &mut L) -> Result<(), E>
+    where
+        S: AsRef<str>,
+        L: FnMut(&str) -> Result<(), E>,
+    {
+        if self.labels.is_some() {
+            return self.push_accessible(row, f);
+        }
+        if self.expects_header() {
+            let header = self.render_header(row);
+            return self.push_header(header, f);
+        }
+
+        let mut line = core::mem::take(&mut self.line);
+        line.clear();
+        self.render_after(&mut line, row_idx, row, self.prev_row.as_deref());
+        let result = self.push_line(&line, f);
+        self.line = line;
+        if !self.printer.suppress_repeats.is_empty() {
+            self.prev_row = Some(row.iter().map(|cell| cell.as_ref().to_string()).collect());
+        }
+
+        result
+    }
+}
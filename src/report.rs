@@ -0,0 +1,187 @@
+//! The report module provides [`Report`], which composes a title, captioned grids,
+//! and paragraphs of text into a single document rendered with one width and one
+//! color mode.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::report::Report;
+//! use grid_printer::style::ColorMode;
+//!
+//! let disks = vec![vec!["/", "81%"], vec!["/home", "35%"]];
+//! let printer = GridPrinter::builder(2, 2).col_spacing(1).build();
+//!
+//! let report = Report::new("Nightly")
+//!     .color_mode(ColorMode::Never)
+//!     .paragraph("All hosts responded.")
+//!     .grid("Disk usage", &printer, &disks);
+//! assert_eq!(report.render(), concat!(
+//!     "Nightly\n",
+//!     "=======\n",
+//!     "\n",
+//!     "All hosts responded.\n",
+//!     "\n",
+//!     "Disk usage\n",
+//!     "/     81% \n",
+//!     "/home 35% \n",
+//! ));
+//! ```
+
+use core::fmt::Display;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+use crate::{GridPrinter, PrintOptions};
+use crate::style::{stylize_into, ColorMode, Sgr, StyleOpt};
+use crate::width::{self, EmojiWidth};
+
+/// A section of a report.
+enum Section<'a> {
+    Paragraph(String),
+    Grid {
+        caption: String,
+        render: Box<dyn Fn(&PrintOptions) -> String + 'a>,
+    },
+}
+
+/// A document of a title followed by paragraphs and captioned grids.
+pub struct Report<'a> {
+    title: String,
+    width: Option<usize>,
+    color_mode: ColorMode,
+    title_style: StyleOpt,
+    caption_style: StyleOpt,
+    sections: Vec<Section<'a>>,
+}
+
+impl<'a> Report<'a> {
+
+    /// Create a report with the given title, a bold title and captions, and no
+    /// maximum width.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            width: None,
+            color_mode: ColorMode::default(),
+            title_style: StyleOpt::new().sgr(Sgr::Bold),
+            caption_style: StyleOpt::new().sgr(Sgr::Bold),
+            sections: Vec::new(),
+        }
+    }
+
+    /// Wrap the paragraphs to `width` columns, and drop the columns of each grid
+    /// which do not fit within it.
+    pub fn width(self, width: usize) -> Self {
+        Self { width: Some(width), ..self }
+    }
+
+    /// Set when the title, captions, and grids are styled.
+    pub fn color_mode(self, color_mode: ColorMode) -> Self {
+        Self { color_mode, ..self }
+    }
+
+    /// Set the style of the title.
+    pub fn title_style(self, title_style: StyleOpt) -> Self {
+        Self { title_style, ..self }
+    }
+
+    /// Set the style of the grid captions.
+    pub fn caption_style(self, caption_style: StyleOpt) -> Self {
+        Self { caption_style, ..self }
+    }
+
+    /// Append a paragraph of text.
+    pub fn paragraph(mut self, text: &str) -> Self {
+        self.sections.push(Section::Paragraph(text.to_string()));
+
+        self
+    }
+
+    /// Append `source`, printed with `printer`, beneath `caption`. The grid is
+    /// rendered with the report's width and color mode.
+    pub fn grid<F: Display>(mut self, caption: &str, printer: &'a GridPrinter, source: &'a [Vec<F>]) -> Self {
+        self.sections.push(Section::Grid {
+            caption: caption.to_string(),
+            render: Box::new(move |opts| printer.render_with(source, opts)),
+        });
+
+        self
+    }
+
+    /// Append `text` to `out` in `style_opt`, if the report is styled.
+    fn push_styled(&self, out: &mut String, text: &str, style_opt: &StyleOpt) {
+        match self.color_mode.enabled() {
+            true => stylize_into(out, text, style_opt),
+            false => out.push_str(text),
+        }
+    }
+
+    /// Render the report into a `String`, separating its sections with blank lines.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.push_styled(&mut out, &self.title, &self.title_style);
+        out.push('\n');
+        let underline = width::display_width(&self.title, EmojiWidth::default());
+        out.extend(core::iter::repeat_n('=', underline));
+        out.push('\n');
+
+        let mut opts = PrintOptions::new().color_mode(self.color_mode.clone());
+        opts.max_width = self.width;
+        for section in self.sections.iter() {
+            out.push('\n');
+            match section {
+                Section::Paragraph(text) => {
+                    for line in wrap(text, self.width) {
+                        out.push_str(&line);
+                        out.push('\n');
+                    }
+                },
+                Section::Grid { caption, render } => {
+                    self.push_styled(&mut out, caption, &self.caption_style);
+                    out.push('\n');
+                    out.push_str(&render(&opts));
+                },
+            }
+        }
+
+        out
+    }
+
+    /// Print the report to stdout.
+    #[cfg(feature = "std")]
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
+
+/// Greedily wrap the words of `text` into lines of at most `width` columns. Words
+/// wider than `width` are kept whole on their own line.
+fn wrap(text: &str, width: Option<usize>) -> Vec<String> {
+    let width = match width {
+        None => return text.lines().map(str::to_string).collect(),
+        Some(width) => width,
+    };
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for word in text.split_whitespace() {
+        let word_width = width::display_width(word, EmojiWidth::default());
+        if line_width > 0 && line_width + 1 + word_width > width {
+            lines.push(core::mem::take(&mut line));
+            line_width = 0;
+        }
+        if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(word);
+        line_width += word_width;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
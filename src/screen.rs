@@ -0,0 +1,84 @@
+//! The screen module, enabled by the `screen` feature, switches the terminal to its
+//! alternate screen with the cursor hidden, so a tool can present one live grid
+//! full-screen, redrawn from the top left, and leave the terminal's scrollback as
+//! it was when it's done.
+//!
+//! # Example
+//! ```rust,no_run
+//! use std::io::Write;
+//! use grid_printer::GridPrinter;
+//! use grid_printer::ansi::CsiSequence;
+//! use grid_printer::screen::with_alt_screen;
+//!
+//! let printer = GridPrinter::builder(2, 2).build();
+//! with_alt_screen(|| {
+//!     let mut stdout = std::io::stdout();
+//!     for tick in 0..10 {
+//!         let grid = vec![vec!["tick".to_string(), tick.to_string()]; 2];
+//!         write!(stdout, "{}{}", CsiSequence::cursor_position(1, 1), printer.render(&grid))?;
+//!         stdout.flush()?;
+//!         std::thread::sleep(std::time::Duration::from_millis(100));
+//!     }
+//!     Ok(())
+//! })
+//! .unwrap();
+//! ```
+
+use std::io;
+use std::io::Write;
+
+use crate::ansi::CsiSequence;
+
+/// The private mode of the alternate screen, which also saves the cursor.
+const ALT_SCREEN: u16 = 1049;
+/// The private mode of the cursor's visibility.
+const CURSOR_VISIBLE: u16 = 25;
+
+/// Write `sequence` to stdout and flush it.
+fn write_stdout(sequence: CsiSequence) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", sequence)?;
+
+    stdout.flush()
+}
+
+/// Switch stdout to the alternate screen.
+pub fn enter_alt() -> io::Result<()> {
+    write_stdout(CsiSequence::set_private_mode(ALT_SCREEN))
+}
+
+/// Switch stdout back from the alternate screen to the main screen.
+pub fn leave_alt() -> io::Result<()> {
+    write_stdout(CsiSequence::reset_private_mode(ALT_SCREEN))
+}
+
+/// Hide the cursor.
+pub fn hide_cursor() -> io::Result<()> {
+    write_stdout(CsiSequence::reset_private_mode(CURSOR_VISIBLE))
+}
+
+/// Show the cursor.
+pub fn show_cursor() -> io::Result<()> {
+    write_stdout(CsiSequence::set_private_mode(CURSOR_VISIBLE))
+}
+
+/// Restores the main screen and the cursor when dropped, including on a panic.
+struct AltScreen;
+
+impl Drop for AltScreen {
+    fn drop(&mut self) {
+        // Note: There's nowhere to report a failure to restore the terminal.
+        let _ = show_cursor();
+        let _ = leave_alt();
+    }
+}
+
+/// Run `f` on the alternate screen with the cursor hidden, then restore the main
+/// screen and the cursor, even if `f` fails or panics.
+pub fn with_alt_screen<T, F: FnOnce() -> io::Result<T>>(f: F) -> io::Result<T> {
+    enter_alt()?;
+    let _guard = AltScreen;
+    hide_cursor()?;
+
+    f()
+}
@@ -0,0 +1,124 @@
+//! The scroll module, enabled by the `scroll` feature, pages a rendered grid taller
+//! than the terminal in a viewport on the alternate screen, for
+//! [`GridPrinter::print_scrollable`](crate::GridPrinter::print_scrollable).
+
+use std::io;
+use std::io::{BufRead, IsTerminal, Write};
+
+use alloc::vec::Vec;
+
+use crossterm::terminal;
+
+use crate::ansi::{CsiSequence, EraseLine};
+use crate::screen::with_alt_screen;
+
+/// A key which moves the viewport.
+#[derive(Debug, PartialEq)]
+enum Key {
+    Down,
+    Up,
+    PageDown,
+    PageUp,
+    Quit,
+    Other,
+}
+
+/// Read a key from `stdin`: `j` or down, `k` or up, space or `b` for a page down
+/// or up, and `q`, Ctrl-C, or the end of input to quit.
+fn read_key<R: BufRead>(stdin: &mut R) -> io::Result<Key> {
+    let buf = stdin.fill_buf()?;
+    let byte = match buf.first() {
+        Some(byte) => *byte,
+        None => return Ok(Key::Quit),
+    };
+    // Note: A terminal writes an arrow key's escape sequence at once, so its bytes are
+    // read together, and an Esc read alone is a key of its own rather than a prefix.
+    let (key, len) = match (byte, buf.get(1..3)) {
+        (0x1b, Some(b"[B")) => (Key::Down, 3),
+        (0x1b, Some(b"[A")) => (Key::Up, 3),
+        (b'j' | b'\r', _) => (Key::Down, 1),
+        (b'k', _) => (Key::Up, 1),
+        (b' ', _) => (Key::PageDown, 1),
+        (b'b', _) => (Key::PageUp, 1),
+        (b'q' | 0x03, _) => (Key::Quit, 1),
+        _ => (Key::Other, 1),
+    };
+    stdin.consume(len);
+
+    Ok(key)
+}
+
+/// Disables raw mode when dropped.
+struct RawMode;
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Draw the `height` lines of `lines` from `top`, followed by a status line.
+fn draw<W: Write>(w: &mut W, lines: &[&str], top: usize, height: usize) -> io::Result<()> {
+    write!(w, "{}", CsiSequence::cursor_position(1, 1))?;
+    let erase = CsiSequence::erase_line(EraseLine::All);
+    for i in top..top + height {
+        // Note: Raw mode doesn't return the cursor to the start of the line.
+        write!(w, "{}{}\r\n", erase, lines.get(i).copied().unwrap_or_default())?;
+    }
+    let end = (top + height).min(lines.len());
+    write!(
+        w, "{}{}lines {}-{} of {} (j/k, space/b, q){}",
+        erase, CsiSequence::sgr().param(7), top + 1, end, lines.len(), CsiSequence::sgr_reset(),
+    )?;
+
+    w.flush()
+}
+
+/// Print `text`, paging it in a viewport when stdin and stdout are terminals and
+/// it's taller than the terminal, or else printing it plainly.
+pub(crate) fn page(text: &str) -> io::Result<()> {
+    let lines: Vec<&str> = text.lines().collect();
+    let rows = terminal::size().map_or(0, |(_, rows)| usize::from(rows));
+    let stdout = io::stdout();
+    if !stdout.is_terminal() || !io::stdin().is_terminal() || rows < 2 || lines.len() < rows {
+        let mut stdout = stdout.lock();
+        stdout.write_all(text.as_bytes())?;
+        return stdout.flush();
+    }
+
+    // The last row of the terminal holds the status line.
+    let height = rows - 1;
+    let last_top = lines.len() - height;
+    with_alt_screen(|| {
+        terminal::enable_raw_mode()?;
+        let _raw = RawMode;
+        let (mut stdin, mut stdout) = (io::stdin().lock(), stdout.lock());
+        let mut top = 0;
+        loop {
+            draw(&mut stdout, &lines, top, height)?;
+            top = match read_key(&mut stdin)? {
+                Key::Down => top + 1,
+                Key::Up => top.saturating_sub(1),
+                Key::PageDown => top + height,
+                Key::PageUp => top.saturating_sub(height),
+                Key::Quit => return Ok(()),
+                Key::Other => top,
+            }
+            .min(last_top);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_read_key() {
+        let mut stdin: &[u8] = b"\x1b[Bj\x1bk\x1b[A\x1b";
+        let keys: Vec<Key> = (0..6).map(|_| read_key(&mut stdin).unwrap()).collect();
+        assert_eq!(keys, vec![Key::Down, Key::Down, Key::Other, Key::Up, Key::Up, Key::Other]);
+        assert_eq!(read_key(&mut stdin).unwrap(), Key::Quit);
+    }
+}
@@ -0,0 +1,226 @@
+//! A state machine over the SGR escape sequences of a rendered line, which writes
+//! only the attributes that change between one run of text and the next, rather
+//! than each cell opening its style in full and resetting it.
+
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+use alloc::format;
+
+use crate::ansi::CsiSequence;
+use crate::style::{fixed_to_rgb, rgb_to_ansi16, rgb_to_fixed, ColorSupport};
+use crate::text::escape_segments;
+
+/// The attributes set by SGR escape sequences: the colors, as their parameters,
+/// the text styles 1 to 9, as a bitmask, and any parameters not understood.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct SgrState {
+    fg: Option<String>,
+    bg: Option<String>,
+    attrs: u16,
+    other: Vec<String>,
+}
+
+impl SgrState {
+
+    /// Apply the parameters of an SGR escape sequence, e.g. `1;31`.
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';');
+        while let Some(param) = params.next() {
+            match param.parse::<u16>() {
+                Ok(0) => *self = Self::default(),
+                Err(_) if param.is_empty() => *self = Self::default(),
+                Ok(n @ 1..=9) => self.attrs |= 1 << n,
+                Ok(22) => self.attrs &= !(1 << 1 | 1 << 2),
+                Ok(n @ 23..=29) if n != 26 => self.attrs &= !(1 << (n - 20)),
+                Ok(39) => self.fg = None,
+                Ok(49) => self.bg = None,
+                Ok(n @ (30..=37 | 90..=97)) => self.fg = Some(n.to_string()),
+                Ok(n @ (40..=47 | 100..=107)) => self.bg = Some(n.to_string()),
+                Ok(n @ (38 | 48)) => {
+                    let color = extended_color(n, &mut params);
+                    match n {
+                        38 => self.fg = Some(color),
+                        _ => self.bg = Some(color),
+                    }
+                },
+                _ => self.other.push(param.to_string()),
+            }
+        }
+    }
+
+    /// The parameters which set every attribute of this state.
+    fn params(&self) -> Vec<String> {
+        let mut params: Vec<String> = (1..=9)
+            .filter(|n| self.attrs & (1 << n) != 0)
+            .map(|n: u16| n.to_string())
+            .collect();
+        params.extend(self.fg.iter().cloned());
+        params.extend(self.bg.iter().cloned());
+        params.extend(self.other.iter().cloned());
+
+        params
+    }
+
+    /// Whether text of blanks looks the same in this state as in `other`, as only
+    /// the background and the underline, blink, reverse, and strikethrough styles
+    /// show on blanks.
+    fn same_blanks(&self, other: &SgrState) -> bool {
+        const BLANK_ATTRS: u16 = 1 << 4 | 1 << 5 | 1 << 6 | 1 << 7 | 1 << 9;
+        self.bg == other.bg
+            && self.attrs & BLANK_ATTRS == other.attrs & BLANK_ATTRS
+            && self.other == other.other
+    }
+
+    /// Append to `buf` a single escape sequence changing the terminal from this
+    /// state to `to`. An attribute can't be unset without a reset, so one which is
+    /// dropped resets the state and writes `to` in full.
+    fn transition(&self, to: &SgrState, buf: &mut String) {
+        let dropped = self.attrs & !to.attrs != 0
+            || (self.fg.is_some() && to.fg.is_none())
+            || (self.bg.is_some() && to.bg.is_none())
+            || !to.other.starts_with(&self.other);
+        let params = match dropped {
+            true => {
+                let mut params = to.params();
+                params.insert(0, "0".to_string());
+                params
+            },
+            false => {
+                let mut params: Vec<String> = (1..=9)
+                    .filter(|n| (to.attrs & !self.attrs) & (1 << n) != 0)
+                    .map(|n: u16| n.to_string())
+                    .collect();
+                params.extend(to.fg.iter().filter(|_| self.fg != to.fg).cloned());
+                params.extend(to.bg.iter().filter(|_| self.bg != to.bg).cloned());
+                params.extend(to.other[self.other.len()..].iter().cloned());
+                params
+            },
+        };
+        buf.push_str(&CsiSequence::sgr().param(params.join(";")).to_string());
+    }
+}
+
+/// Take the parameters of an extended color following 38 or 48, e.g. `5;208` or
+/// `2;255;128;0`, and return the color's parameters.
+fn extended_color<'a, I: Iterator<Item = &'a str>>(n: u16, params: &mut I) -> String {
+    let mut color = n.to_string();
+    let count = match params.next() {
+        Some("5") => { color.push_str(";5"); 1 },
+        Some("2") => { color.push_str(";2"); 3 },
+        Some(mode) => { color.push(';'); color.push_str(mode); 0 },
+        None => 0,
+    };
+    for param in params.take(count) {
+        color.push(';');
+        color.push_str(param);
+    }
+
+    color
+}
+
+/// Rewrite the SGR escape sequences of `line` so that each run of text is
+/// preceded by a single sequence holding only the attributes which changed since
+/// the last run, and the line ends with the attributes reset. Other escape
+/// sequences are kept in place.
+pub(crate) fn minimize(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut wanted = SgrState::default();
+    let mut written = SgrState::default();
+    for (segment, escape) in escape_segments(line) {
+        let sgr = segment.strip_prefix("\x1b[").and_then(|params| params.strip_suffix('m'));
+        match (escape, sgr) {
+            (true, Some(params)) => wanted.apply(params),
+            (true, None) => out.push_str(segment),
+            (false, _) => {
+                let blank = segment.chars().all(|c| c == ' ');
+                if wanted != written && !(blank && wanted.same_blanks(&written)) {
+                    written.transition(&wanted, &mut out);
+                    written = wanted.clone();
+                }
+                out.push_str(segment);
+            },
+        }
+    }
+    if written != SgrState::default() {
+        out.push_str(&CsiSequence::sgr_reset().to_string());
+    }
+
+    out
+}
+
+/// Rewrite the 256-color and RGB colors of the SGR escape sequences of `line` to
+/// the nearest colors available under `support`. Other escape sequences, and the
+/// text, are kept as they are.
+pub(crate) fn downgrade(line: &str, support: ColorSupport) -> String {
+    let mut out = String::with_capacity(line.len());
+    for (segment, escape) in escape_segments(line) {
+        let sgr = segment.strip_prefix("\x1b[").and_then(|params| params.strip_suffix('m'));
+        match (escape, sgr) {
+            (true, Some(params)) => {
+                let mut params = params.split(';');
+                let mut downgraded = Vec::new();
+                while let Some(param) = params.next() {
+                    match param.parse::<u16>() {
+                        Ok(n @ (38 | 48)) => downgraded.push(downgrade_color(n, &mut params, support)),
+                        _ => downgraded.push(param.to_string()),
+                    }
+                }
+                out.push_str(&CsiSequence::sgr().param(downgraded.join(";")).to_string());
+            },
+            _ => out.push_str(segment),
+        }
+    }
+
+    out
+}
+
+/// Take the parameters of an extended color following `n`, 38 or 48, and return
+/// the parameters of the nearest color available under `support`.
+fn downgrade_color<'a, I: Iterator<Item = &'a str>>(n: u16, params: &mut I, support: ColorSupport) -> String {
+    let color = extended_color(n, params);
+    let values: Vec<u8> = color.split(';').skip(2).filter_map(|v| v.parse().ok()).collect();
+    let rgb = match (color.split(';').nth(1), values.as_slice()) {
+        (Some("5"), [n]) if support < ColorSupport::Ansi256 => fixed_to_rgb(*n),
+        (Some("2"), [r, g, b]) if support == ColorSupport::Ansi256 => {
+            return format!("{};5;{}", n, rgb_to_fixed((*r, *g, *b)));
+        },
+        (Some("2"), [r, g, b]) if support < ColorSupport::Ansi256 => (*r, *g, *b),
+        _ => return color,
+    };
+    // Note: The basic colors are 30-37 and 90-97 for the foreground, and 40-47
+    // and 100-107 for the background.
+    let idx = rgb_to_ansi16(rgb) as u16;
+    let base = n - 8;
+    match idx {
+        0..=7 => (base + idx).to_string(),
+        _ => (base + 60 + idx - 8).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use crate::style::{stylize, Bg, Fg, Sgr, StyleOpt};
+
+    #[test]
+    fn test_minimize_adjacent_cells() {
+        let red = StyleOpt::new().fg(Fg::Red);
+        let line = format!("{}  {}  {}", stylize("a", &red), stylize("b", &red), stylize("c", &StyleOpt::new().bg(Bg::Blue)));
+        assert_eq!(minimize(&line), "\x1b[1;31ma  b  \x1b[0;1;44mc\x1b[0m");
+
+        let bold = StyleOpt::new().sgr(Sgr::Bold);
+        assert_eq!(minimize(&stylize("x", &bold)), "\x1b[1mx\x1b[0m");
+        assert_eq!(minimize("plain"), "plain");
+    }
+
+    #[test]
+    fn test_downgrade_colors() {
+        let orange = StyleOpt::new().fg(Fg::Rgb(255, 135, 0)).bg(Bg::Fixed(16));
+        let line = format!("{} \x1b]8;;x\x1b\\", stylize("a", &orange));
+        assert_eq!(downgrade(&line, ColorSupport::TrueColor), line);
+        assert_eq!(downgrade(&line, ColorSupport::Ansi256), "\x1b[1;38;5;208m\x1b[1;48;5;16ma\x1b[1;0m \x1b]8;;x\x1b\\");
+        assert_eq!(downgrade(&line, ColorSupport::Ansi16), "\x1b[1;33m\x1b[1;40ma\x1b[1;0m \x1b]8;;x\x1b\\");
+    }
+}
@@ -0,0 +1,145 @@
+//! The sink module provides the [`Sink`] trait, the destination a GridPrinter
+//! renders its lines into. Sinks allow a grid to be rendered without stdout, e.g.
+//! into a `String` or a per-line callback on `wasm32-unknown-unknown`, where the
+//! lines can be handed to a web terminal such as xterm.js.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::sink::LineSink;
+//!
+//! let grid = vec![
+//!     vec!["a", "b"],
+//!     vec!["c", "d"],
+//! ];
+//! let printer = GridPrinter::new(2, 2);
+//!
+//! let mut lines = Vec::new();
+//! printer.render_into(&mut LineSink(|line: &str| lines.push(line.to_string())), &grid).unwrap();
+//! assert_eq!(lines, vec!["a  b  ", "c  d  "]);
+//! ```
+
+use core::fmt;
+use core::convert::Infallible;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::io;
+
+/// A destination which receives a rendered grid one line at a time.
+pub trait Sink {
+    /// The error produced when a line cannot be written.
+    type Error;
+
+    /// Write a single rendered line. `line` does not include a line terminator.
+    fn write_line(&mut self, line: &str) -> Result<(), Self::Error>;
+
+    /// Write a single rendered line terminated by `ending`, the GridPrinter's
+    /// [`LineEnding`]. Sinks which write text override this to write `ending` in
+    /// place of `\n`; by default `ending` is ignored.
+    fn write_line_ending(&mut self, line: &str, ending: &str) -> Result<(), Self::Error> {
+        let _ = ending;
+        self.write_line(line)
+    }
+}
+
+/// The terminator written after each line of a grid.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`, e.g. for Windows files or SMTP bodies.
+    CrLf,
+    /// Any other terminator.
+    Custom(String),
+}
+
+impl LineEnding {
+
+    /// The terminator as text.
+    pub fn as_str(&self) -> &str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Custom(ending) => ending,
+        }
+    }
+}
+
+/// Append each line to the `String`, terminated by `\n` unless another line ending
+/// is configured.
+impl Sink for String {
+    type Error = Infallible;
+
+    fn write_line(&mut self, line: &str) -> Result<(), Self::Error> {
+        self.write_line_ending(line, "\n")
+    }
+
+    fn write_line_ending(&mut self, line: &str, ending: &str) -> Result<(), Self::Error> {
+        self.push_str(line);
+        self.push_str(ending);
+
+        Ok(())
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for &mut S {
+    type Error = S::Error;
+
+    fn write_line(&mut self, line: &str) -> Result<(), Self::Error> {
+        (**self).write_line(line)
+    }
+
+    fn write_line_ending(&mut self, line: &str, ending: &str) -> Result<(), Self::Error> {
+        (**self).write_line_ending(line, ending)
+    }
+}
+
+/// A Sink which hands each line to a callback.
+pub struct LineSink<F>(pub F);
+
+impl<F: FnMut(&str)> Sink for LineSink<F> {
+    type Error = Infallible;
+
+    fn write_line(&mut self, line: &str) -> Result<(), Self::Error> {
+        (self.0)(line);
+
+        Ok(())
+    }
+}
+
+/// A Sink which writes each line, terminated by `\n` unless another line ending is
+/// configured, to a [`core::fmt::Write`].
+pub struct FmtSink<W>(pub W);
+
+impl<W: fmt::Write> Sink for FmtSink<W> {
+    type Error = fmt::Error;
+
+    fn write_line(&mut self, line: &str) -> Result<(), Self::Error> {
+        self.write_line_ending(line, "\n")
+    }
+
+    fn write_line_ending(&mut self, line: &str, ending: &str) -> Result<(), Self::Error> {
+        self.0.write_str(line)?;
+        self.0.write_str(ending)
+    }
+}
+
+/// A Sink which writes each line, terminated by `\n` unless another line ending is
+/// configured, to a [`std::io::Write`].
+#[cfg(feature = "std")]
+pub struct IoSink<W>(pub W);
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Sink for IoSink<W> {
+    type Error = io::Error;
+
+    fn write_line(&mut self, line: &str) -> Result<(), Self::Error> {
+        self.write_line_ending(line, "\n")
+    }
+
+    fn write_line_ending(&mut self, line: &str, ending: &str) -> Result<(), Self::Error> {
+        write!(self.0, "{}{}", line, ending)
+    }
+}
@@ -0,0 +1,417 @@
+//! The sort module provides [`ColType`], the kinds of values a column may hold,
+//! which decide how the rows are ordered when sorting by that column, as set with
+//! [`col_type`](crate::GridPrinterBuilder::col_type), and [`Collation`], the order
+//! of the text of cells, as set with [`collation`](crate::GridPrinterBuilder::collation).
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::net::{IpAddr, SocketAddr};
+
+/// The kinds of values a column may hold. Cells which are not of the column's
+/// kind sort after those which are, in lexical order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColType {
+    /// IPv4 or IPv6 addresses, or `host:port` pairs, ordered numerically by
+    /// address, then by port. IPv4 addresses sort before IPv6 addresses, and both
+    /// before host names. With `align_octets`, the octets of IPv4 addresses are
+    /// right-aligned in three columns, e.g. ` 10.  0.  0.  1`.
+    Ip { align_octets: bool },
+    /// Semantic versions, e.g. `v1.2.10-rc.1`, ordered by their numeric parts,
+    /// so that `1.2.10` sorts after `1.2.9`. A pre-release sorts before its
+    /// release, and build metadata is ignored. A missing minor or patch number is
+    /// taken as `0`.
+    SemVer,
+}
+
+/// The sort key of a cell of a [`ColType::Ip`] column.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Endpoint<'a> {
+    Addr(IpAddr, Option<u16>),
+    Host(&'a str, Option<u16>),
+    Text(&'a str),
+}
+
+impl<'a> Endpoint<'a> {
+
+    /// Parse an address, with or without a port, or a `host:port` pair.
+    fn parse(cell: &'a str) -> Self {
+        if let Ok(addr) = cell.parse::<IpAddr>() {
+            return Endpoint::Addr(addr, None);
+        }
+        if let Ok(addr) = cell.parse::<SocketAddr>() {
+            return Endpoint::Addr(addr.ip(), Some(addr.port()));
+        }
+        match cell.rsplit_once(':').map(|(host, port)| (host, port.parse::<u16>())) {
+            Some((host, Ok(port))) if !host.is_empty() && !host.contains(':') => Endpoint::Host(host, Some(port)),
+            _ if !cell.is_empty() && !cell.contains(char::is_whitespace) && cell.contains('.') => Endpoint::Host(cell, None),
+            _ => Endpoint::Text(cell),
+        }
+    }
+}
+
+/// An identifier of the pre-release of a [`ColType::SemVer`] version. Numeric
+/// identifiers sort before alphanumeric ones.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum PreRelease<'a> {
+    Numeric(u64),
+    Alpha(&'a str),
+}
+
+/// The sort key of a cell of a [`ColType::SemVer`] column.
+#[derive(Debug, PartialEq, Eq)]
+struct Version<'a> {
+    numbers: [u64; 3],
+    pre: Vec<PreRelease<'a>>,
+}
+
+impl<'a> Version<'a> {
+
+    /// Parse a version, with an optional leading `v`.
+    fn parse(cell: &'a str) -> Option<Self> {
+        let cell = cell.strip_prefix(['v', 'V']).unwrap_or(cell);
+        let cell = cell.split('+').next()?;
+        let (core, pre) = match cell.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (cell, None),
+        };
+        let mut numbers = [0; 3];
+        for (idx, part) in core.split('.').enumerate() {
+            let number = numbers.get_mut(idx)?;
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            *number = part.parse().ok()?;
+        }
+        let pre = pre.map_or_else(Vec::new, |pre| pre.split('.')
+            .map(|id| match id.parse() {
+                Ok(n) if id.bytes().all(|b| b.is_ascii_digit()) => PreRelease::Numeric(n),
+                _ => PreRelease::Alpha(id),
+            })
+            .collect());
+
+        Some(Self { numbers, pre })
+    }
+}
+
+impl Ord for Version<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Note: A release sorts after its pre-releases, which have identifiers.
+        self.numbers.cmp(&other.numbers)
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// How the text of cells is ordered when sorting. Numbers, and the values of
+/// columns with a [`ColType`], are ordered by their value regardless.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Collation {
+    /// By Unicode code point, so that `Zoe` sorts before `adam`, and `Émile` after
+    /// `zed`.
+    #[default]
+    Binary,
+    /// By Unicode code point, ignoring case, so that `adam` sorts before `Zoe`.
+    CaseInsensitive,
+    /// By the root order of the Unicode Collation Algorithm, which orders letters
+    /// with accents next to their base letters, so that `Émile` sorts between
+    /// `Eli` and `Emma`. Enabled by the `collation` feature.
+    #[cfg(feature = "collation")]
+    Unicode,
+}
+
+impl Collation {
+
+    /// Prepare to compare text in this collation, for the duration of a sort.
+    pub(crate) fn collator(&self) -> Collator {
+        Collator {
+            collation: *self,
+            #[cfg(feature = "collation")]
+            unicode: match self {
+                Collation::Unicode => {
+                    icu_collator::Collator::try_new(&Default::default(), icu_collator::CollatorOptions::new()).ok()
+                },
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Compares text in a [`Collation`].
+pub(crate) struct Collator {
+    collation: Collation,
+    #[cfg(feature = "collation")]
+    unicode: Option<icu_collator::Collator>,
+}
+
+impl Collator {
+
+    /// Compare two strings in the collation alone, which may deem different strings
+    /// equal.
+    fn collate(&self, a: &str, b: &str) -> Ordering {
+        match self.collation {
+            Collation::Binary => a.cmp(b),
+            Collation::CaseInsensitive => a.chars().flat_map(char::to_lowercase)
+                .cmp(b.chars().flat_map(char::to_lowercase)),
+            #[cfg(feature = "collation")]
+            Collation::Unicode => self.unicode.as_ref().map_or_else(|| a.cmp(b), |unicode| unicode.compare(a, b)),
+        }
+    }
+
+    /// Compare two strings, breaking ties between strings the collation deems
+    /// equal by code point, so that the order is total.
+    pub(crate) fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.collate(a, b).then_with(|| a.cmp(b))
+    }
+}
+
+/// Compare two cells, ordering numbers by value before all other text, which is
+/// ordered in the collation. Numbers of equal value, such as `1` and `1.0`, are
+/// ordered by code point, so that the order is total.
+pub(crate) fn compare_default(a: &str, b: &str, collator: &Collator) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a_num), Ok(b_num)) => a_num.total_cmp(&b_num).then_with(|| a.cmp(b)),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => collator.compare(a, b),
+    }
+}
+
+/// Compare two cells in natural order, comparing runs of ASCII digits by their
+/// value, and the text between them in the collation. A run of digits sorts
+/// before other text, which a collation may order among the digits.
+pub(crate) fn compare_natural(a: &str, b: &str, collator: &Collator) -> Ordering {
+    let is_digit = |c: char| c.is_ascii_digit();
+    let (mut a_rest, mut b_rest) = (a, b);
+    loop {
+        let (a_digit, b_digit) = match (a_rest.chars().next(), b_rest.chars().next()) {
+            (None, None) => return collator.compare(a, b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) => (is_digit(a_char), is_digit(b_char)),
+        };
+        let a_end = a_rest.find(|c: char| is_digit(c) != a_digit).unwrap_or(a_rest.len());
+        let b_end = b_rest.find(|c: char| is_digit(c) != b_digit).unwrap_or(b_rest.len());
+        let (a_run, b_run) = (&a_rest[..a_end], &b_rest[..b_end]);
+        let ordering = match (a_digit, b_digit) {
+            (true, true) => {
+                let (a_run, b_run) = (a_run.trim_start_matches('0'), b_run.trim_start_matches('0'));
+                // Note: Without leading zeros, the longer run of digits is the larger number.
+                a_run.len().cmp(&b_run.len()).then_with(|| a_run.cmp(b_run))
+            },
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => collator.collate(a_run, b_run),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        a_rest = &a_rest[a_end..];
+        b_rest = &b_rest[b_end..];
+    }
+}
+
+impl ColType {
+
+    /// Compare two cells of a column of this type, in ascending order.
+    ///
+    /// ```rust
+    /// use core::cmp::Ordering;
+    /// use grid_printer::sort::ColType;
+    ///
+    /// let ip = ColType::Ip { align_octets: false };
+    /// assert_eq!(ip.compare("10.0.0.9", "10.0.0.10"), Ordering::Less);
+    /// assert_eq!(ip.compare("[::1]:443", "127.0.0.1:80"), Ordering::Greater);
+    /// assert_eq!(ip.compare("example.com:8080", "example.com:443"), Ordering::Greater);
+    ///
+    /// assert_eq!(ColType::SemVer.compare("1.2.10", "1.2.9"), Ordering::Greater);
+    /// assert_eq!(ColType::SemVer.compare("v2.0.0-rc.1", "2.0.0"), Ordering::Less);
+    /// ```
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            ColType::Ip { .. } => {
+                let (a, b) = (unaligned(a.trim()), unaligned(b.trim()));
+                Endpoint::parse(&a).cmp(&Endpoint::parse(&b))
+            },
+            ColType::SemVer => {
+                let (a, b) = (a.trim(), b.trim());
+                match (Version::parse(a), Version::parse(b)) {
+                    (Some(va), Some(vb)) => va.cmp(&vb),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => a.cmp(b),
+                }
+            },
+        }
+    }
+
+    /// Rewrite a data cell for display, or return `None` to leave it unchanged.
+    pub(crate) fn align(&self, cell: &str) -> Option<String> {
+        match self {
+            ColType::Ip { align_octets: true } => align_octets(cell.trim()),
+            ColType::Ip { align_octets: false } | ColType::SemVer => None,
+        }
+    }
+}
+
+/// Remove the padding of aligned octets.
+fn unaligned(cell: &str) -> Cow<'_, str> {
+    match cell.contains(". ") {
+        true => Cow::Owned(cell.replace(' ', "")),
+        false => Cow::Borrowed(cell),
+    }
+}
+
+/// Right-align the octets of an IPv4 address, keeping any port.
+fn align_octets(cell: &str) -> Option<String> {
+    let (addr, port) = match cell.parse::<SocketAddr>() {
+        Ok(SocketAddr::V4(addr)) => (*addr.ip(), Some(addr.port())),
+        Ok(SocketAddr::V6(_)) => return None,
+        Err(_) => (cell.parse().ok()?, None),
+    };
+    let [a, b, c, d] = addr.octets();
+    let aligned = format!("{:>3}.{:>3}.{:>3}.{:>3}", a, b, c, d);
+
+    match port {
+        None => Some(aligned),
+        Some(port) => Some(format!("{}:{}", aligned, port)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_ip_order() {
+        let ip = ColType::Ip { align_octets: true };
+        let mut cells = vec!["db.local", "n/a", "::1", "10.0.0.10", "10.0.0.9:22", "10.0.0.9", "db.local:5432"];
+        cells.sort_by(|a, b| ip.compare(a, b));
+        assert_eq!(cells, vec!["10.0.0.9", "10.0.0.9:22", "10.0.0.10", "::1", "db.local", "db.local:5432", "n/a"]);
+
+        let aligned = ip.align("10.0.0.9:22").unwrap();
+        assert_eq!(aligned, " 10.  0.  0.  9:22");
+        assert_eq!(ip.compare(&aligned, "10.0.0.10"), Ordering::Less);
+        assert_eq!(ip.align("::1"), None);
+    }
+
+    #[test]
+    fn test_semver_order() {
+        let mut cells = vec!["1.10.0", "unreleased", "1.2.10", "1.2.0-beta.11", "1.2", "1.2.0-beta.2", "1.2.0-alpha", "v1.2.9+build.5"];
+        cells.sort_by(|a, b| ColType::SemVer.compare(a, b));
+        assert_eq!(cells, vec!["1.2.0-alpha", "1.2.0-beta.2", "1.2.0-beta.11", "1.2", "v1.2.9+build.5", "1.2.10", "1.10.0", "unreleased"]);
+        assert_eq!(Version::parse("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn test_natural_order() {
+        let mut cells = vec!["file10.txt", "file2.txt", "file", "file02.txt", "file1b", "file1a", "File3"];
+        let collator = Collation::Binary.collator();
+        cells.sort_by(|a, b| compare_natural(a, b, &collator));
+        assert_eq!(cells, vec!["File3", "file", "file1a", "file1b", "file02.txt", "file2.txt", "file10.txt"]);
+        assert_eq!(compare_natural("v18446744073709551616", "v9", &collator), Ordering::Greater);
+
+        let collator = Collation::CaseInsensitive.collator();
+        cells.sort_by(|a, b| compare_natural(a, b, &collator));
+        assert_eq!(cells, vec!["file", "file1a", "file1b", "file02.txt", "file2.txt", "File3", "file10.txt"]);
+    }
+
+    /// Assert that `compare` is a total order over `cells`.
+    fn assert_total_order<F: Fn(&str, &str) -> Ordering>(cells: &[String], compare: F) {
+        for a in cells {
+            assert_eq!(compare(a, a), Ordering::Equal, "{:?}", a);
+            for b in cells {
+                assert_eq!(compare(a, b), compare(b, a).reverse(), "{:?} {:?}", a, b);
+                for c in cells {
+                    if compare(a, b) != Ordering::Greater && compare(b, c) != Ordering::Greater {
+                        assert_ne!(compare(a, c), Ordering::Greater, "{:?} {:?} {:?}", a, b, c);
+                    }
+                }
+            }
+        }
+    }
+
+    fn cell() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "[0-9]{1,3}",
+            "[0-9]{1,2}[a-c]",
+            "-?[0-9]\\.[0-9]",
+            "[a-cA-C ]{0,3}",
+            Just("NaN".to_string()),
+            Just("-inf".to_string()),
+            Just("1e3".to_string()),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_default_total_order(cells in prop::collection::vec(cell(), 1..12)) {
+            let collator = Collation::Binary.collator();
+            assert_total_order(&cells, |a, b| compare_default(a, b, &collator));
+        }
+    }
+
+    #[test]
+    fn test_default_order() {
+        let mut cells = vec!["10", "5a", "NaN", "9", "", "-1.5", "1.0", "1"];
+        let collator = Collation::Binary.collator();
+        cells.sort_by(|a, b| compare_default(a, b, &collator));
+        assert_eq!(cells, vec!["-1.5", "1", "1.0", "9", "10", "NaN", "", "5a"]);
+    }
+
+    #[cfg(feature = "collation")]
+    fn mixed_script_cell() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "[0-9]{1,2}",
+            "[a-b\u{0660}-\u{0664}\u{0966}-\u{0968}é]{1,2}",
+            "[a-b0-9\u{0660}-\u{0664}]{1,4}",
+        ]
+    }
+
+    #[cfg(feature = "collation")]
+    proptest! {
+        #[test]
+        fn test_unicode_total_order(cells in prop::collection::vec(mixed_script_cell(), 1..12)) {
+            let collator = Collation::Unicode.collator();
+            assert_total_order(&cells, |a, b| compare_default(a, b, &collator));
+            assert_total_order(&cells, |a, b| compare_natural(a, b, &collator));
+        }
+    }
+
+    #[test]
+    fn test_collation() {
+        let mut names = vec!["Émile", "eli", "Zoe", "adam", "Adam"];
+        let collator = Collation::CaseInsensitive.collator();
+        names.sort_by(|a, b| compare_default(a, b, &collator));
+        assert_eq!(names, vec!["Adam", "adam", "eli", "Zoe", "Émile"]);
+
+        #[cfg(feature = "collation")]
+        {
+            let collator = Collation::Unicode.collator();
+            names.sort_by(|a, b| compare_default(a, b, &collator));
+            assert_eq!(names, vec!["adam", "Adam", "eli", "Émile", "Zoe"]);
+
+            let mut cells = vec!["\u{663}", "10", "4", "a\u{663}", "a10", "a4"];
+            cells.sort_by(|a, b| compare_natural(a, b, &collator));
+            assert_eq!(cells, vec!["4", "10", "\u{663}", "a4", "a10", "a\u{663}"]);
+        }
+    }
+}
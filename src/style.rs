@@ -38,9 +38,21 @@
 //! </div>
 
 
+use core::fmt;
+use core::fmt::Display;
+use core::str::FromStr;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::collections::BTreeMap;
+
+use crate::GridPrinterErr;
+use crate::ansi::CsiSequence;
+use crate::text::escape_segments;
+
 /// A struct providing optional customization of the foreground color, background
 /// color, and text style of a GridPrinter column.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StyleOpt {
     pub fg: Option<Fg>,
     pub bg: Option<Bg>, 
@@ -68,42 +80,494 @@ impl StyleOpt {
     pub fn sgr(self, sgr: Sgr) -> Self {
         Self { sgr: Some(sgr), ..self }
     }
+
+    /// Replace any 256-color or RGB colors with the nearest color available under
+    /// `support`. A GridPrinter downgrades the styles it prints to its
+    /// [`color_support`](crate::GridPrinterBuilder::color_support).
+    ///
+    /// ```rust
+    /// use grid_printer::style::{Bg, ColorSupport, Fg, StyleOpt};
+    ///
+    /// let orange = StyleOpt::new().fg(Fg::Rgb(255, 135, 0)).bg(Bg::Rgb(0, 0, 0));
+    /// assert_eq!(orange.downgrade(ColorSupport::Ansi256).fg, Some(Fg::Fixed(208)));
+    /// assert_eq!(orange.downgrade(ColorSupport::Ansi16).fg, Some(Fg::Yellow));
+    /// assert_eq!(orange.downgrade(ColorSupport::Ansi16).bg, Some(Bg::Black));
+    /// ```
+    pub fn downgrade(&self, support: ColorSupport) -> Self {
+        Self {
+            fg: self.fg.as_ref().map(|fg| fg.downgrade(support)),
+            bg: self.bg.as_ref().map(|bg| bg.downgrade(support)),
+            sgr: self.sgr.clone(),
+        }
+    }
+
+    /// Whether no option is set.
+    pub fn is_empty(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.sgr.is_none()
+    }
+
+    /// Append the escape codes of the set options to `buf`.
+    pub(crate) fn push_codes(&self, buf: &mut String) {
+        if let Some(fg) = self.fg.as_ref() {
+            buf.push_str(&fg.escape_code_for(ColorSupport::TrueColor));
+        }
+        if let Some(bg) = self.bg.as_ref() {
+            buf.push_str(&bg.escape_code_for(ColorSupport::TrueColor));
+        }
+        if let Some(sgr) = self.sgr.as_ref() {
+            buf.push_str(sgr.escape_code());
+        }
+    }
+
+    /// Append to `buf` the escape codes which change the terminal from this style to
+    /// `to`. When `to` keeps every option this style sets, only the options which
+    /// differ are written; otherwise the style is reset and `to` written in full.
+    /// A changed text style also needs the reset, as text styles add to each other
+    /// rather than replace each other.
+    ///
+    /// ```rust
+    /// use grid_printer::style::{Bg, Fg, StyleOpt};
+    ///
+    /// let row = StyleOpt::new().bg(Bg::Blue);
+    /// let cell = row.merge(&StyleOpt::new().fg(Fg::Red));
+    /// let mut buf = String::new();
+    /// row.transition(&cell, &mut buf);
+    /// assert_eq!(buf, Fg::Red.escape_code());
+    /// ```
+    pub fn transition(&self, to: &StyleOpt, buf: &mut String) {
+        let keeps = |set: bool, kept: bool| !set || kept;
+        let superset = keeps(self.fg.is_some(), to.fg.is_some())
+            && keeps(self.bg.is_some(), to.bg.is_some())
+            && keeps(self.sgr.is_some(), self.sgr == to.sgr);
+        if !superset {
+            buf.push_str(Fg::Reset.escape_code());
+            return to.push_codes(buf);
+        }
+        let changed = StyleOpt {
+            fg: to.fg.clone().filter(|fg| self.fg.as_ref() != Some(fg)),
+            bg: to.bg.clone().filter(|bg| self.bg.as_ref() != Some(bg)),
+            sgr: to.sgr.clone().filter(|sgr| self.sgr.as_ref() != Some(sgr)),
+        };
+        changed.push_codes(buf);
+    }
+
+    /// Combine this StyleOpt with `other`, whose set options take precedence.
+    pub fn merge(&self, other: &StyleOpt) -> Self {
+        Self {
+            fg: other.fg.clone().or_else(|| self.fg.clone()),
+            bg: other.bg.clone().or_else(|| self.bg.clone()),
+            sgr: other.sgr.clone().or_else(|| self.sgr.clone()),
+        }
+    }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for StyleOpt {
     fn default() -> StyleOpt {
         Self { fg: None, bg: None, sgr: None }
     }
 }
 
+/// A map of named styles, e.g. `"warning"`, which columns and highlighted rows may
+/// reference by name, so an application can theme all of its grids in one place.
+///
+/// ```rust
+/// use grid_printer::GridPrinter;
+/// use grid_printer::style::{Fg, Palette, StyleOpt};
+///
+/// let dark = Palette::new()
+///     .style("muted", StyleOpt::new().fg(Fg::BrightBlack))
+///     .style("warning", StyleOpt::new().fg(Fg::BrightYellow));
+///
+/// let printer = GridPrinter::builder(2, 2)
+///     .palette(dark)
+///     .col_style_named(0, "muted").unwrap()
+///     .highlight_rows_named(&[1], "warning")
+///     .build();
+/// printer.print(&vec![vec!["id", "status"], vec!["7", "degraded"]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    styles: BTreeMap<String, StyleOpt>,
+}
+
+impl Palette {
+
+    /// Create a new, empty Palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the style `name`, replacing any style of the same name.
+    pub fn style(mut self, name: &str, style_opt: StyleOpt) -> Self {
+        self.set_style(name, style_opt);
+
+        self
+    }
+
+    /// Add the style `name` without consuming the Palette.
+    pub fn set_style(&mut self, name: &str, style_opt: StyleOpt) -> &mut Self {
+        self.styles.insert(name.to_string(), style_opt);
+
+        self
+    }
+
+    /// Add every style of `other`, replacing any styles of the same names.
+    pub fn merge(&mut self, other: Palette) -> &mut Self {
+        self.styles.extend(other.styles);
+
+        self
+    }
+
+    /// The style `name`, if the Palette has one.
+    pub fn get(&self, name: &str) -> Option<&StyleOpt> {
+        self.styles.get(name)
+    }
+
+    /// A Palette of styles readable on a terminal with the given `background`,
+    /// named `header`, `zebra` (a row stripe background), `muted`, `success`,
+    /// `warning`, and `error`.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::style::{Background, Palette};
+    ///
+    /// let background = Background::detect().unwrap_or_default();
+    /// let printer = GridPrinter::builder(3, 2)
+    ///     .header(true)
+    ///     .palette(Palette::theme(background))
+    ///     .highlight_rows_named(&[0], "header")
+    ///     .highlight_rows_named(&[2], "zebra")
+    ///     .fill_background(true)
+    ///     .build();
+    /// printer.print(&vec![vec!["name", "size"], vec!["a.txt", "1K"], vec!["b.txt", "2K"]]);
+    /// ```
+    pub fn theme(background: Background) -> Self {
+        let palette = Self::new().style("muted", StyleOpt::new().fg(Fg::BrightBlack));
+        match background {
+            Background::Dark => palette
+                .style("header", StyleOpt::new().fg(Fg::BrightWhite).sgr(Sgr::Bold))
+                .style("zebra", StyleOpt::new().bg(Bg::Fixed(236)))
+                .style("success", StyleOpt::new().fg(Fg::BrightGreen))
+                .style("warning", StyleOpt::new().fg(Fg::BrightYellow))
+                .style("error", StyleOpt::new().fg(Fg::BrightRed)),
+            Background::Light => palette
+                .style("header", StyleOpt::new().fg(Fg::Black).sgr(Sgr::Bold))
+                .style("zebra", StyleOpt::new().bg(Bg::Fixed(254)))
+                .style("success", StyleOpt::new().fg(Fg::Green))
+                .style("warning", StyleOpt::new().fg(Fg::Rgb(175, 95, 0)))
+                .style("error", StyleOpt::new().fg(Fg::Red)),
+        }
+    }
+}
+
+/// An enumeration of terminal background brightness, for choosing a readable
+/// [`Palette::theme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Background {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Background {
+
+    /// Detect the terminal background from the `COLORFGBG` environment variable,
+    /// e.g. `15;0`, set by rxvt, Konsole, and others. Returns `None` when it is unset
+    /// or unrecognized.
+    ///
+    /// Querying the terminal directly with OSC 11 needs the terminal in raw mode,
+    /// which is left to applications which already manage it; see
+    /// [`from_osc11`](Background::from_osc11).
+    #[cfg(feature = "std")]
+    pub fn detect() -> Option<Self> {
+        let colorfgbg = std::env::var("COLORFGBG").ok()?;
+        let bg: u8 = colorfgbg.rsplit(';').next()?.parse().ok()?;
+        match bg {
+            0..=6 | 8 => Some(Self::Dark),
+            _ => Some(Self::Light),
+        }
+    }
+
+    /// Parse a terminal's reply to the OSC 11 background color query
+    /// (`\x1b]11;?\x07`), e.g. `\x1b]11;rgb:ffff/ffff/ffff\x07`.
+    ///
+    /// ```rust
+    /// use grid_printer::style::Background;
+    ///
+    /// assert_eq!(Background::from_osc11("\x1b]11;rgb:1e1e/1e1e/1e1e\x07"), Some(Background::Dark));
+    /// assert_eq!(Background::from_osc11("\x1b]11;rgb:ffff/fafa/f0f0\x1b\\"), Some(Background::Light));
+    /// assert_eq!(Background::from_osc11("\x1b]11;rgb:fffffff/0/0\x07"), None);
+    /// ```
+    pub fn from_osc11(reply: &str) -> Option<Self> {
+        let rgb = reply.split("rgb:").nth(1)?;
+        let mut channels = rgb.split('/').map(|channel| {
+            let hex: String = channel.chars().take_while(char::is_ascii_hexdigit).collect();
+            // Note: Channels have one to four hex digits, which keeps the scaling in range.
+            if !(1..=4).contains(&hex.len()) {
+                return None;
+            }
+            let max = 16u32.pow(hex.len() as u32) - 1;
+            Some(u32::from_str_radix(&hex, 16).ok()? * 255 / max)
+        });
+        let (r, g, b) = (channels.next()??, channels.next()??, channels.next()??);
+        // Perceived luminance, on the 0-255 scale.
+        match (299 * r + 587 * g + 114 * b) / 1000 {
+            0..=127 => Some(Self::Dark),
+            _ => Some(Self::Light),
+        }
+    }
+}
+
 // pub fn reset() -> &'static str {
 //     "\x1b[1;0m"
 // }
 
 
-/// A function which will print a given &str `s` in accordance to the StylOpt `opt`.
-pub fn stylize(s: &str, opt: &StyleOpt) -> String {
-    format!("{fg}{bg}{sgr}{text}{reset}",
-        fg = match opt.fg.as_ref() {
-            None => "",
-            Some(fg) => fg.escape_code(),
-        },
-        bg = match opt.bg.as_ref() {
-            None => "",
-            Some(bg) => bg.escape_code(),
+/// An enumeration of when styles are applied to the printed grid.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorMode {
+    /// Always apply styles.
+    #[default]
+    Always,
+    /// Never apply styles, e.g. when writing to a file.
+    Never,
+    /// Apply styles only when stdout is a terminal. Without the `std` feature this
+    /// behaves like `Always`.
+    Auto,
+}
+
+impl ColorMode {
+
+    /// Whether styles should be applied under this ColorMode.
+    pub fn enabled(&self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            #[cfg(feature = "std")]
+            Self::Auto => std::io::IsTerminal::is_terminal(&std::io::stdout()),
+            #[cfg(not(feature = "std"))]
+            Self::Auto => true,
+        }
+    }
+}
+
+/// An enumeration of the color palettes a terminal may support, from fewest to most
+/// colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorSupport {
+    /// No colors, e.g. a dumb terminal.
+    None,
+    /// The 16 basic ANSI colors.
+    Ansi16,
+    /// The 256-color xterm palette.
+    Ansi256,
+    /// 24-bit RGB colors.
+    #[default]
+    TrueColor,
+}
+
+impl ColorSupport {
+
+    /// Detect the color support of the terminal from the `NO_COLOR`, `COLORTERM`,
+    /// and `TERM` environment variables.
+    #[cfg(feature = "std")]
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).unwrap_or_default();
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Self::None;
+        }
+        if matches!(var("COLORTERM").as_str(), "truecolor" | "24bit") {
+            return Self::TrueColor;
+        }
+        match var("TERM").as_str() {
+            "" | "dumb" => Self::None,
+            term if term.contains("256color") => Self::Ansi256,
+            _ => Self::Ansi16,
+        }
+    }
+}
+
+/// The RGB values of the 16 basic ANSI colors, as drawn by xterm.
+const ANSI16_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+    (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+    (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// The levels of each channel in the 6x6x6 color cube of the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The RGB values of entry `n` of the 256-color palette.
+pub(crate) fn fixed_to_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => ANSI16_RGB[n as usize],
+        16..=231 => {
+            let n = (n - 16) as usize;
+            (CUBE_LEVELS[n / 36], CUBE_LEVELS[n / 6 % 6], CUBE_LEVELS[n % 6])
         },
-        sgr = match opt.sgr.as_ref() {
-            None => "",
-            Some(sgr) => sgr.escape_code(),
+        _ => {
+            let level = 8 + (n - 232) * 10;
+            (level, level, level)
         },
-        text = s,
-        // Note: Using Fg::Reset vs. Bg::Reset makes no difference 
-        reset = Fg::Reset.escape_code(),
-    )
+    }
+}
+
+/// The entry of the 256-color palette, from the color cube or the gray ramp,
+/// nearest to `rgb`.
+pub(crate) fn rgb_to_fixed(rgb: (u8, u8, u8)) -> u8 {
+    let level = |v: u8| CUBE_LEVELS.iter()
+        .enumerate()
+        .min_by_key(|(_, level)| (i32::from(**level) - i32::from(v)).abs())
+        .map_or(0, |(i, _)| i as u8);
+    let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+
+    let mean = (u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3;
+    let gray = 232 + (mean.saturating_sub(3) / 10).min(23) as u8;
+
+    match distance(fixed_to_rgb(gray), rgb) < distance(fixed_to_rgb(cube), rgb) {
+        true => gray,
+        false => cube,
+    }
+}
+
+/// The index of the basic ANSI color nearest to `rgb`.
+pub(crate) fn rgb_to_ansi16(rgb: (u8, u8, u8)) -> usize {
+    (0..ANSI16_RGB.len())
+        .min_by_key(|i| distance(ANSI16_RGB[*i], rgb))
+        .unwrap_or(0)
+}
+
+/// The squared distance between two RGB colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Parse an RGB color from its `#rrggbb` hex code.
+fn parse_hex(s: &str) -> Option<(u8, u8, u8)> {
+    let hex = s.strip_prefix('#').filter(|hex| hex.len() == 6 && hex.is_ascii())?;
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// A function which will print a given &str `s` in accordance to the StylOpt `opt`.
+pub fn stylize(s: &str, opt: &StyleOpt) -> String {
+    let mut buf = String::new();
+    stylize_into(&mut buf, s, opt);
+
+    buf
+}
+
+/// A value displayed in accordance to a StyleOpt, created by [`styled`]. Unlike
+/// [`stylize`], the value is written straight to the formatter, so styled values
+/// compose into `format!` and `write!` calls without intermediate Strings.
+///
+/// Formatting options, such as the width in `{:>8}`, apply to the value within the
+/// escape codes. A reset within the value's own text ends the style early, which
+/// [`stylize`] would restore.
+#[derive(Debug, Clone, Copy)]
+pub struct Styled<'a, T: Display> {
+    value: T,
+    opt: &'a StyleOpt,
+}
+
+impl<T: Display> Display for Styled<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(fg) = self.opt.fg.as_ref() {
+            f.write_str(&fg.escape_code_for(ColorSupport::TrueColor))?;
+        }
+        if let Some(bg) = self.opt.bg.as_ref() {
+            f.write_str(&bg.escape_code_for(ColorSupport::TrueColor))?;
+        }
+        if let Some(sgr) = self.opt.sgr.as_ref() {
+            f.write_str(sgr.escape_code())?;
+        }
+        self.value.fmt(f)?;
+
+        f.write_str(Fg::Reset.escape_code())
+    }
+}
+
+/// Display `value` in accordance to the StyleOpt `opt`, without formatting it into
+/// a String first.
+///
+/// ```rust
+/// use grid_printer::style::{styled, stylize, Fg, StyleOpt};
+///
+/// let warn = StyleOpt::new().fg(Fg::Yellow);
+/// let line = format!("{} of {} disks {:>6}", styled(3, &warn), 4, styled("full", &warn));
+/// assert_eq!(line, format!("{} of 4 disks {}", stylize("3", &warn), stylize("  full", &warn)));
+/// ```
+pub fn styled<T: Display>(value: T, opt: &StyleOpt) -> Styled<'_, T> {
+    Styled { value, opt }
+}
+
+/// Append the given &str `s`, styled in accordance to the StyleOpt `opt`, to `buf`.
+pub(crate) fn stylize_into(buf: &mut String, s: &str, opt: &StyleOpt) {
+    opt.push_codes(buf);
+    push_restoring(buf, s, opt);
+    // Note: Using Fg::Reset vs. Bg::Reset makes no difference 
+    buf.push_str(Fg::Reset.escape_code());
+}
+
+/// Print `s` in `opt` layered over `outer`, the style of the text enclosing it,
+/// e.g. a styled cell in a highlighted row, and return to `outer` afterwards rather
+/// than resetting every style. Only the options which differ are written.
+///
+/// ```rust
+/// use grid_printer::style::{stylize_nested, Bg, Fg, StyleOpt};
+///
+/// let row = StyleOpt::new().bg(Bg::Blue);
+/// let error = StyleOpt::new().fg(Fg::Red);
+/// let nested = stylize_nested("failed", &error, &row);
+/// assert_eq!(nested, format!("{}failed\x1b[1;0m{}", Fg::Red.escape_code(), Bg::Blue.escape_code()));
+/// ```
+pub fn stylize_nested(s: &str, opt: &StyleOpt, outer: &StyleOpt) -> String {
+    let mut buf = String::new();
+    stylize_nested_into(&mut buf, s, opt, outer);
+
+    buf
+}
+
+/// Append `s` to `buf` like [`stylize_nested`].
+pub(crate) fn stylize_nested_into(buf: &mut String, s: &str, opt: &StyleOpt, outer: &StyleOpt) {
+    let inner = outer.merge(opt);
+    outer.transition(&inner, buf);
+    push_restoring(buf, s, &inner);
+    inner.transition(outer, buf);
+}
+
+/// Append `s` to `buf`, restoring `opt` after each reset escape sequence kept in
+/// `s`, so that a reset within a styled cell only ends the cell's own styles.
+pub(crate) fn push_restoring(buf: &mut String, s: &str, opt: &StyleOpt) {
+    if !s.contains('\x1b') || opt.is_empty() {
+        return buf.push_str(s);
+    }
+    for (segment, escape) in escape_segments(s) {
+        buf.push_str(segment);
+        if escape && is_reset(segment) {
+            opt.push_codes(buf);
+        }
+    }
+}
+
+/// Whether the SGR escape sequence `segment` resets every style, e.g. `\x1b[0m`.
+fn is_reset(segment: &str) -> bool {
+    match segment.strip_prefix("\x1b[").and_then(|params| params.strip_suffix('m')) {
+        None => false,
+        Some(params) => params.split(';').any(|param| param.is_empty() || param.trim_start_matches('0').is_empty()),
+    }
 }
 
 /// An enumeration of foreground color options.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Fg {
     Black,
     Red,
@@ -121,14 +585,21 @@ pub enum Fg {
     BrightMagenta,
     BrightCyan,
     BrightWhite,
+    /// An entry of the 256-color palette.
+    Fixed(u8),
+    /// A 24-bit RGB color.
+    Rgb(u8, u8, u8),
     Reset,
 }
 
 impl Fg {
 
-    /// A fucntion which will produce the ASCII escape code for a given Fg.
+    /// A fucntion which will produce the ASCII escape code for a given Fg. A
+    /// 256-color or RGB color, which has no static code, produces the code of the
+    /// nearest basic color; see [`escape_code_for`](Fg::escape_code_for).
     pub fn escape_code(&self) -> &'static str {
         match self {
+            Self::Fixed(_) | Self::Rgb(..) => self.downgrade(ColorSupport::Ansi16).escape_code(),
             Self::Black           => "\x1b[1;30m",
             Self::Red             => "\x1b[1;31m",
             Self::Green           => "\x1b[1;32m",
@@ -148,10 +619,82 @@ impl Fg {
             Self::Reset           => "\x1b[1;0m",
         }
     }
+
+    /// The escape code for this color, downgraded to the nearest color available
+    /// under `support`.
+    ///
+    /// ```rust
+    /// use grid_printer::style::{ColorSupport, Fg};
+    ///
+    /// assert_eq!(Fg::Rgb(255, 135, 0).escape_code_for(ColorSupport::TrueColor), "\x1b[1;38;2;255;135;0m");
+    /// assert_eq!(Fg::Rgb(255, 135, 0).escape_code_for(ColorSupport::Ansi256), "\x1b[1;38;5;208m");
+    /// assert_eq!(Fg::Rgb(255, 135, 0).escape_code_for(ColorSupport::Ansi16), Fg::Yellow.escape_code());
+    /// ```
+    pub fn escape_code_for(&self, support: ColorSupport) -> Cow<'static, str> {
+        match self.downgrade(support) {
+            Self::Fixed(n) => Cow::Owned(CsiSequence::sgr().param("1;38;5").param(n).to_string()),
+            Self::Rgb(r, g, b) => Cow::Owned(CsiSequence::sgr().param("1;38;2").param(r).param(g).param(b).to_string()),
+            color => Cow::Borrowed(color.escape_code()),
+        }
+    }
+
+    /// The basic ANSI color at `idx`, in the order of the variants.
+    fn ansi16(idx: usize) -> Self {
+        [
+            Self::Black, Self::Red, Self::Green, Self::Yellow,
+            Self::Blue, Self::Magenta, Self::Cyan, Self::White,
+            Self::BrightBlack, Self::BrightRed, Self::BrightGreen, Self::BrightYellow,
+            Self::BrightBlue, Self::BrightMagenta, Self::BrightCyan, Self::BrightWhite,
+        ][idx].clone()
+    }
+
+    /// Replace a 256-color or RGB color with the nearest color available under `support`.
+    pub fn downgrade(&self, support: ColorSupport) -> Self {
+        match (self, support) {
+            (Self::Rgb(..), ColorSupport::TrueColor) => self.clone(),
+            (Self::Rgb(r, g, b), ColorSupport::Ansi256) => Self::Fixed(rgb_to_fixed((*r, *g, *b))),
+            (Self::Rgb(r, g, b), _) => Self::ansi16(rgb_to_ansi16((*r, *g, *b))),
+            (Self::Fixed(n), ColorSupport::None | ColorSupport::Ansi16) => Self::ansi16(rgb_to_ansi16(fixed_to_rgb(*n))),
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Parse a foreground color from its snake_case name, e.g. `red`, or its
+/// `#rrggbb` hex code.
+impl FromStr for Fg {
+    type Err = GridPrinterErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "black"           => Ok(Self::Black),
+            "red"             => Ok(Self::Red),
+            "green"           => Ok(Self::Green),
+            "yellow"          => Ok(Self::Yellow),
+            "blue"            => Ok(Self::Blue),
+            "magenta"         => Ok(Self::Magenta),
+            "cyan"            => Ok(Self::Cyan),
+            "white"           => Ok(Self::White),
+            "bright_black"    => Ok(Self::BrightBlack),
+            "bright_red"      => Ok(Self::BrightRed),
+            "bright_green"    => Ok(Self::BrightGreen),
+            "bright_yellow"   => Ok(Self::BrightYellow),
+            "bright_blue"     => Ok(Self::BrightBlue),
+            "bright_magenta"  => Ok(Self::BrightMagenta),
+            "bright_cyan"     => Ok(Self::BrightCyan),
+            "bright_white"    => Ok(Self::BrightWhite),
+            "reset"           => Ok(Self::Reset),
+            name => match parse_hex(name) {
+                Some((r, g, b)) => Ok(Self::Rgb(r, g, b)),
+                None => Err(GridPrinterErr::StyleErr(s.to_string())),
+            },
+        }
+    }
 }
 
 /// An enumeration of background color options.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bg {
     Black,
     Red,
@@ -169,13 +712,20 @@ pub enum Bg {
     BrightMagenta,
     BrightCyan,
     BrightWhite,
+    /// An entry of the 256-color palette.
+    Fixed(u8),
+    /// A 24-bit RGB color.
+    Rgb(u8, u8, u8),
     Reset,
 }
 
 impl Bg {
-    /// A fucntion which will produce the ASCII escape code for a given Bg.
+    /// A fucntion which will produce the ASCII escape code for a given Bg. A
+    /// 256-color or RGB color, which has no static code, produces the code of the
+    /// nearest basic color; see [`escape_code_for`](Bg::escape_code_for).
     pub fn escape_code(&self) -> &'static str {
         match self {
+            Self::Fixed(_) | Self::Rgb(..) => self.downgrade(ColorSupport::Ansi16).escape_code(),
             Self::Black          => "\x1b[1;40m",
             Self::Red            => "\x1b[1;41m",
             Self::Green          => "\x1b[1;42m",
@@ -195,6 +745,77 @@ impl Bg {
             Self::Reset          => "\x1b[1;0m",
         }
     }
+
+    /// The escape code for this color, downgraded to the nearest color available
+    /// under `support`.
+    ///
+    /// ```rust
+    /// use grid_printer::style::{ColorSupport, Bg};
+    ///
+    /// assert_eq!(Bg::Rgb(255, 135, 0).escape_code_for(ColorSupport::TrueColor), "\x1b[1;48;2;255;135;0m");
+    /// assert_eq!(Bg::Rgb(255, 135, 0).escape_code_for(ColorSupport::Ansi256), "\x1b[1;48;5;208m");
+    /// assert_eq!(Bg::Rgb(255, 135, 0).escape_code_for(ColorSupport::Ansi16), Bg::Yellow.escape_code());
+    /// ```
+    pub fn escape_code_for(&self, support: ColorSupport) -> Cow<'static, str> {
+        match self.downgrade(support) {
+            Self::Fixed(n) => Cow::Owned(CsiSequence::sgr().param("1;48;5").param(n).to_string()),
+            Self::Rgb(r, g, b) => Cow::Owned(CsiSequence::sgr().param("1;48;2").param(r).param(g).param(b).to_string()),
+            color => Cow::Borrowed(color.escape_code()),
+        }
+    }
+
+    /// The basic ANSI color at `idx`, in the order of the variants.
+    fn ansi16(idx: usize) -> Self {
+        [
+            Self::Black, Self::Red, Self::Green, Self::Yellow,
+            Self::Blue, Self::Magenta, Self::Cyan, Self::White,
+            Self::BrightBlack, Self::BrightRed, Self::BrightGreen, Self::BrightYellow,
+            Self::BrightBlue, Self::BrightMagenta, Self::BrightCyan, Self::BrightWhite,
+        ][idx].clone()
+    }
+
+    /// Replace a 256-color or RGB color with the nearest color available under `support`.
+    pub fn downgrade(&self, support: ColorSupport) -> Self {
+        match (self, support) {
+            (Self::Rgb(..), ColorSupport::TrueColor) => self.clone(),
+            (Self::Rgb(r, g, b), ColorSupport::Ansi256) => Self::Fixed(rgb_to_fixed((*r, *g, *b))),
+            (Self::Rgb(r, g, b), _) => Self::ansi16(rgb_to_ansi16((*r, *g, *b))),
+            (Self::Fixed(n), ColorSupport::None | ColorSupport::Ansi16) => Self::ansi16(rgb_to_ansi16(fixed_to_rgb(*n))),
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Parse a background color from its snake_case name, e.g. `red`, or its
+/// `#rrggbb` hex code.
+impl FromStr for Bg {
+    type Err = GridPrinterErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "black"           => Ok(Self::Black),
+            "red"             => Ok(Self::Red),
+            "green"           => Ok(Self::Green),
+            "yellow"          => Ok(Self::Yellow),
+            "blue"            => Ok(Self::Blue),
+            "magenta"         => Ok(Self::Magenta),
+            "cyan"            => Ok(Self::Cyan),
+            "white"           => Ok(Self::White),
+            "bright_black"    => Ok(Self::BrightBlack),
+            "bright_red"      => Ok(Self::BrightRed),
+            "bright_green"    => Ok(Self::BrightGreen),
+            "bright_yellow"   => Ok(Self::BrightYellow),
+            "bright_blue"     => Ok(Self::BrightBlue),
+            "bright_magenta"  => Ok(Self::BrightMagenta),
+            "bright_cyan"     => Ok(Self::BrightCyan),
+            "bright_white"    => Ok(Self::BrightWhite),
+            "reset"           => Ok(Self::Reset),
+            name => match parse_hex(name) {
+                Some((r, g, b)) => Ok(Self::Rgb(r, g, b)),
+                None => Err(GridPrinterErr::StyleErr(s.to_string())),
+            },
+        }
+    }
 }
 
 /*
@@ -212,6 +833,7 @@ impl Bg {
 /// The total list of Select Graphic Renditions were trimmed down to those
 /// styles which have general, wide support.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Sgr {
     Bold,
     Faint,
@@ -235,3 +857,20 @@ impl Sgr {
 
     }
 }
+
+/// Parse a Select Graphic Rendition from its snake_case name, e.g. `faint`.
+impl FromStr for Sgr {
+    type Err = GridPrinterErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "bold"            => Ok(Self::Bold),
+            "faint"           => Ok(Self::Faint),
+            "italic"          => Ok(Self::Italic),
+            "underline"       => Ok(Self::Underline),
+            "strike_through"  => Ok(Self::StrikeThrough),
+            "reset"           => Ok(Self::Reset),
+            _ => Err(GridPrinterErr::StyleErr(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,341 @@
+//! The text module provides the clean-up applied to cell values before they are
+//! measured and rendered, such as expanding tab characters.
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::ansi::CsiSequence;
+use crate::width::{self, EmojiWidth};
+
+/// How tab characters in cell values are handled.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tabs {
+    /// Expand each tab with spaces up to the next multiple of the given width,
+    /// measured from the start of the cell.
+    Expand(usize),
+    /// Replace each tab with the given text.
+    Replace(String),
+}
+
+impl Tabs {
+
+    /// Apply this tab policy to `s`.
+    ///
+    /// ```rust
+    /// use grid_printer::text::Tabs;
+    /// use grid_printer::width::EmojiWidth;
+    ///
+    /// assert_eq!(Tabs::Expand(4).apply("a\tbc\td", EmojiWidth::Two), "a   bc  d");
+    /// assert_eq!(Tabs::Replace(" ".to_string()).apply("a\tb", EmojiWidth::Two), "a b");
+    /// ```
+    pub fn apply(&self, s: &str, emoji_width: EmojiWidth) -> String {
+        match self {
+            Tabs::Replace(replacement) => s.replace('\t', replacement),
+            Tabs::Expand(tab_width) => {
+                let mut expanded = String::with_capacity(s.len());
+                let mut col = 0;
+                for (cluster, cluster_width) in width::clusters(s, emoji_width) {
+                    match cluster {
+                        "\t" => {
+                            let n = match *tab_width {
+                                0 => 0,
+                                tab_width => tab_width - col % tab_width,
+                            };
+                            expanded.extend(core::iter::repeat_n(' ', n));
+                            col += n;
+                        },
+                        "\n" => {
+                            expanded.push('\n');
+                            col = 0;
+                        },
+                        _ => {
+                            expanded.push_str(cluster);
+                            col += cluster_width;
+                        },
+                    }
+                }
+
+                expanded
+            },
+        }
+    }
+}
+
+/// How control characters in cell values, such as `\r` or `\x1b`, are handled.
+///
+/// Control characters move the cursor or start escape sequences, so messy or
+/// malicious data containing them can corrupt the table or inject escape
+/// sequences into logs.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlChars {
+    /// Leave control characters untouched.
+    #[default]
+    Keep,
+    /// Remove control characters, along with the whole of any ANSI escape sequence.
+    Strip,
+    /// Replace control characters with a visible escape, e.g. `\r` or `\u{1b}`.
+    Escape,
+    /// Keep the escape sequences which style text, e.g. `\x1b[31m`, measuring them as
+    /// zero columns wide, and remove every other control character like
+    /// [`Strip`](ControlChars::Strip). A style left open at the end of a cell, or of
+    /// the part of a cell kept when it is truncated, is closed with a reset so that
+    /// it can't bleed into the padding, the truncation marker, or later cells.
+    KeepStyles,
+}
+
+impl ControlChars {
+
+    /// Apply this control character policy to `s`.
+    ///
+    /// ```rust
+    /// use grid_printer::text::ControlChars;
+    ///
+    /// assert_eq!(ControlChars::Strip.apply("\x1b[31mred\x1b[0m\r"), "red");
+    /// assert_eq!(ControlChars::Escape.apply("a\rb"), "a\\rb");
+    /// assert_eq!(ControlChars::KeepStyles.apply("\x1b[31mred\x1b[2J"), "\x1b[31mred\x1b[0m");
+    /// ```
+    pub fn apply(&self, s: &str) -> String {
+        let mut sanitized = String::with_capacity(s.len());
+        if *self == ControlChars::KeepStyles {
+            for (segment, escape) in escape_segments(s) {
+                match escape {
+                    true if segment.ends_with('m') => sanitized.push_str(segment),
+                    true => (),
+                    false => sanitized.extend(segment.chars().filter(|c| !c.is_control())),
+                }
+            }
+            close_styles(&mut sanitized);
+            return sanitized;
+        }
+
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !c.is_control() {
+                sanitized.push(c);
+                continue;
+            }
+            match self {
+                ControlChars::Keep => sanitized.push(c),
+                ControlChars::Escape => sanitized.extend(c.escape_default()),
+                ControlChars::Strip | ControlChars::KeepStyles => {
+                    // Skip the parameters and final byte of a CSI sequence, e.g. `\x1b[1;31m`.
+                    if c == '\x1b' && chars.peek() == Some(&'[') {
+                        chars.next();
+                        for c in chars.by_ref() {
+                            if ('\x40'..='\x7e').contains(&c) {
+                                break;
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        sanitized
+    }
+}
+
+/// Split `s` into runs of text and whole CSI escape sequences, e.g. `\x1b[1;31m`,
+/// flagging the escape sequences.
+pub(crate) fn escape_segments(s: &str) -> impl Iterator<Item = (&str, bool)> {
+    let mut rest = s;
+    core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = match rest.strip_prefix("\x1b[") {
+            Some(params) => match params.find(|c| ('\x40'..='\x7e').contains(&c)) {
+                Some(i) => 2 + i + 1,
+                None => rest.len(),
+            },
+            None => {
+                let first = rest.chars().next().map_or(1, char::len_utf8);
+                rest[first..].find("\x1b[").map_or(rest.len(), |i| i + first)
+            },
+        };
+        let (segment, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some((segment, segment.starts_with("\x1b[")))
+    })
+}
+
+/// Append a reset to `s` if the last style escape sequence in it is not one.
+fn close_styles(s: &mut String) {
+    let open = escape_segments(s)
+        .filter(|(segment, escape)| *escape && segment.ends_with('m'))
+        .last()
+        .is_some_and(|(segment, _)| segment != "\x1b[0m" && segment != "\x1b[m");
+    if open {
+        s.push_str(&CsiSequence::sgr_reset().to_string());
+    }
+}
+
+/// The number of columns `s` occupies, excluding its escape sequences.
+pub(crate) fn styled_width(s: &str, emoji_width: EmojiWidth) -> usize {
+    escape_segments(s)
+        .filter(|(_, escape)| !escape)
+        .map(|(segment, _)| width::display_width(segment, emoji_width))
+        .sum()
+}
+
+/// Cut `s` down to at most `width` columns like [`width::truncate`], keeping the
+/// escape sequences before the cut and closing any style they leave open.
+pub(crate) fn truncate_styled(s: &str, width: usize, emoji_width: EmojiWidth) -> (String, usize) {
+    let mut cut = String::with_capacity(s.len());
+    let mut used = 0;
+    for (segment, escape) in escape_segments(s) {
+        if escape {
+            cut.push_str(segment);
+            continue;
+        }
+        let (text, text_width) = width::truncate(segment, width - used, emoji_width);
+        cut.push_str(text);
+        used += text_width;
+        if text.len() < segment.len() {
+            break;
+        }
+    }
+    close_styles(&mut cut);
+
+    (cut, used)
+}
+
+/// The ASCII equivalent of a box-drawing, ellipsis, check-mark, bullet, arrow, or
+/// block glyph, if `c` is one. The equivalent is always a single column wide.
+pub fn ascii_glyph(c: char) -> Option<char> {
+    match c {
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '═' | '╌' | '╍' | '╴' | '╶' | '╸' | '╺' | '╼' | '╾' => Some('-'),
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '║' | '╎' | '╏' | '╵' | '╷' | '╹' | '╻' | '╽' | '╿' => Some('|'),
+        '\u{2500}'..='\u{257f}' => Some('+'),
+        '…' => Some('~'),
+        '✓' | '✔' => Some('v'),
+        '✗' | '✘' => Some('x'),
+        '●' | '•' => Some('*'),
+        '○' => Some('o'),
+        '▲' => Some('^'),
+        '▼' => Some('v'),
+        '█' | '▓' => Some('#'),
+        '\u{2589}'..='\u{258f}' => Some('='),
+        '░' | '▒' => Some('.'),
+        _ => None,
+    }
+}
+
+/// Replace the glyphs in `s` which have an [`ascii_glyph`] equivalent, for dumb
+/// terminals, CI logs, and serial consoles. Other text is left untouched.
+///
+/// ```rust
+/// use grid_printer::text::ascii_glyphs;
+///
+/// assert_eq!(ascii_glyphs("├─ café ✓ …"), "+- café v ~");
+/// ```
+pub fn ascii_glyphs(s: &str) -> Cow<'_, str> {
+    match s.chars().any(|c| ascii_glyph(c).is_some()) {
+        true => Cow::Owned(s.chars().map(|c| ascii_glyph(c).unwrap_or(c)).collect()),
+        false => Cow::Borrowed(s),
+    }
+}
+
+/// Wrap `s` into lines of at most `width` columns, breaking at spaces or after the
+/// hyphens within words where it can, and cutting words wider than a line.
+#[cfg(not(feature = "textwrap"))]
+pub(crate) fn wrap(s: &str, width: usize, emoji_width: EmojiWidth) -> Vec<Cow<'_, str>> {
+    let mut lines = Vec::new();
+    let mut rest = s.trim_end();
+    while width > 0 && width::display_width(rest, emoji_width) > width {
+        let (fit, _) = width::truncate(rest, width, emoji_width);
+        let space = fit.rfind(' ').filter(|i| *i > 0);
+        let hyphen = fit.rfind('-')
+            .filter(|i| fit[..*i].ends_with(char::is_alphanumeric))
+            .map(|i| i + 1);
+        let cut = match rest[fit.len()..].starts_with(' ') {
+            true => fit.len(),
+            false => match space.max(hyphen) {
+                Some(cut) => cut,
+                // Note: A cluster wider than the line is cut whole, so the wrap ends.
+                None if fit.is_empty() => rest.chars().next().map_or(rest.len(), char::len_utf8),
+                None => fit.len(),
+            },
+        };
+        lines.push(Cow::Borrowed(rest[..cut].trim_end()));
+        rest = rest[cut..].trim_start();
+    }
+    lines.push(Cow::Borrowed(rest));
+
+    lines
+}
+
+/// Wrap `s` into lines of at most `width` columns with `textwrap`, which breaks
+/// lines at the opportunities found by the Unicode line breaking algorithm, e.g.
+/// after hyphens and slashes, and cuts words wider than a line.
+///
+/// Words are measured as the rest of the grid measures them, so emoji take up
+/// `emoji_width` columns. Words wider than a line are cut without a hyphen, as
+/// textwrap's hyphenation dictionaries are not enabled.
+#[cfg(feature = "textwrap")]
+pub(crate) fn wrap(s: &str, width: usize, emoji_width: EmojiWidth) -> Vec<Cow<'_, str>> {
+    use textwrap::core::Word;
+    use textwrap::WordSplitter;
+
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for line in s.split('\n') {
+        let mut words = Vec::new();
+        let found = textwrap::WordSeparator::new().find_words(line);
+        for mut word in textwrap::word_splitters::split_words(found, &WordSplitter::HyphenSplitter) {
+            word.width = width::display_width(word.word, emoji_width);
+            while word.width > width {
+                let (fit, fit_width) = width::truncate(word.word, width, emoji_width);
+                // Note: A cluster wider than the line is cut whole.
+                let (cut, fit_width) = match fit.is_empty() {
+                    true => {
+                        let cut = word.word.chars().next().map_or(word.word.len(), char::len_utf8);
+                        (cut, width::display_width(&word.word[..cut], emoji_width))
+                    },
+                    false => (fit.len(), fit_width),
+                };
+                words.push(Word { word: &word.word[..cut], width: fit_width, whitespace: "", penalty: "" });
+                word.word = &word.word[cut..];
+                word.width -= fit_width;
+            }
+            words.push(word);
+        }
+
+        // Note: The words are contiguous in `line`, so each wrapped line is a slice of it.
+        let mut start = 0;
+        let line_widths = [width];
+        let wrapped = textwrap::WrapAlgorithm::new().wrap(&words, &line_widths);
+        for words in wrapped.iter() {
+            let len: usize = words.iter().map(|word| word.word.len() + word.whitespace.len()).sum();
+            lines.push(Cow::Borrowed(line[start..start + len].trim_end_matches(' ')));
+            start += len;
+        }
+        if wrapped.is_empty() {
+            lines.push(Cow::Borrowed(""));
+        }
+    }
+
+    lines
+}
+
+/// Cut `s` into lines of `width` columns, the last of them shorter, keeping all of
+/// its whitespace, for text such as code whose spacing is meaningful.
+pub(crate) fn cut_lines(s: &str, width: usize, emoji_width: EmojiWidth) -> Vec<Cow<'_, str>> {
+    let mut lines = Vec::new();
+    let mut rest = s;
+    while width > 0 && width::display_width(rest, emoji_width) > width {
+        let (fit, _) = width::truncate(rest, width, emoji_width);
+        let cut = match fit.is_empty() {
+            true => rest.chars().next().map_or(rest.len(), char::len_utf8),
+            false => fit.len(),
+        };
+        lines.push(Cow::Borrowed(&rest[..cut]));
+        rest = &rest[cut..];
+    }
+    lines.push(Cow::Borrowed(rest));
+
+    lines
+}
@@ -0,0 +1,93 @@
+//! The tree module provides [`TreeColumn`], which draws hierarchical data in the
+//! first column of a grid with branch glyphs, while the remaining columns stay
+//! aligned, e.g. a process tree with metrics columns.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::tree::TreeColumn;
+//!
+//! let procs = vec![
+//!     vec!["init", "0.1"],
+//!     vec!["sshd", "0.0"],
+//!     vec!["bash", "0.3"],
+//!     vec!["cron", "0.0"],
+//! ];
+//! let tree = TreeColumn::new(vec![0, 1, 2, 1]);
+//! let rows = tree.apply(&procs);
+//! let printer = GridPrinter::builder(rows.len(), 2).col_spacing(1).build();
+//! assert_eq!(printer.render_plain(&rows), concat!(
+//!     "init       0.1\n",
+//!     "├─ sshd    0.0\n",
+//!     "│  └─ bash 0.3\n",
+//!     "└─ cron    0.0\n",
+//! ));
+//! ```
+
+use core::fmt::Display;
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::string::{String, ToString};
+
+/// The depth of each row of a tree, from which the first column is indented and
+/// given branch glyphs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeColumn {
+    depths: Vec<usize>,
+}
+
+impl TreeColumn {
+
+    /// Create a TreeColumn from the depth of each row, where `0` is a root. Each row
+    /// is a child of the closest row above it which is one level shallower. A
+    /// header row, if any, should be given a depth of `0`.
+    pub fn new(depths: Vec<usize>) -> Self {
+        Self { depths }
+    }
+
+    /// Whether the row `idx`'s ancestor at `depth`, or the row itself, is followed
+    /// by a sibling.
+    fn has_next_sibling(&self, idx: usize, depth: usize) -> bool {
+        self.depths[idx + 1..].iter()
+            .find(|next| **next <= depth)
+            .is_some_and(|next| *next == depth)
+    }
+
+    /// The indentation and branch glyphs drawn before the first cell of each row.
+    pub fn prefixes(&self) -> Vec<String> {
+        self.depths.iter()
+            .enumerate()
+            .map(|(idx, depth)| {
+                let mut prefix = String::new();
+                for level in 1..=*depth {
+                    let glyph = match (level == *depth, self.has_next_sibling(idx, level)) {
+                        (false, true) => "│  ",
+                        (false, false) => "   ",
+                        (true, true) => "├─ ",
+                        (true, false) => "└─ ",
+                    };
+                    prefix.push_str(glyph);
+                }
+                prefix
+            })
+            .collect()
+    }
+
+    /// Format the rows of `source`, prefixing the first cell of each row with its
+    /// indentation and branch glyphs. Rows beyond the given depths are left as
+    /// they are.
+    pub fn apply<F: Display>(&self, source: &[Vec<F>]) -> Vec<Vec<String>> {
+        let prefixes = self.prefixes();
+        source.iter()
+            .enumerate()
+            .map(|(idx, row)| row.iter()
+                .enumerate()
+                .map(|(col_idx, cell)| match (col_idx, prefixes.get(idx)) {
+                    (0, Some(prefix)) => format!("{}{}", prefix, cell),
+                    _ => cell.to_string(),
+                })
+                .collect())
+            .collect()
+    }
+}
@@ -0,0 +1,158 @@
+//! An adapter from a GridPrinter to a [`ratatui`] table, enabled by the `ratatui`
+//! feature, so a terminal UI and a plain CLI can share one table definition.
+//!
+//! # Example
+//! ```rust
+//! use grid_printer::GridPrinter;
+//! use grid_printer::style::{Fg, StyleOpt};
+//!
+//! let grid = vec![
+//!     vec!["Name", "Size"],
+//!     vec!["a.txt", "1K"],
+//! ];
+//! let printer = GridPrinter::builder(2, 2)
+//!     .header(true)
+//!     .col_style(1, StyleOpt::new().fg(Fg::Cyan)).unwrap()
+//!     .build();
+//!
+//! // Drawn by the CLI...
+//! printer.print(&grid);
+//! // ...and by the TUI, e.g. with `frame.render_widget(table, area)`.
+//! let table = printer.to_table(&grid);
+//! ```
+
+use std::fmt::Display;
+
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Cell, Row, Table};
+
+use crate::{GridPrinter, StructuredCell};
+use crate::style::{Bg, Fg, Sgr, StyleOpt};
+
+impl GridPrinter {
+
+    /// Lay out `source` as [`render_structured`](GridPrinter::render_structured)
+    /// does, and build a [`ratatui::widgets::Table`] of the printed columns with the
+    /// same widths, spacing, header, and styles.
+    pub fn to_table<F: Display>(&self, source: &[Vec<F>]) -> Table<'static> {
+        let structured = self.render_structured(source);
+        let to_row = |cells: Vec<StructuredCell>| -> Row<'static> {
+            Row::new(cells.into_iter().map(|cell| {
+                let style = cell.style.as_ref().map(Style::from).unwrap_or_default();
+                Cell::from(cell.text).style(style)
+            }))
+        };
+
+        let widths: Vec<Constraint> = structured.layout.col_widths.iter()
+            .map(|width| Constraint::Length(*width as u16))
+            .collect();
+        let rows: Vec<Row> = structured.rows.into_iter().map(to_row).collect();
+        let table = Table::new(rows, widths).column_spacing(self.col_spacing as u16);
+
+        match structured.header {
+            None => table,
+            Some(header) => table.header(to_row(header)),
+        }
+    }
+}
+
+impl From<&Fg> for Color {
+    fn from(fg: &Fg) -> Self {
+        match fg {
+            Fg::Black => Color::Black,
+            Fg::Red => Color::Red,
+            Fg::Green => Color::Green,
+            Fg::Yellow => Color::Yellow,
+            Fg::Blue => Color::Blue,
+            Fg::Magenta => Color::Magenta,
+            Fg::Cyan => Color::Cyan,
+            Fg::White => Color::Gray,
+            Fg::BrightBlack => Color::DarkGray,
+            Fg::BrightRed => Color::LightRed,
+            Fg::BrightGreen => Color::LightGreen,
+            Fg::BrightYellow => Color::LightYellow,
+            Fg::BrightBlue => Color::LightBlue,
+            Fg::BrightMagenta => Color::LightMagenta,
+            Fg::BrightCyan => Color::LightCyan,
+            Fg::BrightWhite => Color::White,
+            Fg::Fixed(n) => Color::Indexed(*n),
+            Fg::Rgb(r, g, b) => Color::Rgb(*r, *g, *b),
+            Fg::Reset => Color::Reset,
+        }
+    }
+}
+
+impl From<&Bg> for Color {
+    fn from(bg: &Bg) -> Self {
+        match bg {
+            Bg::Black => Color::Black,
+            Bg::Red => Color::Red,
+            Bg::Green => Color::Green,
+            Bg::Yellow => Color::Yellow,
+            Bg::Blue => Color::Blue,
+            Bg::Magenta => Color::Magenta,
+            Bg::Cyan => Color::Cyan,
+            Bg::White => Color::Gray,
+            Bg::BrightBlack => Color::DarkGray,
+            Bg::BrightRed => Color::LightRed,
+            Bg::BrightGreen => Color::LightGreen,
+            Bg::BrightYellow => Color::LightYellow,
+            Bg::BrightBlue => Color::LightBlue,
+            Bg::BrightMagenta => Color::LightMagenta,
+            Bg::BrightCyan => Color::LightCyan,
+            Bg::BrightWhite => Color::White,
+            Bg::Fixed(n) => Color::Indexed(*n),
+            Bg::Rgb(r, g, b) => Color::Rgb(*r, *g, *b),
+            Bg::Reset => Color::Reset,
+        }
+    }
+}
+
+impl From<&StyleOpt> for Style {
+    fn from(style_opt: &StyleOpt) -> Self {
+        let mut style = Style::default();
+        if let Some(fg) = style_opt.fg.as_ref() {
+            style = style.fg(fg.into());
+        }
+        if let Some(bg) = style_opt.bg.as_ref() {
+            style = style.bg(bg.into());
+        }
+        let modifier = match style_opt.sgr {
+            None | Some(Sgr::Reset) => Modifier::empty(),
+            Some(Sgr::Bold) => Modifier::BOLD,
+            Some(Sgr::Faint) => Modifier::DIM,
+            Some(Sgr::Italic) => Modifier::ITALIC,
+            Some(Sgr::Underline) => Modifier::UNDERLINED,
+            Some(Sgr::StrikeThrough) => Modifier::CROSSED_OUT,
+        };
+
+        style.add_modifier(modifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+
+    #[test]
+    fn test_to_table() {
+        let v = vec![vec!["Name", "Size"], vec!["a.txt", "1K"]];
+        let printer = GridPrinter::builder(2, 2)
+            .header(true)
+            .col_style(1, StyleOpt::new().fg(Fg::Cyan)).unwrap()
+            .build();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 13, 2));
+        printer.to_table(&v).render(buf.area, &mut buf);
+        assert_eq!(buf, {
+            let mut expected = Buffer::with_lines(["Name   Size  ", "a.txt  1K    "]);
+            expected.set_style(Rect::new(7, 0, 4, 2), Style::new().fg(Color::Cyan));
+            expected
+        });
+    }
+}
@@ -0,0 +1,243 @@
+//! The watch module provides helpers for grids printed repeatedly in a
+//! `watch`-style loop: [`WidthMemory`], a GridPrinter which remembers the widest
+//! value seen in each column across prints, so the columns only ever grow instead
+//! of jittering, and [`RowCache`], which keeps the formatted cells of unchanged
+//! rows between refreshes.
+//!
+//! # Example
+//! ```rust,no_run
+//! use grid_printer::GridPrinter;
+//! use grid_printer::watch::WidthMemory;
+//!
+//! let printer = GridPrinter::builder(2, 2).header(true).build();
+//! let mut watch = WidthMemory::new(printer);
+//! for tick in 0..100u64 {
+//!     let grid = vec![
+//!         vec!["tick".to_string(), "value".to_string()],
+//!         vec![tick.to_string(), (tick * tick).to_string()],
+//!     ];
+//!     print!("\x1b[2J\x1b[H");
+//!     watch.print(&grid);
+//! }
+//! ```
+
+use core::fmt::Display;
+use alloc::format;
+use alloc::vec::Vec;
+use alloc::string::String;
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::GridPrinter;
+use crate::sink::Sink;
+#[cfg(feature = "std")]
+use crate::sink::IoSink;
+
+/// A GridPrinter whose column widths never shrink between prints.
+#[derive(Debug, Clone)]
+pub struct WidthMemory {
+    printer: GridPrinter,
+    widths: Vec<usize>,
+}
+
+impl WidthMemory {
+
+    /// Create a WidthMemory printing with `printer`, with no widths remembered yet.
+    pub fn new(printer: GridPrinter) -> Self {
+        Self {
+            printer,
+            widths: Vec::new(),
+        }
+    }
+
+    /// The underlying GridPrinter.
+    pub fn printer(&self) -> &GridPrinter {
+        &self.printer
+    }
+
+    /// The widest value seen in each column so far.
+    pub fn widths(&self) -> &[usize] {
+        &self.widths
+    }
+
+    /// Forget the remembered widths, e.g. when the data set changes, so the next
+    /// print is measured from scratch.
+    pub fn reset(&mut self) {
+        self.widths.clear();
+    }
+
+    /// Render `source` into a [`Sink`], one line at a time, with each column at
+    /// least as wide as it has been in any earlier render.
+    pub fn render_into<S: Sink, F: Display>(&mut self, sink: &mut S, source: &[Vec<F>]) -> Result<(), S::Error> {
+        let printer = &self.printer;
+        let opts = &printer.print_options;
+        let buff = printer.format_grid(source, opts);
+
+        let max_widths = printer.max_widths(&buff);
+        self.widths.resize(max_widths.len(), 0);
+        for (remembered, width) in self.widths.iter_mut().zip(max_widths) {
+            *remembered = (*remembered).max(width);
+        }
+
+        printer.render_lines_with(&buff, self.widths.clone(), opts, |line| sink.write_line_ending(line, printer.line_ending.as_str()))
+            .map(|_| ())
+    }
+
+    /// Render `source` into a `String`, remembering its column widths.
+    ///
+    /// ```rust
+    /// use grid_printer::GridPrinter;
+    /// use grid_printer::watch::WidthMemory;
+    ///
+    /// let mut watch = WidthMemory::new(GridPrinter::new(1, 2));
+    /// assert_eq!(watch.render(&vec![vec!["100", "a"]]), "100  a  \n");
+    /// assert_eq!(watch.render(&vec![vec!["9", "a"]]), "9    a  \n");
+    ///
+    /// watch.reset();
+    /// assert_eq!(watch.render(&vec![vec!["9", "a"]]), "9  a  \n");
+    /// ```
+    pub fn render<F: Display>(&mut self, source: &[Vec<F>]) -> String {
+        let mut buf = String::new();
+        let Ok(()) = self.render_into(&mut buf, source);
+
+        buf
+    }
+
+    /// Print `source` to stdout, remembering its column widths.
+    #[cfg(feature = "std")]
+    pub fn print<F: Display>(&mut self, source: &[Vec<F>]) {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_into(&mut sink, source).unwrap();
+        sink.0.flush().unwrap();
+    }
+}
+
+/// A memo of formatted rows keyed by row identity, so that cells with expensive
+/// `Display` impls are only formatted when their row is new or changed.
+///
+/// The key identifies a row's content, e.g. an id together with a version or
+/// modification time; a row whose key was seen in the previous render reuses the
+/// cells formatted then. Rows absent from a render are evicted from the cache.
+///
+/// ```rust
+/// use std::cell::Cell;
+/// use std::fmt;
+/// use grid_printer::GridPrinter;
+/// use grid_printer::watch::RowCache;
+///
+/// struct Costly<'a>(u64, &'a Cell<usize>);
+///
+/// impl fmt::Display for Costly<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         self.1.set(self.1.get() + 1);
+///         write!(f, "{}", self.0)
+///     }
+/// }
+///
+/// let calls = Cell::new(0);
+/// let source = vec![vec![Costly(1, &calls), Costly(2, &calls)]];
+/// let printer = GridPrinter::new(source.len(), 2);
+/// let mut cache = RowCache::new();
+///
+/// // The rows never change here, so their index identifies them.
+/// assert_eq!(cache.render(&printer, &source, |i, _| i), "1  2  \n");
+/// assert_eq!(cache.render(&printer, &source, |i, _| i), "1  2  \n");
+/// assert_eq!(calls.get(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RowCache<K> {
+    rows: BTreeMap<K, Vec<String>>,
+}
+
+impl<K: Ord + Clone> RowCache<K> {
+
+    /// Create a new, empty RowCache.
+    pub fn new() -> Self {
+        Self { rows: BTreeMap::new() }
+    }
+
+    /// The number of rows cached.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether no rows are cached.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Forget every cached row.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    /// Format the rows of `source` not already cached under the key `key` gives for
+    /// each row and its index, evicting the rows no longer present. Returns the keys
+    /// in source order.
+    fn update<F, R, KF>(&mut self, source: &[R], key: KF) -> Vec<K>
+    where
+        F: Display,
+        R: AsRef<[F]>,
+        KF: Fn(usize, &R) -> K,
+    {
+        let mut previous = core::mem::take(&mut self.rows);
+        let keys: Vec<K> = source.iter().enumerate().map(|(i, row)| key(i, row)).collect();
+        for (k, row) in keys.iter().zip(source) {
+            if self.rows.contains_key(k) {
+                continue;
+            }
+            let cells = previous.remove(k).unwrap_or_else(|| {
+                row.as_ref().iter().map(|cell| format!("{}", cell)).collect()
+            });
+            self.rows.insert(k.clone(), cells);
+        }
+
+        keys
+    }
+
+    /// Render `source` into a [`Sink`] with `printer`, one line at a time, formatting
+    /// only the rows whose key was not seen in the previous render.
+    pub fn render_into<S, F, R, KF>(&mut self, printer: &GridPrinter, sink: &mut S, source: &[R], key: KF) -> Result<(), S::Error>
+    where
+        S: Sink,
+        F: Display,
+        R: AsRef<[F]>,
+        KF: Fn(usize, &R) -> K,
+    {
+        let keys = self.update(source, key);
+        let grid: Vec<Vec<&str>> = keys.iter()
+            .map(|k| self.rows[k].iter().map(String::as_str).collect())
+            .collect();
+
+        printer.render_cells_into(sink, &grid)
+    }
+
+    /// Render `source` into a `String` with `printer`, reusing the cached rows.
+    pub fn render<F, R, KF>(&mut self, printer: &GridPrinter, source: &[R], key: KF) -> String
+    where
+        F: Display,
+        R: AsRef<[F]>,
+        KF: Fn(usize, &R) -> K,
+    {
+        let mut buf = String::new();
+        let Ok(()) = self.render_into(printer, &mut buf, source, key);
+
+        buf
+    }
+
+    /// Print `source` to stdout with `printer`, reusing the cached rows.
+    #[cfg(feature = "std")]
+    pub fn print<F, R, KF>(&mut self, printer: &GridPrinter, source: &[R], key: KF)
+    where
+        F: Display,
+        R: AsRef<[F]>,
+        KF: Fn(usize, &R) -> K,
+    {
+        let stdout = io::stdout();
+        let mut sink = IoSink(stdout.lock());
+        self.render_into(printer, &mut sink, source, key).unwrap();
+        sink.0.flush().unwrap();
+    }
+}
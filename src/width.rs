@@ -0,0 +1,189 @@
+//! The width module measures how many terminal columns text occupies, treating
+//! wide characters, combining marks, and emoji sequences (ZWJ sequences, flags,
+//! skin tones, keycaps, and variation selectors) as single units, so that one
+//! emoji doesn't shift every later column.
+
+use core::fmt;
+use core::fmt::Display;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::raw;
+
+/// The number of columns a terminal uses to draw a wide emoji.
+///
+/// Most terminals draw emoji two columns wide, which is the default. Some older
+/// terminals disagree and draw them one column wide.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmojiWidth {
+    One,
+    #[default]
+    Two,
+}
+
+const ZWJ: char = '\u{200D}';
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+fn is_skin_tone(c: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+/// Whether a two column wide cluster is drawn as an emoji.
+fn is_emoji(cluster: &str) -> bool {
+    cluster.chars().any(|c| matches!(c,
+        '\u{FE0F}' | '\u{20E3}' | ZWJ
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{1F000}'..='\u{1FAFF}'
+    ))
+}
+
+/// Split `s` into clusters of characters which are drawn as a single unit, along
+/// with the number of columns each cluster occupies.
+pub(crate) fn clusters(s: &str, emoji_width: EmojiWidth) -> impl Iterator<Item = (&str, usize)> {
+    let mut rest = s;
+    core::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut prev = first;
+        let mut regional_indicators = is_regional_indicator(first) as usize;
+        let mut end = rest.len();
+        for (i, c) in chars {
+            let joins = c.width() == Some(0)
+                || prev == ZWJ
+                || is_skin_tone(c)
+                || (is_regional_indicator(c) && regional_indicators == 1);
+            if !joins {
+                end = i;
+                break;
+            }
+            regional_indicators += is_regional_indicator(c) as usize;
+            prev = c;
+        }
+
+        let (cluster, remaining) = rest.split_at(end);
+        rest = remaining;
+        let width = match UnicodeWidthStr::width(cluster) {
+            2 if emoji_width == EmojiWidth::One && is_emoji(cluster) => 1,
+            width => width,
+        };
+        Some((cluster, width))
+    })
+}
+
+/// The number of columns `s` occupies.
+///
+/// ```rust
+/// use grid_printer::width::{display_width, EmojiWidth};
+///
+/// assert_eq!(display_width("héllo", EmojiWidth::Two), 5);
+/// assert_eq!(display_width("中文", EmojiWidth::Two), 4);
+/// assert_eq!(display_width("👨‍👩‍👧 🇺🇸", EmojiWidth::Two), 5);
+/// assert_eq!(display_width("👨‍👩‍👧 🇺🇸", EmojiWidth::One), 3);
+/// ```
+pub fn display_width(s: &str, emoji_width: EmojiWidth) -> usize {
+    clusters(s, emoji_width).map(|(_, width)| width).sum()
+}
+
+/// A `fmt::Write` adapter which measures the display width of the text written to
+/// it without keeping the text, as long as it is printable ASCII. From the first
+/// other character on, the text is kept and measured when finished.
+#[derive(Default)]
+struct WidthCounter {
+    width: usize,
+    last: Option<char>,
+    rest: Option<String>,
+}
+
+impl fmt::Write for WidthCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if let Some(rest) = self.rest.as_mut() {
+            rest.push_str(s);
+        } else if s.bytes().all(|b| (b' '..=b'~').contains(&b)) {
+            self.width += s.len();
+            self.last = s.chars().last().or(self.last);
+        } else {
+            // Note: The last ASCII character may join a cluster with what follows,
+            // e.g. the digit of a keycap emoji, so it is measured again with the rest.
+            let mut rest = String::new();
+            if let Some(last) = self.last {
+                self.width -= 1;
+                rest.push(last);
+            }
+            rest.push_str(s);
+            self.rest = Some(rest);
+        }
+
+        Ok(())
+    }
+}
+
+/// The number of columns `el` occupies once formatted, measured without
+/// formatting it into a `String` when it is printable ASCII, e.g. a number. The
+/// characters marking raw text are not counted, as they are never printed.
+pub(crate) fn display_width_of<F: Display + ?Sized>(el: &F, emoji_width: EmojiWidth) -> usize {
+    let mut counter = WidthCounter::default();
+    let _ = fmt::write(&mut counter, format_args!("{}", el));
+
+    counter.width + counter.rest.map_or(0, |rest| display_width(&raw::strip_markers(Cow::Owned(rest)), emoji_width))
+}
+
+/// Cut `s` down to at most `width` columns without splitting a cluster, returning
+/// the remaining text and the number of columns it occupies.
+pub(crate) fn truncate(s: &str, width: usize, emoji_width: EmojiWidth) -> (&str, usize) {
+    let mut end = 0;
+    let mut used = 0;
+    for (cluster, cluster_width) in clusters(s, emoji_width) {
+        if used + cluster_width > width {
+            break;
+        }
+        end += cluster.len();
+        used += cluster_width;
+    }
+
+    (&s[..end], used)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_clusters() {
+        let clusters: alloc::vec::Vec<_> = clusters("e\u{301}❤️👍🏽1️⃣🇺🇸", EmojiWidth::Two).collect();
+        assert_eq!(clusters, vec![
+            ("e\u{301}", 1),
+            ("❤️", 2),
+            ("👍🏽", 2),
+            ("1️⃣", 2),
+            ("🇺🇸", 2),
+        ]);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("a👨‍👩‍👧b", 2, EmojiWidth::Two), ("a", 1));
+        assert_eq!(truncate("a👨‍👩‍👧b", 3, EmojiWidth::Two), ("a👨‍👩‍👧", 3));
+        assert_eq!(truncate("a👨‍👩‍👧b", 3, EmojiWidth::One), ("a👨‍👩‍👧b", 3));
+    }
+
+    #[test]
+    fn test_display_width_of() {
+        struct Keycap;
+        impl Display for Keycap {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("#1")?;
+                f.write_str("\u{FE0F}\u{20E3}")
+            }
+        }
+
+        assert_eq!(display_width_of(&1234.5, EmojiWidth::Two), 6);
+        assert_eq!(display_width_of(&Keycap, EmojiWidth::Two), display_width("#1\u{FE0F}\u{20E3}", EmojiWidth::Two));
+    }
+}